@@ -0,0 +1,202 @@
+//! Derive macros for the DolphinDB Rust API.
+//!
+//! Use these macros through the `derive` feature of `dolphindb` instead of depending on this
+//! crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, LitInt, LitStr, Result,
+};
+
+/// Derives `dolphindb::types::DolphinRow` for structs with named fields.
+///
+/// Each field maps to a column of the same name. Field attributes:
+///
+/// - `#[dolphindb(rename = "name")]`: column name.
+/// - `#[dolphindb(symbol)]`: stores a `String` field as `SYMBOL` instead of `STRING`.
+/// - `#[dolphindb(scale = N)]`: rescales a `Decimal` field.
+/// - `#[dolphindb(precision = "second" | "milli" | "nano")]`: precision of `NaiveTime` and
+///   `NaiveDateTime` fields, `milli` by default.
+/// - `#[dolphindb(precision = N)]`: maximum number of digits of a `Decimal` field, up to 38,
+///   which selects `DECIMAL32`, `DECIMAL64` or `DECIMAL128`. `DECIMAL64` by default.
+#[proc_macro_derive(DolphinRow, attributes(dolphindb))]
+pub fn derive_dolphin_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct Field {
+    ident: syn::Ident,
+    ty: syn::Type,
+    name: String,
+    symbol: bool,
+    scale: Option<u32>,
+    precision: Option<syn::Ident>,
+    decimal_precision: Option<u32>,
+}
+
+impl Field {
+    fn parse(field: &syn::Field) -> Result<Self> {
+        let ident = field.ident.clone().unwrap();
+        let mut parsed = Self {
+            name: ident.to_string(),
+            ident,
+            ty: field.ty.clone(),
+            symbol: false,
+            scale: None,
+            precision: None,
+            decimal_precision: None,
+        };
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("dolphindb"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    parsed.name = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.path.is_ident("symbol") {
+                    parsed.symbol = true;
+                } else if meta.path.is_ident("scale") {
+                    parsed.scale = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+                } else if meta.path.is_ident("precision") {
+                    let value = meta.value()?;
+                    if value.peek(LitInt) {
+                        let lit = value.parse::<LitInt>()?;
+                        let digits = lit.base10_parse()?;
+                        if !(1..=38).contains(&digits) {
+                            return Err(Error::new(lit.span(), "expected 1 to 38 digits"));
+                        }
+                        parsed.decimal_precision = Some(digits);
+                        return Ok(());
+                    }
+                    let lit = value.parse::<LitStr>()?;
+                    let variant = match lit.value().as_str() {
+                        "second" => "Second",
+                        "milli" => "Milli",
+                        "nano" => "Nano",
+                        _ => {
+                            return Err(Error::new(
+                                lit.span(),
+                                "expected `second`, `milli` or `nano`",
+                            ))
+                        }
+                    };
+                    parsed.precision = Some(syn::Ident::new(variant, lit.span()));
+                } else {
+                    return Err(meta.error("unknown dolphindb attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(parsed)
+    }
+
+    fn options(&self) -> TokenStream2 {
+        let symbol = self.symbol;
+        let scale = match self.scale {
+            Some(scale) => quote!(::core::option::Option::Some(#scale)),
+            None => quote!(::core::option::Option::None),
+        };
+        let precision = match &self.precision {
+            Some(precision) => quote!(::dolphindb::types::TimePrecision::#precision),
+            None => quote!(::dolphindb::types::TimePrecision::Milli),
+        };
+        let decimal_precision = match self.decimal_precision {
+            Some(digits) => quote!(::core::option::Option::Some(#digits)),
+            None => quote!(::core::option::Option::None),
+        };
+
+        quote! {
+            ::dolphindb::types::FieldOptions {
+                symbol: #symbol,
+                scale: #scale,
+                precision: #precision,
+                decimal_precision: #decimal_precision,
+            }
+        }
+    }
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "DolphinRow can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "DolphinRow can only be derived for structs",
+            ))
+        }
+    };
+    let fields = fields
+        .iter()
+        .map(Field::parse)
+        .collect::<Result<Vec<_>>>()?;
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let names = fields.iter().map(|f| &f.name);
+    let types = fields.iter().map(|f| {
+        let ty = &f.ty;
+        let options = f.options();
+        quote!(<#ty as ::dolphindb::types::RowField>::field_type(&#options))
+    });
+    let pushes = fields.iter().enumerate().map(|(i, f)| {
+        let field = &f.ident;
+        let options = f.options();
+        quote! {
+            ::dolphindb::types::RowField::push_field(&self.#field, &mut columns[#i], &#options)?;
+        }
+    });
+    let gets = fields.iter().enumerate().map(|(i, f)| {
+        let field = &f.ident;
+        let options = f.options();
+        quote! {
+            #field: ::dolphindb::types::RowField::get_field(columns[#i], index, &#options)?,
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::dolphindb::types::DolphinRow for #ident #ty_generics #where_clause {
+            fn column_names() -> ::std::vec::Vec<::std::string::String> {
+                ::std::vec![#(::std::string::String::from(#names)),*]
+            }
+
+            fn column_types() -> ::std::vec::Vec<::dolphindb::types::DataType> {
+                ::std::vec![#(#types),*]
+            }
+
+            fn push_row(
+                &self,
+                columns: &mut [::dolphindb::types::VectorImpl],
+            ) -> ::dolphindb::error::Result<()> {
+                #(#pushes)*
+                ::core::result::Result::Ok(())
+            }
+
+            fn from_row(
+                columns: &[&::dolphindb::types::VectorImpl],
+                index: usize,
+            ) -> ::dolphindb::error::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    #(#gets)*
+                })
+            }
+        }
+    })
+}
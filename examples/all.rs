@@ -355,7 +355,7 @@ async fn test_table_writer() -> Result<(), Error> {
     // 写入器
     tokio::spawn(async move {
         let mut inserted = 0usize;
-        let mut writer = TableWriter::new(client, stream_table, 512).await;
+        let mut writer = TableWriter::new(client, stream_table, 512).await.unwrap();
         while let Some(event) = rx.recv().await {
             let mut row = build_table_row(&event);
             if let Err(e) = writer.append_row(&mut row).await {
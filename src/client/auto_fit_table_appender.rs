@@ -0,0 +1,97 @@
+use super::{
+    table_writer::{convert_column, fetch_scales, fetch_schema, table_expr},
+    Client,
+};
+use crate::{
+    error::{Error, Result},
+    types::*,
+};
+
+/// Appends whole tables after converting their columns to the types of the target table.
+///
+/// Columns are matched by position and converted with the rules of [`VectorImpl::cast`]:
+/// temporals change precision, decimals are rescaled to the scale of the target column,
+/// strings and symbols are interchangeable, and integers fail on overflow.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dolphindb::client::{AutoFitTableAppender, ClientBuilder};
+/// use dolphindb::types::*;
+/// #[tokio::main]
+/// async fn main() {
+///     let mut builder = ClientBuilder::new("127.0.0.1:8848");
+///     builder.with_auth(("admin", "123456"));
+///     let mut client = builder.connect().await.unwrap();
+///     let mut appender = AutoFitTableAppender::new(&mut client, "dfs://db", "pt")
+///         .await
+///         .unwrap();
+///     let mut prices = DoubleVector::new();
+///     prices.push(Double::new(1.5));
+///     let mut builder = TableBuilder::new();
+///     builder.with_contents(vec![prices.into()], vec!["price".to_string()]);
+///     appender.append(&builder.build().unwrap()).await.unwrap();
+/// }
+/// ```
+pub struct AutoFitTableAppender<'a> {
+    client: &'a mut Client,
+    script: String,
+    column_names: Vec<String>,
+    column_types: Vec<DataType>,
+    scales: Vec<Option<u32>>,
+}
+
+impl<'a> AutoFitTableAppender<'a> {
+    /// Reads the schema of `loadTable(db_path, table_name)`, or of the shared table `table_name` if `db_path` is empty.
+    pub async fn new(client: &'a mut Client, db_path: &str, table_name: &str) -> Result<Self> {
+        let table = table_expr(db_path, table_name);
+
+        let (column_names, column_types) = fetch_schema(client, &table).await?;
+        let scales = fetch_scales(client, &table, &column_types).await?;
+
+        Ok(Self {
+            client,
+            script: format!("tableInsert{{{table}}}"),
+            column_names,
+            column_types,
+            scales,
+        })
+    }
+
+    pub fn column_names(&self) -> &[String] {
+        &self.column_names
+    }
+
+    pub fn column_types(&self) -> &[DataType] {
+        &self.column_types
+    }
+
+    /// Converts the columns of `table` and appends them, returning the result of `tableInsert`.
+    pub async fn append(&mut self, table: &Table) -> Result<Option<ConstantImpl>> {
+        let converted = self.convert(table)?;
+        self.client
+            .run_function(self.script.as_str(), &[converted.into()])
+            .await
+    }
+
+    /// Converts the columns of `table` to the target types without sending them.
+    pub fn convert(&self, table: &Table) -> Result<Table> {
+        if table.columns().len() != self.column_types.len() {
+            return Err(Error::InvalidData {
+                expect: format!("{} columns", self.column_types.len()),
+                actual: format!("{} columns", table.columns().len()),
+            });
+        }
+
+        let columns = table
+            .columns()
+            .iter()
+            .zip(self.column_types.iter().zip(self.scales.iter()))
+            .map(|(column, (data_type, scale))| convert_column(column, *data_type, *scale))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut builder = TableBuilder::new();
+        builder.with_contents(columns, self.column_names.clone());
+        builder.build()
+    }
+}
@@ -8,15 +8,18 @@ use crate::{
 };
 
 use bytes::BytesMut;
+use chrono_tz::Tz;
 use std::time::Duration;
 use tokio::io::{AsyncWriteExt, BufReader};
 use tokio::net::{TcpStream, ToSocketAddrs};
 
+#[derive(Clone)]
 pub struct ClientBuilder<'a, A: ToSocketAddrs> {
     addr: A,
     ssl: bool,
     auth: Option<(&'a str, &'a str)>,
     option: BehaviorOptions,
+    timezone: Tz,
 }
 
 impl<'a, A: ToSocketAddrs> ClientBuilder<'a, A> {
@@ -26,6 +29,7 @@ impl<'a, A: ToSocketAddrs> ClientBuilder<'a, A> {
             ssl: false,
             auth: None,
             option: BehaviorOptions::default(),
+            timezone: Tz::UTC,
         }
     }
 
@@ -45,6 +49,12 @@ impl<'a, A: ToSocketAddrs> ClientBuilder<'a, A> {
         self
     }
 
+    /// Timezone whose wall clock time is stored for zoned datetimes, defaults to UTC.
+    pub fn with_timezone(&mut self, timezone: Tz) -> &mut Self {
+        self.timezone = timezone;
+        self
+    }
+
     pub async fn connect(mut self) -> Result<Client> {
         let conn = TcpStream::connect(&self.addr).await?;
 
@@ -81,6 +91,7 @@ impl<'a, A: ToSocketAddrs> ClientBuilder<'a, A> {
             rx,
             endian: resp.header.endian,
             option: self.option,
+            timezone: self.timezone,
         })
     }
 }
@@ -2,18 +2,29 @@
 //!
 //! See [DolphinDB connection docs](https://docs.dolphindb.cn/zh/rustdoc/chap3_basic_operations_landingpage.html) for more information.
 
+mod auto_fit_table_appender;
 mod builder;
+mod multithreaded_table_writer;
 mod request_info;
+mod table_upserter;
 mod table_writer;
+mod write_ahead_log;
 use bytes::BytesMut;
+use chrono_tz::Tz;
 pub(crate) use request_info::*;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use tokio::io::{AsyncWriteExt, BufReader};
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 
+pub use auto_fit_table_appender::AutoFitTableAppender;
 pub use builder::ClientBuilder;
-pub use table_writer::TableWriter;
+pub use multithreaded_table_writer::{
+    MultithreadedTableWriter, MultithreadedTableWriterBuilder, WriterStatus,
+};
+pub use table_upserter::{TableUpserter, UpsertOptions};
+pub use table_writer::{PartitionColumn, PartitionType, RetryPolicy, TableWriter};
+pub use write_ahead_log::LogSync;
 
 use crate::request::BehaviorOptions;
 use crate::{
@@ -28,6 +39,7 @@ pub struct Client {
     rx: BufReader<OwnedReadHalf>,
     endian: Endian,
     option: BehaviorOptions,
+    timezone: Tz,
 }
 
 impl Client {
@@ -118,6 +130,29 @@ impl Client {
         self.run(req).await
     }
 
+    /// Runs `script` and converts the resulting table to a Polars [`DataFrame`](polars::frame::DataFrame).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidData`](crate::error::Error::InvalidData) if the script does not return a table.
+    #[cfg(feature = "polars")]
+    pub async fn query_df(&mut self, script: &str) -> Result<polars::frame::DataFrame> {
+        use crate::{error::Error, types::Constant};
+
+        match self.run_script(script).await? {
+            Some(ConstantImpl::Table(table)) => table.try_into(),
+            res => Err(Error::InvalidData {
+                expect: "Table".into(),
+                actual: res.map_or("nothing".into(), |c| c.data_form().to_string()),
+            }),
+        }
+    }
+
+    /// Timezone applied to zoned datetimes written through this client.
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
     pub fn local_addr(&self) -> SocketAddr {
         self.tx.local_addr().unwrap()
     }
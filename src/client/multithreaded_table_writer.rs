@@ -0,0 +1,620 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    mem,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{net::ToSocketAddrs, sync::Notify, task::JoinHandle, time::MissedTickBehavior};
+
+use super::{
+    table_writer::{
+        convert_column, empty_column, fetch_partitions, fetch_scales, fetch_schema, table_expr,
+    },
+    Client, ClientBuilder, PartitionColumn, PartitionType,
+};
+use crate::{
+    error::{Error, Result},
+    types::*,
+};
+
+/// Progress of a [`MultithreadedTableWriter`], one of its writers, or a [`TableWriter`](super::TableWriter).
+#[derive(Debug, Clone, Default)]
+pub struct WriterStatus {
+    /// Rows confirmed by the server.
+    pub sent_rows: usize,
+    /// Rows buffered or in a batch that failed.
+    pub unsent_rows: usize,
+    /// Cause of the failure that stopped a writer, or of the last flush of a `TableWriter` if it failed.
+    pub error: Option<String>,
+}
+
+/// Configures and connects a [`MultithreadedTableWriter`].
+pub struct MultithreadedTableWriterBuilder {
+    db_path: String,
+    table_name: String,
+    partition_column: Option<String>,
+    thread_count: usize,
+    batch_size: usize,
+    flush_interval: Duration,
+}
+
+impl MultithreadedTableWriterBuilder {
+    /// Writes into `loadTable(db_path, table_name)`, or the shared table `table_name` if `db_path` is empty.
+    pub fn new(db_path: &str, table_name: &str) -> Self {
+        Self {
+            db_path: db_path.to_string(),
+            table_name: table_name.to_string(),
+            partition_column: None,
+            thread_count: 1,
+            batch_size: 1024,
+            flush_interval: Duration::from_millis(100),
+        }
+    }
+
+    /// Column whose value picks the writer of a row, required with more than one thread.
+    pub fn with_partition_column(&mut self, column: &str) -> &mut Self {
+        self.partition_column = Some(column.to_string());
+        self
+    }
+
+    pub fn with_thread_count(&mut self, thread_count: usize) -> &mut Self {
+        self.thread_count = thread_count;
+        self
+    }
+
+    /// Rows buffered by a writer before it flushes, defaults to 1024.
+    pub fn with_batch_size(&mut self, batch_size: usize) -> &mut Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Longest time rows wait in a writer, defaults to 100 milliseconds.
+    pub fn with_flush_interval(&mut self, flush_interval: Duration) -> &mut Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    /// Opens one connection per writer and fetches the table schema.
+    pub async fn build<A>(&self, client: &ClientBuilder<'_, A>) -> Result<MultithreadedTableWriter>
+    where
+        A: ToSocketAddrs + Clone,
+    {
+        if self.thread_count == 0 || self.batch_size == 0 || self.flush_interval.is_zero() {
+            return Err(Error::ConstraintsViolated(
+                "thread count, batch size and flush interval must be positive".into(),
+            ));
+        }
+
+        let table = table_expr(&self.db_path, &self.table_name);
+
+        let mut clients = Vec::with_capacity(self.thread_count);
+        for _ in 0..self.thread_count {
+            clients.push(client.clone().connect().await?);
+        }
+
+        let (column_names, column_types) = fetch_schema(&mut clients[0], &table).await?;
+        let column_scales = fetch_scales(&mut clients[0], &table, &column_types).await?;
+        let timezone = clients[0].timezone();
+
+        let partition_column = match &self.partition_column {
+            Some(name) => Some(column_names.iter().position(|c| c == name).ok_or_else(|| {
+                Error::ConstraintsViolated(format!("column {name} does not exist"))
+            })?),
+            None if self.thread_count > 1 => {
+                return Err(Error::ConstraintsViolated(
+                    "partition column is required with multiple threads".into(),
+                ))
+            }
+            None => None,
+        };
+
+        // rows are routed by the partition holding them, if the column partitions the table
+        let partitioner = match partition_column {
+            Some(column) => {
+                let partitions = fetch_partitions(&mut clients[0], &table).await?;
+                Partitioner::new(partitions.iter().find(|p| p.index == column))
+            }
+            None => Partitioner::Value(None),
+        };
+
+        let columns = column_types
+            .iter()
+            .map(|t| empty_column(*t))
+            .collect::<Result<Vec<_>>>()?;
+
+        let writers = clients
+            .into_iter()
+            .map(|client| {
+                let shared = Arc::new(Shared {
+                    queue: Mutex::new(Queue {
+                        buffer: columns.clone(),
+                        status: WriterStatus::default(),
+                        closed: false,
+                    }),
+                    notify: Notify::new(),
+                });
+                let worker = Worker {
+                    client,
+                    script: format!("tableInsert{{{table}}}"),
+                    columns: columns.clone(),
+                    column_names: column_names.clone(),
+                    flush_interval: self.flush_interval,
+                    shared: shared.clone(),
+                };
+                let handle = tokio::spawn(worker.run());
+                (shared, Some(handle))
+            })
+            .collect::<Vec<_>>();
+
+        Ok(MultithreadedTableWriter {
+            column_names,
+            column_types,
+            column_scales,
+            columns,
+            partition_column,
+            partitioner,
+            batch_size: self.batch_size,
+            timezone,
+            writers,
+        })
+    }
+}
+
+/// Writes rows into a table through several connections in the background.
+///
+/// Rows of the same partition always go to the same writer, so a partition is never
+/// written by two connections at once. The partition of a row is found with the scheme of
+/// the level partitioned by the partition column, or is its value if the column does not
+/// partition the table.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dolphindb::client::{ClientBuilder, MultithreadedTableWriterBuilder};
+/// #[tokio::main]
+/// async fn main() {
+///     let mut builder = ClientBuilder::new("127.0.0.1:8848");
+///     builder.with_auth(("admin", "123456"));
+///     let mut writer = MultithreadedTableWriterBuilder::new("dfs://db", "pt");
+///     writer.with_partition_column("sym").with_thread_count(4);
+///     let mut writer = writer.build(&builder).await.unwrap();
+///     writer.insert(vec!["a".into(), 1.5.into()]).unwrap();
+///     writer.wait_for_completion().await.unwrap();
+/// }
+/// ```
+pub struct MultithreadedTableWriter {
+    column_names: Vec<String>,
+    column_types: Vec<DataType>,
+    /// Scales of the decimal columns.
+    column_scales: Vec<Option<u32>>,
+    /// Empty buffer columns.
+    columns: Vec<VectorImpl>,
+    partition_column: Option<usize>,
+    partitioner: Partitioner,
+    batch_size: usize,
+    timezone: chrono_tz::Tz,
+    writers: Vec<Writer>,
+}
+
+/// Queue of a writer and its task, whose handle is None once awaited.
+type Writer = (Arc<Shared>, Option<JoinHandle<Result<()>>>);
+
+/// Finds the partition of a value of the partition column.
+enum Partitioner {
+    /// Each value is a partition, once converted to the type of the scheme if any.
+    Value(Option<DataType>),
+    /// Ascending boundaries of the partitions.
+    Range(Vec<ScalarImpl>),
+    /// Values of each partition.
+    List(Vec<Vec<ScalarImpl>>),
+    /// Number of buckets.
+    Hash(u32),
+}
+
+#[derive(Hash)]
+enum PartitionKey {
+    Value(ScalarImpl),
+    Index(usize),
+}
+
+impl MultithreadedTableWriter {
+    /// Buffers one row for its writer without waiting for the server.
+    ///
+    /// Fails if the row does not fit the schema, or if its writer is stopped.
+    pub fn insert(&self, row: Vec<PrimitiveType>) -> Result<()> {
+        if row.len() != self.column_types.len() {
+            return Err(Error::InvalidData {
+                expect: format!("{} columns", self.column_types.len()),
+                actual: format!("{} columns", row.len()),
+            });
+        }
+
+        let row = row
+            .into_iter()
+            .map(|value| match value {
+                PrimitiveType::ZonedDateTime(dt) => {
+                    PrimitiveType::NaiveDateTime(dt.with_timezone(&self.timezone).naive_local())
+                }
+                value => value,
+            })
+            .collect::<Vec<_>>();
+
+        let value = match self.partition_column {
+            Some(column) => Some(row[column].clone().into_scalar(self.column_types[column])?),
+            None => None,
+        };
+        self.buffer(self.writer_index(value), 1, |buffer| {
+            for (i, value) in row.into_iter().enumerate() {
+                buffer[i].push_primitive_type(value)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Buffers typed rows for their writers without waiting for the server.
+    ///
+    /// Fields must be named and ordered as the table's columns and are converted to their types.
+    /// Fails like [`insert`](Self::insert), once the rows routed to the writers before are buffered.
+    pub fn insert_rows<T: DolphinRow>(&self, rows: &[T]) -> Result<()> {
+        let names = T::column_names();
+        let matches = names.len() == self.column_names.len()
+            && names
+                .iter()
+                .zip(self.column_names.iter())
+                .all(|(a, b)| a.eq_ignore_ascii_case(b));
+        if !matches {
+            return Err(Error::InvalidData {
+                expect: format!("columns {}", self.column_names.join(",")),
+                actual: format!("columns {}", names.join(",")),
+            });
+        }
+
+        let table = T::to_table(rows)?;
+        let columns = table
+            .columns()
+            .iter()
+            .zip(self.column_types.iter().zip(self.column_scales.iter()))
+            .map(|(column, (data_type, scale))| convert_column(column, *data_type, *scale))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut routed = vec![vec![]; self.writers.len()];
+        for i in 0..rows.len() {
+            // routed by the value in the column type, as in insert
+            let value = self.partition_column.and_then(|c| match columns[c].get(i) {
+                Some(ConstantImpl::Scalar(value)) => value.cast(self.column_types[c]).ok(),
+                _ => None,
+            });
+            routed[self.writer_index(value)].push(i);
+        }
+        for (writer, indices) in routed.iter().enumerate() {
+            if indices.is_empty() {
+                continue;
+            }
+            self.buffer(writer, indices.len(), |buffer| {
+                for (i, column) in columns.iter().enumerate() {
+                    buffer[i].extend_from(&column.gather(indices))?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Adds `rows` to the buffer of `writer` with `push`, which leaves the buffer unchanged on failure.
+    fn buffer(
+        &self,
+        writer: usize,
+        rows: usize,
+        push: impl FnOnce(&mut [VectorImpl]) -> Result<()>,
+    ) -> Result<()> {
+        let (shared, _) = &self.writers[writer];
+        let mut queue = shared.queue.lock().unwrap();
+        if let Some(error) = &queue.status.error {
+            return Err(Error::ChannelClosed(format!("writer stopped: {error}")));
+        }
+        if queue.closed {
+            return Err(Error::ChannelClosed("writer is closed".into()));
+        }
+
+        let buffered = queue.buffer[0].len();
+        if let Err(err) = push(&mut queue.buffer) {
+            for column in queue.buffer.iter_mut() {
+                column.resize(buffered);
+            }
+            return Err(err);
+        }
+        queue.status.unsent_rows += rows;
+
+        if queue.buffer[0].len() >= self.batch_size {
+            shared.notify.notify_one();
+        }
+        Ok(())
+    }
+
+    /// Progress summed over all writers, with the first failure if any.
+    pub fn status(&self) -> WriterStatus {
+        self.thread_status()
+            .into_iter()
+            .fold(WriterStatus::default(), |mut total, s| {
+                total.sent_rows += s.sent_rows;
+                total.unsent_rows += s.unsent_rows;
+                total.error = total.error.or(s.error);
+                total
+            })
+    }
+
+    /// Progress of each writer.
+    pub fn thread_status(&self) -> Vec<WriterStatus> {
+        self.writers
+            .iter()
+            .map(|(shared, _)| shared.queue.lock().unwrap().status.clone())
+            .collect()
+    }
+
+    pub fn column_names(&self) -> &[String] {
+        &self.column_names
+    }
+
+    pub fn column_types(&self) -> &[DataType] {
+        &self.column_types
+    }
+
+    /// Stops accepting rows, flushes what is buffered and waits for every writer to exit.
+    ///
+    /// Returns the first error that stopped a writer, also when called again.
+    pub async fn wait_for_completion(&mut self) -> Result<()> {
+        self.close();
+
+        let mut res = Ok(());
+        for (shared, handle) in self.writers.iter_mut() {
+            let exited = match handle.take() {
+                Some(handle) => handle.await.map_err(std::io::Error::from)?,
+                None => match &shared.queue.lock().unwrap().status.error {
+                    Some(error) => Err(Error::ChannelClosed(format!("writer stopped: {error}"))),
+                    None => Ok(()),
+                },
+            };
+            if res.is_ok() {
+                res = exited;
+            }
+        }
+        res
+    }
+
+    /// Takes the rows of the writers stopped by a failure, so they can be written again.
+    ///
+    /// The rows of the batch that failed come first, followed by the rows buffered after it.
+    pub fn take_failed(&mut self) -> Result<Table> {
+        let mut columns = self.columns.clone();
+        for (shared, _) in self.writers.iter() {
+            let mut queue = shared.queue.lock().unwrap();
+            if queue.status.error.is_none() {
+                continue;
+            }
+            let failed = mem::replace(&mut queue.buffer, self.columns.clone());
+            queue.status.unsent_rows -= failed[0].len();
+            for (column, failed) in columns.iter_mut().zip(failed.iter()) {
+                column.extend_from(failed)?;
+            }
+        }
+
+        let mut builder = TableBuilder::new();
+        builder.with_contents(columns, self.column_names.clone());
+        builder.build()
+    }
+
+    /// Writer of the rows whose partitioning column holds `value`.
+    fn writer_index(&self, value: Option<ScalarImpl>) -> usize {
+        let Some(value) = value else {
+            return 0;
+        };
+
+        let mut hasher = DefaultHasher::new();
+        self.partitioner.key(value).hash(&mut hasher);
+        hasher.finish() as usize % self.writers.len()
+    }
+
+    fn close(&self) {
+        for (shared, _) in self.writers.iter() {
+            shared.queue.lock().unwrap().closed = true;
+            shared.notify.notify_one();
+        }
+    }
+}
+
+impl Drop for MultithreadedTableWriter {
+    // writers flush the remaining rows in the background
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+struct Queue {
+    buffer: Vec<VectorImpl>,
+    status: WriterStatus,
+    closed: bool,
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    notify: Notify,
+}
+
+struct Worker {
+    client: Client,
+    script: String,
+    columns: Vec<VectorImpl>,
+    column_names: Vec<String>,
+    flush_interval: Duration,
+    shared: Arc<Shared>,
+}
+
+impl Worker {
+    async fn run(mut self) -> Result<()> {
+        let mut ticker = tokio::time::interval(self.flush_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = self.shared.notify.notified() => {}
+                _ = ticker.tick() => {}
+            }
+
+            let (batch, closed) = {
+                let mut queue = self.shared.queue.lock().unwrap();
+                let batch = mem::replace(&mut queue.buffer, self.columns.clone());
+                (batch, queue.closed)
+            };
+
+            let rows = batch[0].len();
+            if rows > 0 {
+                if let Err(err) = self.flush(&batch).await {
+                    // the batch is kept before the rows buffered since, for take_failed
+                    let mut queue = self.shared.queue.lock().unwrap();
+                    let buffered = mem::replace(&mut queue.buffer, batch);
+                    for (column, buffered) in queue.buffer.iter_mut().zip(buffered.iter()) {
+                        column.extend_from(buffered)?;
+                    }
+                    queue.status.error = Some(err.to_string());
+                    return Err(err);
+                }
+
+                let mut queue = self.shared.queue.lock().unwrap();
+                queue.status.sent_rows += rows;
+                queue.status.unsent_rows -= rows;
+            }
+
+            if closed {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn flush(&mut self, batch: &[VectorImpl]) -> Result<()> {
+        let mut builder = TableBuilder::new();
+        builder.with_contents(batch.to_vec(), self.column_names.clone());
+        self.client
+            .run_function(self.script.as_str(), &[builder.build()?.into()])
+            .await?;
+        Ok(())
+    }
+}
+
+impl Partitioner {
+    /// Partitioner of the level `partition`, or of a column that does not partition the table.
+    fn new(partition: Option<&PartitionColumn>) -> Self {
+        let Some(partition) = partition else {
+            return Self::Value(None);
+        };
+        match (partition.partition_type, &partition.scheme) {
+            (PartitionType::Value, ConstantImpl::Vector(scheme)) => {
+                Self::Value(Some(scheme.data_type()))
+            }
+            (PartitionType::Range, ConstantImpl::Vector(scheme)) => Self::Range(scalars(scheme)),
+            (PartitionType::List, ConstantImpl::Vector(scheme)) => Self::List(
+                (0..scheme.len())
+                    .map(|i| match scheme.get(i) {
+                        Some(ConstantImpl::Vector(values)) => scalars(&values),
+                        Some(ConstantImpl::Scalar(value)) => vec![value],
+                        _ => vec![],
+                    })
+                    .collect(),
+            ),
+            (PartitionType::Hash, ConstantImpl::Scalar(ScalarImpl::Int(buckets))) => {
+                match buckets.into_inner().and_then(|b| u32::try_from(b).ok()) {
+                    Some(buckets) if buckets > 0 => Self::Hash(buckets),
+                    _ => Self::Value(None),
+                }
+            }
+            _ => Self::Value(None),
+        }
+    }
+
+    /// Partition of `value`, which is its own partition if it falls in none of the scheme.
+    fn key(&self, value: ScalarImpl) -> PartitionKey {
+        let index = match self {
+            Self::Value(Some(data_type)) if value.data_type() != *data_type => {
+                return PartitionKey::Value(value.cast(*data_type).unwrap_or(value));
+            }
+            Self::Value(_) => return PartitionKey::Value(value),
+            Self::Range(boundaries) => boundaries.first().and_then(|first| {
+                let value = value.cast(first.data_type()).ok()?;
+                let end = boundaries.partition_point(|b| *b <= value);
+                (0 < end && end < boundaries.len()).then(|| end - 1)
+            }),
+            Self::List(lists) => lists.iter().position(|list| {
+                list.first()
+                    .and_then(|first| value.cast(first.data_type()).ok())
+                    .is_some_and(|value| list.contains(&value))
+            }),
+            Self::Hash(buckets) => hash_bucket(&value, *buckets),
+        };
+        match index {
+            Some(index) => PartitionKey::Index(index),
+            None => PartitionKey::Value(value),
+        }
+    }
+}
+
+fn scalars(vector: &VectorImpl) -> Vec<ScalarImpl> {
+    (0..vector.len())
+        .filter_map(|i| match vector.get(i) {
+            Some(ConstantImpl::Scalar(value)) => Some(value),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Bucket of `value` in a HASH partition, as the server computes it.
+fn hash_bucket(value: &ScalarImpl, buckets: u32) -> Option<usize> {
+    if value.is_null() {
+        return None;
+    }
+    let bucket = match value {
+        ScalarImpl::String(s) => murmur32(s.0.as_deref()?.as_bytes()) % buckets,
+        ScalarImpl::Symbol(s) => murmur32(s.0.as_deref()?.as_bytes()) % buckets,
+        // negative values are taken as unsigned
+        ScalarImpl::Char(v) => v.0 as i32 as u32 % buckets,
+        ScalarImpl::Short(v) => v.0 as i32 as u32 % buckets,
+        ScalarImpl::Int(v) => v.0 as u32 % buckets,
+        ScalarImpl::Date(v) => v.0 as u32 % buckets,
+        ScalarImpl::Month(v) => v.0 as u32 % buckets,
+        ScalarImpl::Time(v) => v.0 as u32 % buckets,
+        ScalarImpl::Minute(v) => v.0 as u32 % buckets,
+        ScalarImpl::Second(v) => v.0 as u32 % buckets,
+        ScalarImpl::DateTime(v) => v.0 as u32 % buckets,
+        ScalarImpl::DateHour(v) => v.0 as u32 % buckets,
+        ScalarImpl::Long(v) => (v.0 as u64 % buckets as u64) as u32,
+        ScalarImpl::Timestamp(v) => (v.0 as u64 % buckets as u64) as u32,
+        ScalarImpl::NanoTime(v) => (v.0 as u64 % buckets as u64) as u32,
+        ScalarImpl::NanoTimestamp(v) => (v.0 as u64 % buckets as u64) as u32,
+        _ => return None,
+    };
+    Some(bucket as usize)
+}
+
+/// MurmurHash2 with a zero seed, which the server hashes strings with.
+fn murmur32(data: &[u8]) -> u32 {
+    const M: u32 = 0x5bd1_e995;
+    let mut h = data.len() as u32;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in chunks.by_ref() {
+        let mut k = u32::from_le_bytes(chunk.try_into().unwrap());
+        k = k.wrapping_mul(M);
+        k ^= k >> 24;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M) ^ k;
+    }
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        for (i, byte) in tail.iter().enumerate() {
+            h ^= (*byte as u32) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^ (h >> 15)
+}
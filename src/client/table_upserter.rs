@@ -0,0 +1,127 @@
+use super::{
+    table_writer::{table_expr, TableWriter},
+    Client, WriterStatus,
+};
+use crate::{error::Result, types::*};
+
+/// Arguments of `upsert!` used by a [`TableUpserter`].
+#[derive(Debug, Clone, Default)]
+pub struct UpsertOptions {
+    key_columns: Vec<String>,
+    sort_columns: Vec<String>,
+    ignore_null: bool,
+}
+
+impl UpsertOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Columns identifying a row, required for DFS tables and ignored by keyed tables.
+    pub fn with_key_columns<S: Into<String>>(
+        &mut self,
+        columns: impl IntoIterator<Item = S>,
+    ) -> &mut Self {
+        self.key_columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Columns the updated partitions are sorted by, DFS tables only.
+    pub fn with_sort_columns<S: Into<String>>(
+        &mut self,
+        columns: impl IntoIterator<Item = S>,
+    ) -> &mut Self {
+        self.sort_columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether null values leave the existing values unchanged, defaults to false.
+    pub fn with_ignore_null(&mut self, ignore_null: bool) -> &mut Self {
+        self.ignore_null = ignore_null;
+        self
+    }
+
+    fn script(&self, table: &str) -> String {
+        let symbols = |columns: &[String]| {
+            if columns.is_empty() {
+                return "NULL".to_string();
+            }
+            let columns = columns.iter().map(|c| format!("`{c}")).collect::<Vec<_>>();
+            format!("[{}]", columns.join(","))
+        };
+
+        let mut script = format!("upsert!{{{table}, , {}", self.ignore_null);
+        if !self.key_columns.is_empty() || !self.sort_columns.is_empty() {
+            script += &format!(", {}", symbols(&self.key_columns));
+        }
+        if !self.sort_columns.is_empty() {
+            script += &format!(", {}", symbols(&self.sort_columns));
+        }
+        script + "}"
+    }
+}
+
+/// Buffers rows like [`TableWriter`] and flushes them with `upsert!`.
+///
+/// Rows whose keys already exist update the table, other rows are inserted.
+#[derive(Clone)]
+pub struct TableUpserter {
+    writer: TableWriter,
+}
+
+impl TableUpserter {
+    /// Creates a `TableUpserter` for a shared keyed table.
+    pub async fn new(
+        client: Client,
+        table_name: &str,
+        batch_size: u32,
+        options: &UpsertOptions,
+    ) -> Result<Self> {
+        Self::load(client, "", table_name, batch_size, options).await
+    }
+
+    /// Creates a `TableUpserter` for the DFS table `loadTable(db_path, table_name)`.
+    pub async fn load(
+        client: Client,
+        db_path: &str,
+        table_name: &str,
+        batch_size: u32,
+        options: &UpsertOptions,
+    ) -> Result<Self> {
+        let table = table_expr(db_path, table_name);
+        let script = options.script(&table);
+        Ok(Self {
+            writer: TableWriter::open(client, table, script, batch_size).await?,
+        })
+    }
+
+    /// Append one row to the buffer.
+    ///
+    /// When buffer is full, the rows are upserted and the number of rows the server accepted,
+    /// updated or inserted, is returned. A failure of the flush is reported by
+    /// [`status`](Self::status) like for [`TableWriter::append_row`].
+    /// When buffer is not full, None is returned.
+    pub async fn append_row(&mut self, row: &mut Vec<PrimitiveType>) -> Result<Option<usize>> {
+        self.writer.append_row_counted(row).await
+    }
+
+    /// Manually flush the buffer, returning the number of rows the server accepted.
+    ///
+    /// Batches of earlier flushes lost with the connection are retried first and counted too.
+    pub async fn flush(&mut self) -> Result<usize> {
+        if self.writer.status().unsent_rows == 0 {
+            return Ok(0);
+        }
+        self.writer.flush_counted().await
+    }
+
+    /// Rows upserted and left to upsert, with the cause of the last flush if it failed.
+    pub fn status(&self) -> WriterStatus {
+        self.writer.status()
+    }
+
+    /// Returns the number of rows in the buffer.
+    pub fn size(&self) -> usize {
+        self.writer.size()
+    }
+}
@@ -1,237 +1,1136 @@
-use std::vec;
+use std::{
+    collections::VecDeque,
+    mem::{self, size_of_val},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
-use tokio::{runtime::Handle, task::block_in_place};
+use chrono_tz::Tz;
+use tokio::{runtime::Handle, task::JoinHandle, time::MissedTickBehavior};
 
-use crate::{error::Error, types::*};
+use crate::{
+    error::{Error, Result},
+    types::*,
+    Serialize,
+};
 
-use super::Client;
+use super::{
+    multithreaded_table_writer::WriterStatus,
+    write_ahead_log::{LogSync, WriteAheadLog},
+    Client,
+};
 
 /// This is a simple buffer for tableInsert
-pub struct TableWriter<'a> {
-    client: &'a mut Client,
+///
+/// Clones share the connection and the buffer, so producers on several tasks
+/// can append to the same writer.
+/// Dropping the last clone flushes the remaining rows in the background,
+/// rows that cannot be sent are reported through the [`log`](https://docs.rs/log) crate.
+#[derive(Clone)]
+pub struct TableWriter {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    /// None once closed.
+    client: tokio::sync::Mutex<Option<Client>>,
     table_name: String,
     script: String,
-    columns: Vec<VectorImpl>,
-    column_types: Vec<DataType>,
-    column_names: Vec<String>,
-    buffer: Vec<VectorImpl>,
-    size: u32,
+    timezone: Tz,
     batch_size: u32,
+    max_bytes: AtomicUsize,
+    retry_policy: Mutex<RetryPolicy>,
+    buffer: Mutex<Buffer>,
+    /// Batches lost with the connection, oldest first.
+    failed: Mutex<VecDeque<Table>>,
+    /// Batches the server rejected, no longer sent.
+    rejected: Mutex<Vec<Table>>,
+    sent_rows: AtomicUsize,
+    /// Cause of the last failed flush.
+    error: Mutex<Option<String>>,
+    /// Locked before the buffer, from the conversion of appended rows to their buffering.
+    log: Mutex<Option<WriteAheadLog>>,
+    log_sync: Mutex<LogSync>,
+    timer: Mutex<Option<JoinHandle<()>>>,
 }
 
-/// TableWriter is a simple buffer for tableInsert.
+struct Buffer {
+    /// Schema of the buffered rows.
+    schema: Arc<Schema>,
+    columns: Vec<VectorImpl>,
+    bytes: usize,
+}
+
+/// Columns of the table as last fetched from the server.
+struct Schema {
+    /// Empty buffer columns.
+    columns: Vec<VectorImpl>,
+    names: Vec<String>,
+    types: Vec<DataType>,
+    /// Scales of decimal columns.
+    scales: Vec<Option<u32>>,
+    partition_columns: Vec<PartitionColumn>,
+}
+
+/// How a [`TableWriter`] retries batches rejected by the server or lost with the connection.
 ///
-/// Partitioned tables are not supported.
-impl<'a> TableWriter<'a> {
-    /// Creates a `TableWriter`.
+/// A batch is sent up to `max_retries + 1` times per flush, waiting `interval` between attempts.
+/// Batches still failing are kept and retried by the next flush, before newer rows.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    interval: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            interval: Duration::from_millis(100),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attempts after the first one within a flush, defaults to 3.
+    pub fn with_max_retries(&mut self, max_retries: u32) -> &mut Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Wait between two attempts, defaults to 100 milliseconds.
+    pub fn with_interval(&mut self, interval: Duration) -> &mut Self {
+        self.interval = interval;
+        self
+    }
+}
+
+/// How a level of a partitioned table splits its rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionType {
+    Seq,
+    Value,
+    Range,
+    List,
+    Hash,
+}
+
+impl TryFrom<i32> for PartitionType {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self> {
+        match value {
+            0 => Ok(Self::Seq),
+            1 => Ok(Self::Value),
+            2 => Ok(Self::Range),
+            3 => Ok(Self::List),
+            5 => Ok(Self::Hash),
+            _ => Err(Error::InvalidData {
+                expect: "partition type".into(),
+                actual: value.to_string(),
+            }),
+        }
+    }
+}
+
+/// Partitioning column of one level of a partitioned table.
+#[derive(Debug, Clone)]
+pub struct PartitionColumn {
+    pub name: String,
+    /// Position of the column in the table.
+    pub index: usize,
+    pub partition_type: PartitionType,
+    /// Partition scheme as reported by `schema`, e.g. the boundaries of a RANGE partition
+    /// or the bucket count of a HASH partition.
+    pub scheme: ConstantImpl,
+}
+
+/// TableWriter is a simple buffer for tableInsert.
+impl TableWriter {
+    /// Creates a `TableWriter` for a shared in-memory or stream table.
     ///
     /// This function will try to get the table's schema through client,
     /// so the caller needs to make sure table is created before calling this function.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use dolphindb::client::{ClientBuilder, TableWriter};
     /// #[tokio::main]
     /// async fn main() {
     ///     let mut builder = ClientBuilder::new("127.0.0.1:8848");
     ///     builder.with_auth(("admin", "123456"));
-    ///     let mut client = builder.connect().await.unwrap();
-    ///     let mut table = TableWriter::new(client, "test_table", 512);
+    ///     let client = builder.connect().await.unwrap();
+    ///     let mut table = TableWriter::new(client, "test_table", 512).await.unwrap();
     /// }
     /// ```
-    pub async fn new(client: &'a mut Client, table_name: &str, batch_size: u32) -> Self {
+    pub async fn new(client: Client, table_name: &str, batch_size: u32) -> Result<Self> {
+        let script = format!("tableInsert{{{}}}", table_name);
+        Self::open(client, table_name.to_string(), script, batch_size).await
+    }
+
+    /// Creates a `TableWriter` for the DFS table `loadTable(db_path, table_name)`.
+    ///
+    /// The partition scheme is fetched together with the schema, see [`partition_columns`](Self::partition_columns).
+    pub async fn load(
+        client: Client,
+        db_path: &str,
+        table_name: &str,
+        batch_size: u32,
+    ) -> Result<Self> {
+        let table = table_expr(db_path, table_name);
+        let script = format!("tableInsert{{{}}}", table);
+        Self::open(client, table, script, batch_size).await
+    }
+
+    /// Creates a writer flushing through the function `script`, which takes the buffered rows as a table.
+    pub(crate) async fn open(
+        mut client: Client,
+        table: String,
+        script: String,
+        batch_size: u32,
+    ) -> Result<Self> {
         if batch_size == 0 {
-            panic!("TableWriter: batch_size must be positive.");
-        }
-        let mut columns = vec![];
-        let mut column_types = vec![];
-        let mut column_names: Vec<String> = vec![];
-        let schema = client
-            .run_script(format!("schema({}).colDefs.typeInt", table_name).as_str())
-            .await;
-        if schema.is_err() {
-            panic!("Failed to get schema for table {}", table_name);
-        }
-        if let VectorImpl::Int(columns_ddb) = schema.unwrap().unwrap().as_vector().unwrap() {
-            for i in columns_ddb.iter() {
-                let data_type = DataType::try_from(i.0.unwrap() as u8).unwrap();
-                column_types.push(data_type);
-                let vec = match data_type {
-                    DataType::Bool => BoolVector::new().into(),
-                    DataType::Char => CharVector::new().into(),
-                    DataType::Short => ShortVector::new().into(),
-                    DataType::Int
-                    | DataType::Date
-                    | DataType::Month
-                    | DataType::Time
-                    | DataType::Minute
-                    | DataType::Second
-                    | DataType::DateTime
-                    | DataType::DateHour => IntVector::new().into(),
-                    DataType::Long
-                    | DataType::Timestamp
-                    | DataType::NanoTime
-                    | DataType::NanoTimestamp => LongVector::new().into(),
-                    DataType::Float => FloatVector::new().into(),
-                    DataType::Double => DoubleVector::new().into(),
-                    DataType::String | DataType::Symbol => StringVector::new().into(),
-                    DataType::Decimal32 => Decimal32Vector::new().into(),
-                    DataType::Decimal64 => Decimal64Vector::new().into(),
-                    DataType::Decimal128 => Decimal128Vector::new().into(),
-                    DataType::CharArray => CharArrayVector::new().into(),
-                    DataType::ShortArray => ShortArrayVector::new().into(),
-                    DataType::IntArray => IntArrayVector::new().into(),
-                    DataType::LongArray => LongArrayVector::new().into(),
-                    DataType::FloatArray => FloatArrayVector::new().into(),
-                    DataType::DoubleArray => DoubleArrayVector::new().into(),
-                    _ => unimplemented!(),
+            return Err(Error::ConstraintsViolated(
+                "batch size must be positive".into(),
+            ));
+        }
+        let schema = Schema::fetch(&mut client, &table).await?;
+        let buffer = Buffer {
+            columns: schema.columns.clone(),
+            schema: Arc::new(schema),
+            bytes: 0,
+        };
+        Ok(Self {
+            inner: Arc::new(Inner {
+                timezone: client.timezone(),
+                client: tokio::sync::Mutex::new(Some(client)),
+                script,
+                table_name: table,
+                batch_size,
+                max_bytes: AtomicUsize::new(usize::MAX),
+                retry_policy: Mutex::new(RetryPolicy::default()),
+                buffer: Mutex::new(buffer),
+                failed: Mutex::new(VecDeque::new()),
+                rejected: Mutex::new(Vec::new()),
+                sent_rows: AtomicUsize::new(0),
+                error: Mutex::new(None),
+                log: Mutex::new(None),
+                log_sync: Mutex::new(LogSync::default()),
+                timer: Mutex::new(None),
+            }),
+        })
+    }
+
+    /// Flushes the buffer every `interval` in the background, even if it is not full.
+    ///
+    /// A zero interval stops the background flushes. Applies to every clone of this writer.
+    pub fn with_flush_interval(&mut self, interval: Duration) -> &mut Self {
+        if let Some(timer) = self.inner.timer.lock().unwrap().take() {
+            timer.abort();
+        }
+        if interval.is_zero() {
+            return self;
+        }
+        let inner = Arc::downgrade(&self.inner);
+        let timer = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(inner) = inner.upgrade() else {
+                    return;
                 };
-                columns.push(vec);
+                // failed batches stay queued for the next flush
+                if inner.size() > 0 {
+                    let _ = inner.flush().await;
+                }
             }
-        }
-        if let VectorImpl::String(column_names_ddb) = client
-            .run_script(format!("schema({}).colDefs.name", table_name).as_str())
-            .await
-            .unwrap()
-            .unwrap()
-            .as_vector()
-            .unwrap()
+        });
+        *self.inner.timer.lock().unwrap() = Some(timer);
+        self
+    }
+
+    /// Flushes the buffer once it holds about `max_bytes` of data, even if it has fewer than `batch_size` rows.
+    ///
+    /// Applies to every clone of this writer.
+    pub fn with_max_bytes(&mut self, max_bytes: usize) -> &mut Self {
+        self.inner.max_bytes.store(max_bytes, Ordering::Relaxed);
+        self
+    }
+
+    /// How batches that fail to flush are retried.
+    ///
+    /// Applies to every clone of this writer.
+    pub fn with_retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+        *self.inner.retry_policy.lock().unwrap() = retry_policy;
+        self
+    }
+
+    /// Persists appended rows in `dir` until the server confirms them.
+    ///
+    /// Rows left in `dir` by a previous writer of the same table are queued like failed batches,
+    /// so the next flush sends them before newer rows. They are matched to the columns by name.
+    /// Rows sent just before a crash may be replayed twice. Appends fail if their rows cannot be logged.
+    /// Applies to every clone of this writer.
+    pub async fn with_log_dir(&mut self, dir: impl AsRef<Path>) -> Result<&mut Self> {
+        let sync = *self.inner.log_sync.lock().unwrap();
+        let (mut log, pending) = WriteAheadLog::open(dir.as_ref(), sync).await?;
         {
-            column_names = column_names_ddb
+            // no flush may run while the log is replaced
+            let _client = self.inner.client.lock().await;
+            let mut current = self.inner.log.lock().unwrap();
+            let buffer = self.inner.buffer.lock().unwrap();
+            let pending = pending
                 .iter()
-                .map(|b| b.to_string())
-                .collect::<Vec<_>>();
+                .map(|table| buffer.schema.conform(table))
+                .collect::<Result<Vec<_>>>()?;
+            if !buffer.columns[0].is_empty() {
+                log.append(&buffer.schema.batch(buffer.columns.clone())?)?;
+            }
+            *current = Some(log);
+            let mut failed = self.inner.failed.lock().unwrap();
+            for table in pending.into_iter().rev() {
+                failed.push_front(table);
+            }
         }
-        let buffer = columns.clone();
-        Self {
-            client,
-            table_name: table_name.to_string(),
-            script: format!("tableInsert{{'{}'}}", table_name),
-            columns,
-            column_types,
-            column_names,
-            buffer,
-            size: 0,
-            batch_size,
+        Ok(self)
+    }
+
+    /// When the write-ahead log forces appended rows to disk, defaults to [`LogSync::Always`].
+    ///
+    /// Applies to every clone of this writer.
+    pub fn with_log_sync(&mut self, sync: LogSync) -> &mut Self {
+        *self.inner.log_sync.lock().unwrap() = sync;
+        if let Some(log) = self.inner.log.lock().unwrap().as_mut() {
+            log.set_sync(sync);
         }
+        self
     }
+
+    pub fn column_names(&self) -> Vec<String> {
+        self.inner.schema().names.clone()
+    }
+
+    pub fn column_types(&self) -> Vec<DataType> {
+        self.inner.schema().types.clone()
+    }
+
+    /// Partitioning columns of the table, from the outermost level.
+    ///
+    /// Empty if the table is not partitioned.
+    pub fn partition_columns(&self) -> Vec<PartitionColumn> {
+        self.inner.schema().partition_columns.clone()
+    }
+
+    /// Reloads the columns of the table after they changed on the server.
+    ///
+    /// Buffered rows and batches that failed are moved to the new columns by name, added columns are null.
+    /// Fails without changing anything if a column holding buffered rows was removed.
+    pub async fn refresh_schema(&mut self) -> Result<()> {
+        // no flush may run while the rows are moved
+        let mut client = self.inner.client.lock().await;
+        let Some(client) = client.as_mut() else {
+            return Err(Error::ChannelClosed("writer is closed".into()));
+        };
+        let schema = Schema::fetch(client, &self.inner.table_name).await?;
+
+        // appends convert their rows to the schema while holding the log
+        let _log = self.inner.log.lock().unwrap();
+        let mut buffer = self.inner.buffer.lock().unwrap();
+        let mut failed = self.inner.failed.lock().unwrap();
+        let mut rejected = self.inner.rejected.lock().unwrap();
+        let buffered = schema.conform(&buffer.schema.batch(buffer.columns.clone())?)?;
+        let batches = failed
+            .iter()
+            .map(|batch| schema.conform(batch))
+            .collect::<Result<VecDeque<_>>>()?;
+        let rejected_batches = rejected
+            .iter()
+            .map(|batch| schema.conform(batch))
+            .collect::<Result<Vec<_>>>()?;
+        buffer.columns = buffered.columns().clone();
+        buffer.schema = Arc::new(schema);
+        *failed = batches;
+        *rejected = rejected_batches;
+        Ok(())
+    }
+
     /// Append one row to the TableWriter's buffer.
     ///
     /// When buffer is full, this function will run tableInsert and return the result of the script.
     /// When buffer is not full, None is returned.
     ///
-    /// This interface is NOT thread-safe.
+    /// Rows that do not fit the schema are rejected without changing the buffer. Once buffered,
+    /// a row is accepted even if the flush fails: None is returned and the failure is reported
+    /// by [`status`](Self::status), see [`flush`](Self::flush).
     pub async fn append_row(
         &mut self,
         row: &mut Vec<PrimitiveType>,
-    ) -> Result<Option<ConstantImpl>, Error> {
-        if self.buffer.len() != row.len() {
-            panic!(
-                "Table {} has {} columns, but {} provided.",
-                self.table_name,
-                self.buffer.len(),
-                row.len()
-            );
-        }
-        let mut res: Result<(), Error> = Ok(());
-        for i in (0..self.buffer.len()).rev() {
-            let data = row.pop().unwrap();
-            match &data {
-                PrimitiveType::NaiveDate(d) => match self.column_types[i] {
-                    DataType::Date => {
-                        _ = self.buffer[i].push_primitive_type(Date::new(*d).ddb_rep().into())
-                    }
-                    DataType::Month => {
-                        _ = self.buffer[i].push_primitive_type(Month::new(*d).ddb_rep().into())
-                    }
-                    t => {
-                        res = Err(Error::InvalidConvert {
-                            from: "NaiveDate".to_string(),
-                            to: t.to_string(),
-                        })
-                    }
-                },
-                PrimitiveType::NaiveTime(t) => match self.column_types[i] {
-                    DataType::Time => {
-                        _ = self.buffer[i].push_primitive_type(Time::new(*t).ddb_rep().into())
-                    }
-                    DataType::Minute => {
-                        _ = self.buffer[i].push_primitive_type(Minute::new(*t).ddb_rep().into())
-                    }
-                    DataType::Second => {
-                        _ = self.buffer[i].push_primitive_type(Second::new(*t).ddb_rep().into())
-                    }
-                    DataType::NanoTime => {
-                        _ = self.buffer[i].push_primitive_type(NanoTime::new(*t).ddb_rep().into())
-                    }
-                    t => {
-                        res = Err(Error::InvalidConvert {
-                            from: "NaiveTime".to_string(),
-                            to: t.to_string(),
-                        })
-                    }
-                },
-                PrimitiveType::NaiveDateTime(dt) => match self.column_types[i] {
-                    DataType::Timestamp => {
-                        _ = self.buffer[i].push_primitive_type(Timestamp::new(*dt).ddb_rep().into())
-                    }
-                    DataType::NanoTimestamp => {
-                        _ = self.buffer[i]
-                            .push_primitive_type(NanoTimestamp::new(*dt).ddb_rep().into())
-                    }
-                    DataType::DateTime => {
-                        _ = self.buffer[i].push_primitive_type(DateTime::new(*dt).ddb_rep().into())
-                    }
-                    DataType::DateHour => {
-                        _ = self.buffer[i].push_primitive_type(DateHour::new(*dt).ddb_rep().into())
-                    }
-                    t => {
-                        res = Err(Error::InvalidConvert {
-                            from: "NaiveDateTime".to_string(),
-                            to: t.to_string(),
-                        })
-                    }
-                },
-                _ => res = self.buffer[i].push_primitive_type(data),
-            };
-            if let Err(err) = res {
-                panic!(
-                    "Failed to insert into column `{}`: {}.",
-                    self.column_names[i].clone(),
-                    err.to_string()
-                );
+    ) -> Result<Option<ConstantImpl>> {
+        let full = self
+            .inner
+            .append(|schema| self.inner.row_columns(schema, row))?;
+        self.flush_accepted(full).await
+    }
+
+    /// Append one row given as column names and values, such as a [`HashMap`](std::collections::HashMap).
+    ///
+    /// Names are matched case-insensitively and omitted columns are null.
+    /// Unknown or repeated names are rejected, see [`append_row`](Self::append_row).
+    pub async fn append_named_row<S: AsRef<str>>(
+        &mut self,
+        row: impl IntoIterator<Item = (S, PrimitiveType)>,
+    ) -> Result<Option<ConstantImpl>> {
+        let full = self.inner.append(|schema| {
+            let mut values = vec![None; schema.names.len()];
+            for (name, value) in row {
+                let name = name.as_ref();
+                let i = schema.index(name).ok_or_else(|| {
+                    Error::ConstraintsViolated(format!("column {name} does not exist"))
+                })?;
+                if values[i].replace(value).is_some() {
+                    return Err(Error::ConstraintsViolated(format!(
+                        "column {name} is given more than once"
+                    )));
+                }
+            }
+            let mut values = values
+                .into_iter()
+                .map(|v| v.unwrap_or(PrimitiveType::None))
+                .collect();
+            self.inner.row_columns(schema, &mut values)
+        })?;
+        self.flush_accepted(full).await
+    }
+
+    /// Append whole columns to the buffer, in the order of the table's columns.
+    ///
+    /// Columns are converted to the column types as [`AutoFitTableAppender`](super::AutoFitTableAppender) does.
+    /// When buffer is full, it is flushed in batches of `batch_size` rows and the result of the last batch is returned.
+    /// When buffer is not full, None is returned.
+    ///
+    /// Columns that do not fit the schema are rejected without changing the buffer,
+    /// accepted ones are kept even if the flush fails, see [`append_row`](Self::append_row).
+    pub async fn append_columns(
+        &mut self,
+        columns: Vec<VectorImpl>,
+    ) -> Result<Option<ConstantImpl>> {
+        self.append_converted(&columns, None).await
+    }
+
+    /// Append the rows of `table`, whose column names must match the table's.
+    ///
+    /// See [`append_columns`](Self::append_columns).
+    pub async fn append_table(&mut self, table: &Table) -> Result<Option<ConstantImpl>> {
+        self.append_converted(table.columns(), Some(table.column_names()))
+            .await
+    }
+
+    /// Append typed rows, whose fields must be named and ordered as the table's columns.
+    ///
+    /// See [`append_columns`](Self::append_columns).
+    pub async fn append_rows<T: DolphinRow>(&mut self, rows: &[T]) -> Result<Option<ConstantImpl>> {
+        self.append_table(&T::to_table(rows)?).await
+    }
+
+    async fn append_converted(
+        &mut self,
+        columns: &[VectorImpl],
+        names: Option<&[String]>,
+    ) -> Result<Option<ConstantImpl>> {
+        let rows = columns.first().map_or(0, |c| c.len());
+        if let Some(column) = columns.iter().find(|c| c.len() != rows) {
+            return Err(Error::InvalidData {
+                expect: format!("{rows} rows in every column"),
+                actual: format!("{} rows", column.len()),
+            });
+        }
+
+        let inner = &self.inner;
+        let full = inner.append(|schema| {
+            if let Some(names) = names {
+                let matches = names.len() == schema.names.len()
+                    && names
+                        .iter()
+                        .zip(schema.names.iter())
+                        .all(|(a, b)| a.eq_ignore_ascii_case(b));
+                if !matches {
+                    return Err(Error::InvalidData {
+                        expect: format!("columns {}", schema.names.join(",")),
+                        actual: format!("columns {}", names.join(",")),
+                    });
+                }
+            }
+            if columns.len() != schema.types.len() {
+                return Err(Error::InvalidData {
+                    expect: format!("{} columns in {}", schema.types.len(), inner.table_name),
+                    actual: format!("{} columns", columns.len()),
+                });
             }
+            let columns = columns
+                .iter()
+                .zip(schema.types.iter().zip(schema.scales.iter()))
+                .map(|(column, (data_type, scale))| convert_column(column, *data_type, *scale))
+                .collect::<Result<Vec<_>>>()?;
+            let mut serialized = Vec::new();
+            for column in columns.iter() {
+                column.serialize_le(&mut serialized)?;
+            }
+            Ok((columns, serialized.len()))
+        })?;
+        self.flush_accepted(full).await
+    }
+
+    /// Appends one row like [`append_row`](Self::append_row), returning the rows the server
+    /// confirmed if the buffer was flushed.
+    pub(crate) async fn append_row_counted(
+        &mut self,
+        row: &mut Vec<PrimitiveType>,
+    ) -> Result<Option<usize>> {
+        let full = self
+            .inner
+            .append(|schema| self.inner.row_columns(schema, row))?;
+        if !full {
+            return Ok(None);
         }
-        self.size += 1;
-        if self.size == self.batch_size {
-            return self.flush().await;
+        let mut sent = 0;
+        // the failure is reported by the status like for append_row
+        let _ = self.inner.flush_counted(&mut sent).await;
+        Ok(Some(sent))
+    }
+
+    /// Flushes the buffer after appending accepted rows, whose failure is only reported by the status.
+    async fn flush_accepted(&mut self, full: bool) -> Result<Option<ConstantImpl>> {
+        if !full {
+            return Ok(None);
         }
-        Ok(None)
+        Ok(self.inner.flush().await.unwrap_or(None))
     }
-    /// Manually flush the buffer.
-    pub async fn flush(&mut self) -> Result<Option<ConstantImpl>, Error> {
-        let mut builder = TableBuilder::new();
-        let content = std::mem::take(&mut self.buffer);
-        self.buffer = self.columns.clone();
-        self.size = 0;
-        builder.with_contents(content, self.column_names.clone());
-        self.client
-            .run_function(self.script.as_str(), &[builder.build().unwrap().into()])
-            .await
+
+    /// Manually flush the buffer, after retrying the batches lost with the connection before.
+    ///
+    /// The buffer is sent in batches of at most `batch_size` rows, returning the result of the last one.
+    /// A batch the server still rejects after the retries of the [`RetryPolicy`] is set aside and
+    /// the later batches are sent. A batch lost with the connection is kept with the later ones and
+    /// retried by the next flush, before newer rows. The first failure is returned, and the unsent
+    /// batches can be taken with [`take_failed`](Self::take_failed).
+    pub async fn flush(&mut self) -> Result<Option<ConstantImpl>> {
+        self.inner.flush().await
+    }
+
+    /// Flushes like [`flush`](Self::flush), returning the rows the server confirmed.
+    pub(crate) async fn flush_counted(&mut self) -> Result<usize> {
+        let mut sent = 0;
+        self.inner.flush_counted(&mut sent).await?;
+        Ok(sent)
+    }
+
+    /// Stops the background flushes and flushes the remaining rows, reporting why they could not be sent.
+    ///
+    /// The connection is closed for every clone of this writer. Rows that could not be sent are dropped,
+    /// unless a write-ahead log keeps them for the next writer.
+    pub async fn close(self) -> Result<Option<ConstantImpl>> {
+        if let Some(timer) = self.inner.timer.lock().unwrap().take() {
+            timer.abort();
+        }
+        let res = self.inner.flush().await;
+        self.inner.client.lock().await.take();
+        res
+    }
+    /// Replaces the connection, then flushes the rows that failed to be sent through the old one.
+    pub async fn reconnect(&mut self, client: Client) -> Result<Option<ConstantImpl>> {
+        *self.inner.client.lock().await = Some(client);
+        self.inner.flush().await
+    }
+    /// Takes the rows of the batches that failed to flush, the rejected ones first,
+    /// so they are no longer retried.
+    ///
+    /// They are also removed from the write-ahead log.
+    pub async fn take_failed(&mut self) -> Result<Table> {
+        // wait for a running flush to return its batches
+        let _client = self.inner.client.lock().await;
+        let rejected = mem::take(&mut *self.inner.rejected.lock().unwrap());
+        let failed = mem::take(&mut *self.inner.failed.lock().unwrap());
+        if let Some(log) = self.inner.log.lock().unwrap().as_mut() {
+            log.truncate(&[])?;
+        }
+        let schema = self.inner.schema();
+        let empty = schema.batch(schema.columns.clone())?;
+        rejected
+            .iter()
+            .chain(failed.iter())
+            .try_fold(empty, |rows, batch| rows.concat(batch))
     }
     /// Returns the number of rows in the buffer.
     pub fn size(&self) -> usize {
-        self.buffer[0].len()
+        self.inner.size()
+    }
+    /// Rows sent and left to send, with the cause of the last flush if it failed.
+    ///
+    /// Unsent rows are buffered or in a batch that failed, see [`take_failed`](Self::take_failed).
+    pub fn status(&self) -> WriterStatus {
+        let failed = self.inner.failed.lock().unwrap();
+        let rejected = self.inner.rejected.lock().unwrap();
+        WriterStatus {
+            sent_rows: self.inner.sent_rows.load(Ordering::Relaxed),
+            unsent_rows: self.inner.size()
+                + batch_rows(failed.iter())
+                + batch_rows(rejected.iter()),
+            error: self.inner.error.lock().unwrap().clone(),
+        }
     }
 }
 
-impl Drop for TableWriter<'_> {
-    fn drop(&mut self) {
-        block_in_place(|| {
-            Handle::current().block_on(async move {
-                let _ = self.flush().await;
+impl Inner {
+    async fn flush(&self) -> Result<Option<ConstantImpl>> {
+        self.flush_counted(&mut 0).await
+    }
+
+    /// Flushes like [`flush`](Self::flush), adding the rows the server confirmed to `sent`.
+    async fn flush_counted(&self, sent: &mut usize) -> Result<Option<ConstantImpl>> {
+        // holding the connection keeps batches in order
+        let mut client = self.client.lock().await;
+        let Some(client) = client.as_mut() else {
+            return Err(Error::ChannelClosed("writer is closed".into()));
+        };
+        let (schema, content) = {
+            let mut log = self.log.lock().unwrap();
+            let mut buffer = self.buffer.lock().unwrap();
+            // rows appended from now on are logged apart from the ones sent
+            if let Some(log) = log.as_mut() {
+                log.seal()?;
+            }
+            buffer.bytes = 0;
+            let columns = buffer.schema.columns.clone();
+            (
+                buffer.schema.clone(),
+                mem::replace(&mut buffer.columns, columns),
+            )
+        };
+        let mut batches = mem::take(&mut *self.failed.lock().unwrap());
+        let rows = content[0].len();
+        let batch_size = self.batch_size as usize;
+        if rows > batch_size {
+            for start in (0..rows).step_by(batch_size) {
+                let columns = content
+                    .iter()
+                    .map(|c| c.slice(start..rows.min(start + batch_size)))
+                    .collect::<Result<Vec<_>>>()?;
+                batches.push_back(schema.batch(columns)?);
+            }
+        } else if rows > 0 || batches.is_empty() {
+            batches.push_back(schema.batch(content)?);
+        }
+
+        let retry_policy = self.retry_policy.lock().unwrap().clone();
+        let mut res = Ok(None);
+        let mut error = None;
+        let mut rejected = vec![];
+        while let Some(batch) = batches.pop_front() {
+            match self.send(client, &batch, &retry_policy).await {
+                Ok(result) => {
+                    self.sent_rows.fetch_add(batch.len(), Ordering::Relaxed);
+                    *sent += batch.len();
+                    res = Ok(result);
+                }
+                // the server refuses this batch, which must not hold back the later ones
+                Err(err @ Error::BadResponse(_)) => {
+                    rejected.push(batch);
+                    error.get_or_insert(err);
+                }
+                Err(err) => {
+                    batches.push_front(batch);
+                    error.get_or_insert(err);
+                    break;
+                }
+            }
+        }
+        *self.error.lock().unwrap() = error.as_ref().map(Error::to_string);
+
+        let mut log = self.log.lock().unwrap();
+        let mut all_rejected = self.rejected.lock().unwrap();
+        all_rejected.extend(rejected);
+        // only the batches left unsent stay logged
+        let truncated = match log.as_mut() {
+            Some(log) => log.truncate(
+                &all_rejected
+                    .iter()
+                    .chain(batches.iter())
+                    .collect::<Vec<_>>(),
+            ),
+            None => Ok(()),
+        };
+        self.failed.lock().unwrap().extend(batches);
+        truncated?;
+        match error {
+            Some(err) => Err(err),
+            None => res,
+        }
+    }
+
+    /// Buffers the rows converted by `rows` to the current schema, with their approximate size.
+    ///
+    /// They are logged once buffered, if a log is set. Returns whether the buffer is full.
+    fn append(
+        &self,
+        rows: impl FnOnce(&Schema) -> Result<(Vec<VectorImpl>, usize)>,
+    ) -> Result<bool> {
+        // holding the log keeps the schema, and keeps flushes from sealing the segment
+        // between the buffering and the logging of the rows
+        let mut log = self.log.lock().unwrap();
+        let schema = self.schema();
+        let (columns, bytes) = rows(&schema)?;
+
+        // a failed extend or log write takes the rows back out of the buffer
+        let mut buffer = self.buffer.lock().unwrap();
+        let rows = buffer.columns[0].len();
+        let appended = columns
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, column)| buffer.columns[i].extend_from(column))
+            .and_then(|()| match log.as_mut() {
+                Some(log) => log.append(&schema.batch(columns)?),
+                None => Ok(()),
             });
+        if let Err(err) = appended {
+            for column in buffer.columns.iter_mut() {
+                column.resize(rows);
+            }
+            return Err(err);
+        }
+        buffer.bytes += bytes;
+        Ok(buffer.columns[0].len() >= self.batch_size as usize
+            || buffer.bytes >= self.max_bytes.load(Ordering::Relaxed))
+    }
+
+    /// Converts `row` to one-row columns of `schema`, with its approximate size.
+    fn row_columns(
+        &self,
+        schema: &Schema,
+        row: &mut Vec<PrimitiveType>,
+    ) -> Result<(Vec<VectorImpl>, usize)> {
+        if schema.columns.len() != row.len() {
+            return Err(Error::InvalidData {
+                expect: format!("{} columns in {}", schema.columns.len(), self.table_name),
+                actual: format!("{} columns", row.len()),
+            });
+        }
+        let mut columns = schema.columns.clone();
+        let mut bytes = 0;
+        for (column, data) in columns.iter_mut().zip(row.drain(..)) {
+            let data = match data {
+                PrimitiveType::ZonedDateTime(dt) => {
+                    PrimitiveType::NaiveDateTime(dt.with_timezone(&self.timezone).naive_local())
+                }
+                data => data,
+            };
+            bytes += value_size(&data, column.data_type());
+            column.push_primitive_type(data)?;
+        }
+        Ok((columns, bytes))
+    }
+
+    async fn send(
+        &self,
+        client: &mut Client,
+        batch: &Table,
+        retry_policy: &RetryPolicy,
+    ) -> Result<Option<ConstantImpl>> {
+        let args = [batch.clone().into()];
+        let mut retries = 0;
+        loop {
+            match client.run_function(self.script.as_str(), &args).await {
+                Err(_) if retries < retry_policy.max_retries => {
+                    retries += 1;
+                    tokio::time::sleep(retry_policy.interval).await;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        self.buffer.lock().unwrap().schema.clone()
+    }
+
+    fn size(&self) -> usize {
+        self.buffer.lock().unwrap().columns[0].len()
+    }
+}
+
+impl Drop for Inner {
+    // rows left by a writer that was not closed are flushed in the background
+    fn drop(&mut self) {
+        if let Some(timer) = self
+            .timer
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+        {
+            timer.abort();
+        }
+        let logged = self
+            .log
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some();
+        let buffer = self.buffer.get_mut().unwrap_or_else(|e| e.into_inner());
+        let failed = self.failed.get_mut().unwrap_or_else(|e| e.into_inner());
+        let rejected = self.rejected.get_mut().unwrap_or_else(|e| e.into_inner());
+        let buffered = buffer.columns.first().map_or(0, |c| c.len());
+        let unsent = buffered + batch_rows(failed.iter()) + batch_rows(rejected.iter());
+        if buffered == 0 && failed.is_empty() {
+            // rejected batches are not sent again
+            if unsent > 0 {
+                warn_unsent(&self.table_name, unsent, logged, "rejected by the server");
+            }
+            return;
+        }
+        let Ok(handle) = Handle::try_current() else {
+            warn_unsent(&self.table_name, unsent, logged, "no runtime to flush them");
+            return;
+        };
+        let Some(client) = self.client.get_mut().take() else {
+            warn_unsent(&self.table_name, unsent, logged, "the writer is closed");
+            return;
+        };
+
+        let mut inner = Inner {
+            client: tokio::sync::Mutex::new(Some(client)),
+            table_name: mem::take(&mut self.table_name),
+            script: mem::take(&mut self.script),
+            timezone: self.timezone,
+            batch_size: self.batch_size,
+            max_bytes: AtomicUsize::new(usize::MAX),
+            retry_policy: Mutex::new(mem::take(
+                self.retry_policy
+                    .get_mut()
+                    .unwrap_or_else(|e| e.into_inner()),
+            )),
+            buffer: Mutex::new(Buffer {
+                schema: buffer.schema.clone(),
+                columns: mem::take(&mut buffer.columns),
+                bytes: 0,
+            }),
+            failed: Mutex::new(mem::take(failed)),
+            // kept in the log until a writer takes them
+            rejected: Mutex::new(mem::take(rejected)),
+            sent_rows: AtomicUsize::new(0),
+            error: Mutex::new(None),
+            log: Mutex::new(self.log.get_mut().unwrap_or_else(|e| e.into_inner()).take()),
+            log_sync: Mutex::new(LogSync::default()),
+            timer: Mutex::new(None),
+        };
+        handle.spawn(async move {
+            let res = inner.flush().await;
+            // leaves nothing for the drop of this copy
+            inner.client.get_mut().take();
+            let buffer = inner.buffer.get_mut().unwrap();
+            let unsent = buffer.columns.first().map_or(0, |c| c.len())
+                + batch_rows(mem::take(inner.failed.get_mut().unwrap()).iter())
+                + batch_rows(mem::take(inner.rejected.get_mut().unwrap()).iter());
+            buffer.columns.clear();
+            if unsent > 0 {
+                let cause = match res {
+                    Err(err) => err.to_string(),
+                    Ok(_) => "rejected by the server".into(),
+                };
+                warn_unsent(&inner.table_name, unsent, logged, &cause);
+            }
         });
     }
 }
+
+impl Schema {
+    async fn fetch(client: &mut Client, table: &str) -> Result<Self> {
+        let (names, types) = fetch_schema(client, table).await?;
+        let partition_columns = fetch_partitions(client, table).await?;
+        let scales = fetch_scales(client, table, &types).await?;
+        let columns = types
+            .iter()
+            .copied()
+            .map(empty_column)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            columns,
+            names,
+            types,
+            scales,
+            partition_columns,
+        })
+    }
+
+    fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n.eq_ignore_ascii_case(name))
+    }
+
+    fn batch(&self, columns: Vec<VectorImpl>) -> Result<Table> {
+        let mut builder = TableBuilder::new();
+        builder.with_contents(columns, self.names.clone());
+        builder.build()
+    }
+
+    /// Moves the rows of `table` to these columns by name, missing columns are null.
+    fn conform(&self, table: &Table) -> Result<Table> {
+        if let Some(name) = table
+            .column_names()
+            .iter()
+            .find(|n| self.index(n).is_none())
+        {
+            return Err(Error::ConstraintsViolated(format!(
+                "column {name} does not exist"
+            )));
+        }
+        let columns = self
+            .names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                match table
+                    .column_names()
+                    .iter()
+                    .position(|n| n.eq_ignore_ascii_case(name))
+                {
+                    Some(j) => convert_column(&table.columns()[j], self.types[i], self.scales[i]),
+                    None => {
+                        let mut column = self.columns[i].clone();
+                        column.resize(table.len());
+                        Ok(column)
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.batch(columns)
+    }
+}
+
+fn batch_rows<'a>(batches: impl Iterator<Item = &'a Table>) -> usize {
+    batches.map(Table::len).sum()
+}
+
+fn warn_unsent(table: &str, rows: usize, logged: bool, cause: &str) {
+    let fate = if logged {
+        "kept in the write-ahead log"
+    } else {
+        "lost"
+    };
+    log::warn!("{rows} rows of {table} were not sent before the writer was dropped and are {fate}: {cause}");
+}
+
+/// Approximate size of `value` once written into a column of `data_type`.
+fn value_size(value: &PrimitiveType, data_type: DataType) -> usize {
+    match value {
+        PrimitiveType::String(s) | PrimitiveType::Symbol(s) => s.len() + 1,
+        PrimitiveType::Blob(b) => b.len() + 4,
+        PrimitiveType::VecI8(v) => size_of_val(v.as_slice()),
+        PrimitiveType::VecI16(v) => size_of_val(v.as_slice()),
+        PrimitiveType::VecI32(v) => size_of_val(v.as_slice()),
+        PrimitiveType::VecI64(v) => size_of_val(v.as_slice()),
+        PrimitiveType::VecF32(v) => size_of_val(v.as_slice()),
+        PrimitiveType::VecF64(v) => size_of_val(v.as_slice()),
+        _ => match data_type {
+            DataType::Bool | DataType::Char => 1,
+            DataType::Short => 2,
+            DataType::Int
+            | DataType::Date
+            | DataType::Month
+            | DataType::Time
+            | DataType::Minute
+            | DataType::Second
+            | DataType::DateTime
+            | DataType::DateHour
+            | DataType::Float
+            | DataType::Decimal32 => 4,
+            DataType::Decimal128 => 16,
+            _ => 8,
+        },
+    }
+}
+
+/// `loadTable(db_path, table_name)`, or the shared table `table_name` if `db_path` is empty.
+pub(crate) fn table_expr(db_path: &str, table_name: &str) -> String {
+    if db_path.is_empty() {
+        table_name.to_string()
+    } else {
+        format!("loadTable(\"{db_path}\", \"{table_name}\")")
+    }
+}
+
+/// Buffer column for `data_type`, symbols are uploaded as strings.
+pub(crate) fn empty_column(data_type: DataType) -> Result<VectorImpl> {
+    let unsupported = Error::Unsupported {
+        data_form: DataForm::Vector.to_string(),
+        data_type: data_type.to_string(),
+    };
+    match data_type {
+        DataType::Symbol => Ok(StringVector::new().into()),
+        DataType::Void | DataType::Any => Err(unsupported),
+        _ => VectorImpl::from_type(data_type).ok_or(unsupported),
+    }
+}
+
+/// Converts `column` to `data_type` for a buffer column, decimals are rescaled to `scale`.
+pub(crate) fn convert_column(
+    column: &VectorImpl,
+    data_type: DataType,
+    scale: Option<u32>,
+) -> Result<VectorImpl> {
+    // symbols are uploaded as strings and converted by the server
+    let data_type = match data_type {
+        DataType::Symbol => DataType::String,
+        t => t,
+    };
+    match scale {
+        Some(scale) => column.cast_decimal(data_type, scale),
+        None if column.data_type() == data_type => Ok(column.clone()),
+        None => column.cast(data_type),
+    }
+}
+
+/// Scales of the decimal columns of `table`, None for other columns.
+pub(crate) async fn fetch_scales(
+    client: &mut Client,
+    table: &str,
+    column_types: &[DataType],
+) -> Result<Vec<Option<u32>>> {
+    let scales = match client
+        .run_script(format!("schema({table}).colDefs.extra").as_str())
+        .await
+    {
+        Ok(Some(ConstantImpl::Vector(VectorImpl::Int(extra)))) => extra
+            .iter()
+            .zip(column_types.iter())
+            .map(|(scale, t)| match t {
+                DataType::Decimal32 | DataType::Decimal64 | DataType::Decimal128 => {
+                    scale.into_inner().map(|s| s as u32)
+                }
+                _ => None,
+            })
+            .collect(),
+        // servers without decimal support have no extra column and fail the script
+        Ok(_) | Err(Error::BadResponse(_)) => vec![None; column_types.len()],
+        Err(err) => return Err(err),
+    };
+    Ok(scales)
+}
+
+/// Column names and types of `table`, which may be any expression evaluating to a table.
+pub(crate) async fn fetch_schema(
+    client: &mut Client,
+    table: &str,
+) -> Result<(Vec<String>, Vec<DataType>)> {
+    let invalid = |actual: Option<ConstantImpl>| Error::InvalidData {
+        expect: "schema vector".into(),
+        actual: actual.map_or("nothing".into(), |c| c.data_form().to_string()),
+    };
+
+    let names = match client
+        .run_script(format!("schema({table}).colDefs.name").as_str())
+        .await?
+    {
+        Some(ConstantImpl::Vector(VectorImpl::String(names))) => {
+            names.iter().map(|name| name.to_string()).collect()
+        }
+        res => return Err(invalid(res)),
+    };
+
+    let types = match client
+        .run_script(format!("schema({table}).colDefs.typeInt").as_str())
+        .await?
+    {
+        Some(ConstantImpl::Vector(VectorImpl::Int(types))) => types
+            .iter()
+            .map(|t| DataType::try_from(t.into_inner().unwrap_or_default() as u8))
+            .collect::<Result<Vec<_>>>()?,
+        res => return Err(invalid(res)),
+    };
+
+    Ok((names, types))
+}
+
+/// Partitioning columns of `table`, empty if it is not partitioned.
+pub(crate) async fn fetch_partitions(
+    client: &mut Client,
+    table: &str,
+) -> Result<Vec<PartitionColumn>> {
+    let schema = match client
+        .run_script(format!("schema({table})").as_str())
+        .await?
+    {
+        Some(ConstantImpl::Dictionary(DictionaryImpl::String(schema))) => schema,
+        res => {
+            return Err(Error::InvalidData {
+                expect: "schema dictionary".into(),
+                actual: res.map_or("nothing".into(), |c| c.data_form().to_string()),
+            })
+        }
+    };
+    let get = |key: &str| {
+        schema
+            .get(&DolphinString::new(key.to_string()))
+            .map(Any::get)
+    };
+
+    let Some(indices) = get("partitionColumnIndex") else {
+        return Ok(vec![]);
+    };
+    let invalid = |key: &str| Error::InvalidData {
+        expect: format!("{key} of schema"),
+        actual: "unexpected value".into(),
+    };
+
+    // composite partitions report one element per level
+    let levels = |key: &str| -> Result<Vec<ConstantImpl>> {
+        match get(key) {
+            Some(c @ ConstantImpl::Vector(v)) if matches!(indices, ConstantImpl::Vector(_)) => {
+                Ok((0..v.len()).filter_map(|i| c.get(i)).collect())
+            }
+            Some(c) => Ok(vec![c.clone()]),
+            None => Err(invalid(key)),
+        }
+    };
+    let int = |c: &ConstantImpl| match c {
+        ConstantImpl::Scalar(ScalarImpl::Int(v)) => v.into_inner(),
+        _ => None,
+    };
+
+    let indices = levels("partitionColumnIndex")?;
+    let names = levels("partitionColumnName")?;
+    let types = levels("partitionType")?;
+    let schemes = levels("partitionSchema")?;
+    if [names.len(), types.len(), schemes.len()] != [indices.len(); 3] {
+        return Err(invalid("partitionColumnName"));
+    }
+
+    indices
+        .iter()
+        .zip(names)
+        .zip(types.iter())
+        .zip(schemes)
+        .map(|(((index, name), partition_type), scheme)| {
+            let ConstantImpl::Scalar(ScalarImpl::String(name)) = name else {
+                return Err(invalid("partitionColumnName"));
+            };
+            Ok(PartitionColumn {
+                name: name.to_string(),
+                index: int(index)
+                    .and_then(|i| usize::try_from(i).ok())
+                    .ok_or_else(|| invalid("partitionColumnIndex"))?,
+                partition_type: int(partition_type)
+                    .ok_or_else(|| invalid("partitionType"))?
+                    .try_into()?,
+                scheme,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,215 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{Error, Result},
+    types::{Constant, ConstantImpl, Table},
+    Deserialize, Serialize,
+};
+
+const EXTENSION: &str = "wal";
+
+/// When the write-ahead log of a [`TableWriter`](super::TableWriter) forces its rows to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogSync {
+    /// Before each append returns, so every accepted row survives a crash of the machine.
+    #[default]
+    Always,
+    /// Before each flush sends the logged rows, grouping the appends in between into one sync.
+    ///
+    /// Rows appended since the last flush may be lost if the machine crashes,
+    /// but not if only the process does.
+    OnFlush,
+}
+
+/// Rows appended to a [`TableWriter`](super::TableWriter) and not yet confirmed by the server.
+///
+/// The log is a directory of numbered segments, each a sequence of tables serialized in
+/// little endian and prefixed by their length. Records are only appended to the newest
+/// segment; a flush seals it, and once its batches are sent the sealed segments are replaced
+/// by the batches left unsent, which are written in the number reserved below the newest segment.
+///
+/// Replay is at least once: rows sent just before a crash may be replayed by the next writer.
+pub(crate) struct WriteAheadLog {
+    dir: PathBuf,
+    /// Oldest segment that may exist.
+    first: u64,
+    segment: u64,
+    file: File,
+    written: bool,
+    sync: LogSync,
+}
+
+impl WriteAheadLog {
+    /// Opens the log in `dir`, returning the tables left by a previous writer, oldest first.
+    pub(crate) async fn open(dir: &Path, sync: LogSync) -> Result<(Self, Vec<Table>)> {
+        tokio::fs::create_dir_all(dir).await?;
+        let segments = segments(dir).await?;
+
+        let mut pending = vec![];
+        for segment in segments.iter() {
+            let content = tokio::fs::read(segment_path(dir, *segment)).await?;
+            pending.extend(read_records(&content).await?);
+        }
+
+        // the number below each segment is reserved for the batches left unsent
+        let segment = segments.last().map_or(1, |s| s + 2);
+        let file = {
+            let dir = dir.to_path_buf();
+            tokio::task::spawn_blocking(move || create_segment(&dir, segment))
+                .await
+                .map_err(|e| Error::IO(e.into()))??
+        };
+        Ok((
+            Self {
+                dir: dir.to_path_buf(),
+                first: segments.first().copied().unwrap_or(segment),
+                segment,
+                file,
+                written: false,
+                sync,
+            },
+            pending,
+        ))
+    }
+
+    pub(crate) fn set_sync(&mut self, sync: LogSync) {
+        self.sync = sync;
+    }
+
+    /// Appends `table` to the newest segment, returning once it is written as the sync policy requires.
+    pub(crate) fn append(&mut self, table: &Table) -> Result<()> {
+        self.file.write_all(&record(table)?)?;
+        self.written = true;
+        if self.sync == LogSync::Always {
+            self.file.sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// Syncs the newest segment and starts a new one, so the records written so far
+    /// can be replaced by [`truncate`](Self::truncate).
+    pub(crate) fn seal(&mut self) -> Result<()> {
+        if self.written {
+            self.file.sync_data()?;
+            self.file = create_segment(&self.dir, self.segment + 2)?;
+            self.segment += 2;
+            self.written = false;
+        }
+        Ok(())
+    }
+
+    /// Replaces the sealed segments by `unsent`, the batches of them that are not confirmed yet.
+    pub(crate) fn truncate(&mut self, unsent: &[&Table]) -> Result<()> {
+        let reserved = self.segment - 1;
+        let path = segment_path(&self.dir, reserved);
+        if unsent.is_empty() {
+            remove_segment(&path)?;
+        } else {
+            // written aside and renamed, so a crash leaves either the old or the new batches
+            let tmp = path.with_extension("tmp");
+            let mut file = File::create(&tmp)?;
+            for table in unsent {
+                file.write_all(&record(table)?)?;
+            }
+            file.sync_all()?;
+            fs::rename(&tmp, &path)?;
+        }
+        for segment in self.first..reserved {
+            remove_segment(&segment_path(&self.dir, segment))?;
+        }
+        sync_dir(&self.dir)?;
+        self.first = reserved;
+        Ok(())
+    }
+}
+
+/// `table` serialized in little endian and prefixed by its length.
+fn record(table: &Table) -> Result<Vec<u8>> {
+    let mut record = vec![0; 4];
+    table.serialize_le(&mut record)?;
+    let len = u32::try_from(record.len() - 4)
+        .map_err(|_| Error::ConstraintsViolated("log record must be smaller than 4 GiB".into()))?;
+    record[..4].copy_from_slice(&len.to_le_bytes());
+    Ok(record)
+}
+
+fn segment_path(dir: &Path, segment: u64) -> PathBuf {
+    dir.join(format!("{segment:020}.{EXTENSION}"))
+}
+
+fn create_segment(dir: &Path, segment: u64) -> Result<File> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(segment_path(dir, segment))?;
+    sync_dir(dir)?;
+    Ok(file)
+}
+
+fn remove_segment(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Makes the creation, renaming and removal of segments in `dir` durable.
+fn sync_dir(dir: &Path) -> Result<()> {
+    // directories cannot be opened as files on windows, where the entries are durable already
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+/// Numbers of the segments in `dir`, in ascending order.
+async fn segments(dir: &Path) -> Result<Vec<u64>> {
+    let mut segments = vec![];
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+            continue;
+        }
+        if let Some(segment) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.parse().ok())
+        {
+            segments.push(segment);
+        }
+    }
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+async fn read_records(mut content: &[u8]) -> Result<Vec<Table>> {
+    let mut tables = vec![];
+    while content.len() >= 4 {
+        let len = u32::from_le_bytes(content[..4].try_into().unwrap()) as usize;
+        // a record cut short by a crash was never acknowledged
+        if content.len() - 4 < len {
+            break;
+        }
+        let mut record = &content[4..4 + len];
+        content = &content[4 + len..];
+
+        let mut constant = ConstantImpl::default();
+        constant.deserialize_le(&mut record).await?;
+        match constant {
+            ConstantImpl::Table(table) => tables.push(table),
+            c => {
+                return Err(Error::IO(std::io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("log record is a {}, not a table", c.data_form()),
+                )))
+            }
+        }
+    }
+    Ok(tables)
+}
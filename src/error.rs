@@ -35,6 +35,18 @@ pub enum Error {
     ChannelClosed(String),
     #[error("0")]
     StreamSubscriptionError(String),
+    #[cfg(feature = "serde")]
+    #[error("serde error: {0}")]
+    Serde(String),
+    #[cfg(feature = "arrow")]
+    #[error("arrow error: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
+    #[cfg(feature = "csv")]
+    #[error("csv error: {0}")]
+    Csv(#[from] csv::Error),
+    #[cfg(feature = "polars")]
+    #[error("polars error: {0}")]
+    Polars(#[from] polars::error::PolarsError),
 }
 
 impl<T> From<tokio::sync::mpsc::error::SendError<T>> for Error {
@@ -29,6 +29,30 @@ pub(crate) trait Serialize {
     {
         self.serialize(buffer)
     }
+
+    /// serialize consecutive values, one by one unless the type can write them at once.
+    fn serialize_slice<B>(data: &[Self], buffer: &mut B) -> Result<()>
+    where
+        Self: Sized,
+        B: BufMut,
+    {
+        for v in data {
+            v.serialize(buffer)?;
+        }
+        Ok(())
+    }
+
+    /// similar to `serialize_slice()`, but in little endian.
+    fn serialize_slice_le<B>(data: &[Self], buffer: &mut B) -> Result<()>
+    where
+        Self: Sized,
+        B: BufMut,
+    {
+        for v in data {
+            v.serialize_le(buffer)?;
+        }
+        Ok(())
+    }
 }
 
 pub(crate) trait Deserialize {
@@ -42,6 +66,30 @@ pub(crate) trait Deserialize {
     {
         self.deserialize(reader).await
     }
+
+    /// deserialize consecutive values, one by one unless the type can read them at once.
+    async fn deserialize_slice<R>(slots: &mut [Self], reader: &mut R) -> Result<()>
+    where
+        Self: Sized,
+        R: AsyncBufReadExt + Unpin,
+    {
+        for slot in slots.iter_mut() {
+            slot.deserialize(reader).await?;
+        }
+        Ok(())
+    }
+
+    /// similar to `deserialize_slice()`, but in little endian.
+    async fn deserialize_slice_le<R>(slots: &mut [Self], reader: &mut R) -> Result<()>
+    where
+        Self: Sized,
+        R: AsyncBufReadExt + Unpin,
+    {
+        for slot in slots.iter_mut() {
+            slot.deserialize_le(reader).await?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy, Default, Debug)]
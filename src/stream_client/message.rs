@@ -1,4 +1,7 @@
-use crate::types::VectorImpl;
+use crate::{
+    error::{Error, Result},
+    types::{ConstantImpl, DataForm, DolphinRow, VectorImpl},
+};
 
 #[derive(Debug, Clone)]
 pub struct Message {
@@ -24,4 +27,34 @@ impl Message {
     pub fn msg(&self) -> &VectorImpl {
         &self.msg
     }
+
+    /// Decodes the row of this message, whose columns must be ordered as the fields of `T`.
+    pub fn decode<T: DolphinRow>(&self) -> Result<T> {
+        let names = T::column_names();
+        if self.msg.len() != names.len() {
+            return Err(Error::InvalidData {
+                expect: format!("{} columns", names.len()),
+                actual: format!("{} columns", self.msg.len()),
+            });
+        }
+
+        let columns = (0..self.msg.len())
+            .map(|i| match self.msg.get(i) {
+                Some(ConstantImpl::Scalar(value)) => {
+                    let mut column =
+                        VectorImpl::from_type(value.data_type()).ok_or(Error::Unsupported {
+                            data_form: DataForm::Vector.to_string(),
+                            data_type: value.data_type().to_string(),
+                        })?;
+                    column.push_scalar(value);
+                    Ok(column)
+                }
+                _ => Err(Error::InvalidConvert {
+                    from: format!("{} column", names[i]),
+                    to: DataForm::Scalar.to_string(),
+                }),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        T::from_row(&columns.iter().collect::<Vec<_>>(), 0)
+    }
 }
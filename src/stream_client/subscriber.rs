@@ -3,7 +3,6 @@ use std::sync::Arc;
 
 use futures::Stream;
 use tokio::runtime::Handle;
-use tokio::task::block_in_place;
 use tokio::{
     net::ToSocketAddrs,
     sync::mpsc::{unbounded_channel, UnboundedReceiver},
@@ -27,27 +26,35 @@ pub struct Subscriber {
 }
 
 impl Subscriber {
-    async fn unsubscribe(&mut self) -> Result<()> {
-        if let Some(client) = self.client.as_mut() {
-            let topic = get_topic(
-                client,
-                self.req.table_name.clone(),
-                self.req.action_name.clone(),
-            )
-            .await?;
-            stop_publish_table(client, topic, &self.req).await?;
+    /// Instructs the server to stop publishing, reporting if it fails.
+    ///
+    /// Dropping a `Subscriber` unsubscribes in the background instead, logging failures.
+    pub async fn close(mut self) -> Result<()> {
+        match self.client.take() {
+            Some(mut client) => unsubscribe(&mut client, &self.req).await,
+            None => Ok(()),
         }
-        Ok(())
     }
 }
 
 impl Drop for Subscriber {
     fn drop(&mut self) {
-        block_in_place(|| {
-            Handle::current().block_on(async move {
-                let _ = self.unsubscribe().await;
+        // without a runtime the server stops publishing once the connection is closed
+        let Ok(handle) = Handle::try_current() else {
+            return;
+        };
+        if let Some(mut client) = self.client.take() {
+            let req = self.req.clone();
+            handle.spawn(async move {
+                if let Err(err) = unsubscribe(&mut client, &req).await {
+                    log::warn!(
+                        "failed to unsubscribe from {}/{}, the server publishes until the connection is closed: {err}",
+                        req.table_name,
+                        req.action_name
+                    );
+                }
             });
-        });
+        }
     }
 }
 
@@ -227,6 +234,11 @@ async fn publish_table(client: &mut Client, _topic: String, req: &Request) -> Re
     Ok(())
 }
 
+async fn unsubscribe(client: &mut Client, req: &Request) -> Result<()> {
+    let topic = get_topic(client, req.table_name.clone(), req.action_name.clone()).await?;
+    stop_publish_table(client, topic, req).await
+}
+
 fn stop_publish_table_args(addr: SocketAddr, req: Request) -> Vec<ConstantImpl> {
     let mut res = Vec::with_capacity(7);
 
@@ -94,6 +94,29 @@ macro_rules! vector_interface {
                 )*
                 }
             }
+
+            pub(crate) fn gather(&self, indices: &[usize]) -> Self {
+                match self {
+                $(
+                    ArrayVectorImpl::$data_type(v) => ArrayVectorImpl::$data_type(v.gather(indices)),
+                )*
+                }
+            }
+
+            pub(crate) fn extend_from(&mut self, other: &Self) -> Result<()> {
+                match (self, other) {
+                $(
+                    (ArrayVectorImpl::$data_type(v), ArrayVectorImpl::$data_type(o)) => {
+                        v.extend_from(o);
+                        Ok(())
+                    }
+                )*
+                    (v, o) => Err(Error::InvalidConvert {
+                        from: o.data_type().to_string(),
+                        to: v.data_type().to_string(),
+                    }),
+                }
+            }
         }
     };
 }
@@ -134,6 +157,22 @@ impl<S> ArrayVector<S> {
 }
 
 impl<S: Clone> ArrayVector<S> {
+    /// Rows at `indices`, which must be in bounds.
+    pub(crate) fn gather(&self, indices: &[usize]) -> Self {
+        let mut res = Self::new();
+        for &i in indices {
+            res.data.extend_from_slice(&self[i]);
+            res.index.push(res.data.len());
+        }
+        res
+    }
+
+    pub(crate) fn extend_from(&mut self, other: &Self) {
+        let offset = self.data.len();
+        self.data.extend_from_slice(&other.data);
+        self.index.extend(other.index.iter().map(|i| i + offset));
+    }
+
     pub(crate) fn resize(&mut self, new_len: usize) {
         let mut index = 0;
         if !self.is_empty() {
@@ -0,0 +1,494 @@
+//! Conversions between DolphinDB types following the server's `cast`.
+//!
+//! Nulls stay null, overflows and unsupported conversions are errors while unparsable strings
+//! become null as on the server.
+
+use std::{fmt::Display, str::FromStr};
+
+use num_traits::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+use super::{
+    Blob, Bool, Char, Constant, ConstantImpl, DataType, Date, DateHour, DateTime, Decimal128,
+    Decimal32, Decimal64, DolphinString, Double, Float, Int, Long, Minute, Month, NanoTime,
+    NanoTimestamp, ScalarImpl, Second, Short, Symbol, Time, Timestamp, VectorImpl,
+};
+use crate::error::{Error, Result};
+
+const NANOS_PER_DAY: i128 = 86_400_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Category {
+    Void,
+    Bool,
+    Integer,
+    Float,
+    Decimal,
+    // Date and Month, which carry no time of day.
+    Calendar,
+    Instant,
+    TimeOfDay,
+    Text,
+    Binary,
+}
+
+pub(crate) fn category(data_type: DataType) -> Option<Category> {
+    use DataType::*;
+
+    Some(match data_type {
+        Void => Category::Void,
+        Bool => Category::Bool,
+        Char | Short | Int | Long => Category::Integer,
+        Float | Double => Category::Float,
+        Decimal32 | Decimal64 | Decimal128 => Category::Decimal,
+        Date | Month => Category::Calendar,
+        DateHour | DateTime | Timestamp | NanoTimestamp => Category::Instant,
+        Time | Minute | Second | NanoTime => Category::TimeOfDay,
+        Symbol | String => Category::Text,
+        Blob => Category::Binary,
+        _ => return None,
+    })
+}
+
+fn check(from: DataType, to: DataType) -> Result<()> {
+    use Category::*;
+
+    let convertible = match (category(from), category(to)) {
+        (Some(from), Some(to)) => match (from, to) {
+            (_, Void) => false,
+            (Void, _) => true,
+            (from, to) if from == to => true,
+            (_, Text) | (Text, _) => true,
+            (Binary, _) | (_, Binary) => false,
+            (Bool | Integer | Float | Decimal, Bool | Integer | Float | Decimal) => true,
+            (Integer, Calendar | Instant | TimeOfDay) => true,
+            (Calendar | Instant | TimeOfDay, Integer) => true,
+            (Calendar | Instant, Calendar | Instant) => true,
+            (Instant, TimeOfDay) => true,
+            _ => false,
+        },
+        _ => false,
+    };
+
+    if convertible {
+        Ok(())
+    } else {
+        Err(invalid(from, to))
+    }
+}
+
+fn check_scale(data_type: DataType, scale: u32) -> Result<()> {
+    let max = match data_type {
+        DataType::Decimal32 => 9,
+        DataType::Decimal64 => 18,
+        DataType::Decimal128 => 28,
+        _ => {
+            return Err(Error::ConstraintsViolated(format!(
+                "{data_type} is not a decimal type"
+            )))
+        }
+    };
+
+    if scale > max {
+        return Err(Error::ConstraintsViolated(format!(
+            "scale {scale} exceeds {max} of {data_type}"
+        )));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn invalid(from: DataType, to: DataType) -> Error {
+    Error::InvalidConvert {
+        from: from.to_string(),
+        to: to.to_string(),
+    }
+}
+
+pub(crate) fn overflow(value: impl Display, to: DataType) -> Error {
+    Error::InvalidNumeric(format!("{value} overflows {to}"))
+}
+
+/// Nanoseconds per unit of a temporal type, Month has no fixed unit.
+fn unit(data_type: DataType) -> i128 {
+    match data_type {
+        DataType::Date => NANOS_PER_DAY,
+        DataType::DateHour => 3_600_000_000_000,
+        DataType::Minute => 60_000_000_000,
+        DataType::DateTime | DataType::Second => 1_000_000_000,
+        DataType::Timestamp | DataType::Time => 1_000_000,
+        _ => 1,
+    }
+}
+
+pub(crate) fn null(data_type: DataType) -> ScalarImpl {
+    // checked types are all scalar types
+    ScalarImpl::from_type(data_type).unwrap()
+}
+
+/// Mantissa of `decimal` at `scale`, rounding half away from zero.
+pub(crate) fn rescale(decimal: Decimal, scale: u32) -> Option<i128> {
+    let mantissa = decimal.mantissa();
+    let current = decimal.scale();
+
+    if scale >= current {
+        mantissa.checked_mul(10i128.checked_pow(scale - current)?)
+    } else {
+        let divisor = 10i128.checked_pow(current - scale)?;
+        let (quotient, remainder) = (mantissa / divisor, mantissa % divisor);
+        if remainder.abs() * 2 >= divisor {
+            Some(quotient + mantissa.signum())
+        } else {
+            Some(quotient)
+        }
+    }
+}
+
+pub(crate) fn to_decimal(decimal: Decimal, to: DataType, scale: Option<u32>) -> Result<ScalarImpl> {
+    let scale = scale.unwrap_or(decimal.scale());
+    check_scale(to, scale).map_err(|_| overflow(decimal, to))?;
+    let mantissa = rescale(decimal, scale).ok_or_else(|| overflow(decimal, to))?;
+
+    let result = match to {
+        DataType::Decimal32 => i32::try_from(mantissa)
+            .ok()
+            .filter(|m| *m != i32::MIN)
+            .and_then(|m| Decimal32::from_raw(m, scale))
+            .map(ScalarImpl::Decimal32),
+        DataType::Decimal64 => i64::try_from(mantissa)
+            .ok()
+            .filter(|m| *m != i64::MIN)
+            .and_then(|m| Decimal64::from_raw(m, scale))
+            .map(ScalarImpl::Decimal64),
+        DataType::Decimal128 => Decimal128::from_raw(mantissa, scale).map(ScalarImpl::Decimal128),
+        _ => unreachable!(),
+    };
+
+    result.ok_or_else(|| overflow(decimal, to))
+}
+
+macro_rules! checked_integer {
+    ($value:expr, $raw_type:ty, $struct_name:ident, $to:expr) => {
+        <$raw_type>::try_from($value)
+            .ok()
+            .filter(|v| *v != <$raw_type>::MIN)
+            .map(|v| ScalarImpl::$struct_name($struct_name::new(v)))
+            .ok_or_else(|| overflow($value, $to))
+    };
+}
+
+pub(crate) fn from_integer(value: i128, to: DataType, scale: Option<u32>) -> Result<ScalarImpl> {
+    match to {
+        DataType::Bool => Ok(ScalarImpl::Bool(Bool::new(value != 0))),
+        DataType::Char => checked_integer!(value, i8, Char, to),
+        DataType::Short => checked_integer!(value, i16, Short, to),
+        DataType::Int => checked_integer!(value, i32, Int, to),
+        DataType::Long => checked_integer!(value, i64, Long, to),
+        DataType::Float => Ok(ScalarImpl::Float(Float::new(value as f32))),
+        DataType::Double => Ok(ScalarImpl::Double(Double::new(value as f64))),
+        DataType::Decimal32 | DataType::Decimal64 | DataType::Decimal128 => {
+            let decimal =
+                Decimal::try_from_i128_with_scale(value, 0).map_err(|_| overflow(value, to))?;
+            to_decimal(decimal, to, scale)
+        }
+        _ => from_temporal_raw(value, to),
+    }
+}
+
+pub(crate) fn from_float(value: f64, to: DataType, scale: Option<u32>) -> Result<ScalarImpl> {
+    match to {
+        DataType::Bool => Ok(ScalarImpl::Bool(Bool::new(value != 0.0))),
+        DataType::Float => {
+            let float = value as f32;
+            if value.is_finite() && !float.is_finite() {
+                return Err(overflow(value, to));
+            }
+            Ok(ScalarImpl::Float(Float::new(float)))
+        }
+        DataType::Double => Ok(ScalarImpl::Double(Double::new(value))),
+        DataType::Decimal32 | DataType::Decimal64 | DataType::Decimal128 => {
+            let decimal = Decimal::from_f64(value).ok_or_else(|| overflow(value, to))?;
+            to_decimal(decimal, to, scale)
+        }
+        _ => {
+            // rounds half away from zero like the server
+            let integer = value.round().to_i128().ok_or_else(|| overflow(value, to))?;
+            from_integer(integer, to, scale)
+        }
+    }
+}
+
+fn from_decimal(value: Decimal, to: DataType, scale: Option<u32>) -> Result<ScalarImpl> {
+    match to {
+        DataType::Bool => Ok(ScalarImpl::Bool(Bool::new(!value.is_zero()))),
+        DataType::Float | DataType::Double => {
+            from_float(value.to_f64().ok_or_else(|| overflow(value, to))?, to, None)
+        }
+        DataType::Decimal32 | DataType::Decimal64 | DataType::Decimal128 => {
+            to_decimal(value, to, scale)
+        }
+        _ => from_integer(
+            rescale(value, 0).ok_or_else(|| overflow(value, to))?,
+            to,
+            scale,
+        ),
+    }
+}
+
+macro_rules! checked_temporal {
+    ($raw:expr, $wire_type:ty, $struct_name:ident, $to:expr) => {
+        <$wire_type>::try_from($raw)
+            .ok()
+            .filter(|raw| *raw != <$wire_type>::MIN && $struct_name::decode(*raw).is_some())
+            .map(|raw| ScalarImpl::$struct_name($struct_name(raw)))
+            .ok_or_else(|| overflow($raw, $to))
+    };
+}
+
+/// Temporal scalar from the wire value, which counts units since the epoch or midnight.
+pub(crate) fn from_temporal_raw(raw: i128, to: DataType) -> Result<ScalarImpl> {
+    match to {
+        DataType::Date => checked_temporal!(raw, i32, Date, to),
+        DataType::Month => checked_temporal!(raw, i32, Month, to),
+        DataType::Time => checked_temporal!(raw, i32, Time, to),
+        DataType::Minute => checked_temporal!(raw, i32, Minute, to),
+        DataType::Second => checked_temporal!(raw, i32, Second, to),
+        DataType::DateTime => checked_temporal!(raw, i32, DateTime, to),
+        DataType::Timestamp => checked_temporal!(raw, i64, Timestamp, to),
+        DataType::NanoTime => checked_temporal!(raw, i64, NanoTime, to),
+        DataType::NanoTimestamp => checked_temporal!(raw, i64, NanoTimestamp, to),
+        DataType::DateHour => checked_temporal!(raw, i32, DateHour, to),
+        _ => unreachable!(),
+    }
+}
+
+pub(crate) fn temporal_raw(scalar: &ScalarImpl) -> Option<i128> {
+    Some(match scalar {
+        ScalarImpl::Date(v) => v.0 as i128,
+        ScalarImpl::Month(v) => v.0 as i128,
+        ScalarImpl::Time(v) => v.0 as i128,
+        ScalarImpl::Minute(v) => v.0 as i128,
+        ScalarImpl::Second(v) => v.0 as i128,
+        ScalarImpl::DateTime(v) => v.0 as i128,
+        ScalarImpl::Timestamp(v) => v.0 as i128,
+        ScalarImpl::NanoTime(v) => v.0 as i128,
+        ScalarImpl::NanoTimestamp(v) => v.0 as i128,
+        ScalarImpl::DateHour(v) => v.0 as i128,
+        _ => return None,
+    })
+}
+
+/// Truncates towards the past, a timestamp keeps its time of day only when cast to a time.
+fn from_temporal(raw: i128, from: DataType, to: DataType) -> Result<ScalarImpl> {
+    let nanos = match from {
+        DataType::Month => {
+            // checked on construction
+            let date = Month::decode(raw as i32).ok_or_else(|| overflow(raw, from))?;
+            Date::encode(date) as i128 * NANOS_PER_DAY
+        }
+        _ => raw * unit(from),
+    };
+
+    let raw = match (to, category(to)) {
+        (DataType::Month, _) => {
+            let days =
+                i32::try_from(nanos.div_euclid(NANOS_PER_DAY)).map_err(|_| overflow(nanos, to))?;
+            let date = Date::decode(days).ok_or_else(|| overflow(days, to))?;
+            Month::encode(date) as i128
+        }
+        (_, Some(Category::TimeOfDay)) => nanos.rem_euclid(NANOS_PER_DAY) / unit(to),
+        _ => nanos.div_euclid(unit(to)),
+    };
+
+    from_temporal_raw(raw, to)
+}
+
+fn format_temporal(scalar: &ScalarImpl) -> Option<String> {
+    match scalar {
+        ScalarImpl::Date(v) => v.format_with(Date::DDB_FORMAT),
+        ScalarImpl::Month(v) => v.format_with(Month::DDB_FORMAT),
+        ScalarImpl::Time(v) => v.format_with(Time::DDB_FORMAT),
+        ScalarImpl::Minute(v) => v.format_with(Minute::DDB_FORMAT),
+        ScalarImpl::Second(v) => v.format_with(Second::DDB_FORMAT),
+        ScalarImpl::DateTime(v) => v.format_with(DateTime::DDB_FORMAT),
+        ScalarImpl::Timestamp(v) => v.format_with(Timestamp::DDB_FORMAT),
+        ScalarImpl::NanoTime(v) => v.format_with(NanoTime::DDB_FORMAT),
+        ScalarImpl::NanoTimestamp(v) => v.format_with(NanoTimestamp::DDB_FORMAT),
+        ScalarImpl::DateHour(v) => v.format_with(DateHour::DDB_FORMAT),
+        _ => None,
+    }
+}
+
+fn parse_temporal(s: &str, to: DataType) -> Option<ScalarImpl> {
+    Some(match to {
+        DataType::Date => ScalarImpl::Date(Date::parse_with(s, Date::DDB_FORMAT)?),
+        DataType::Month => ScalarImpl::Month(Month::parse_with(s, Month::DDB_FORMAT)?),
+        DataType::Time => ScalarImpl::Time(Time::parse_with(s, Time::DDB_FORMAT)?),
+        DataType::Minute => ScalarImpl::Minute(Minute::parse_with(s, Minute::DDB_FORMAT)?),
+        DataType::Second => ScalarImpl::Second(Second::parse_with(s, Second::DDB_FORMAT)?),
+        DataType::DateTime => ScalarImpl::DateTime(DateTime::parse_with(s, DateTime::DDB_FORMAT)?),
+        DataType::Timestamp => {
+            ScalarImpl::Timestamp(Timestamp::parse_with(s, Timestamp::DDB_FORMAT)?)
+        }
+        DataType::NanoTime => ScalarImpl::NanoTime(NanoTime::parse_with(s, NanoTime::DDB_FORMAT)?),
+        DataType::NanoTimestamp => {
+            ScalarImpl::NanoTimestamp(NanoTimestamp::parse_with(s, NanoTimestamp::DDB_FORMAT)?)
+        }
+        DataType::DateHour => ScalarImpl::DateHour(DateHour::parse_with(s, DateHour::DDB_FORMAT)?),
+        _ => return None,
+    })
+}
+
+fn to_text(s: String, to: DataType) -> Result<ScalarImpl> {
+    match to {
+        DataType::Symbol => Ok(ScalarImpl::Symbol(Symbol::new(s))),
+        DataType::String => Ok(ScalarImpl::String(DolphinString::new(s))),
+        DataType::Blob => Ok(ScalarImpl::Blob(Blob::new(s.into_bytes()))),
+        _ => unreachable!(),
+    }
+}
+
+fn from_text(s: &str, to: DataType, scale: Option<u32>) -> Result<ScalarImpl> {
+    if let Some(Category::Text | Category::Binary) = category(to) {
+        return to_text(s.to_string(), to);
+    }
+
+    let s = s.trim();
+
+    match category(to) {
+        Some(Category::Bool) => match s.to_ascii_lowercase().as_str() {
+            "true" => from_integer(1, to, None),
+            "false" => from_integer(0, to, None),
+            _ => match f64::from_str(s) {
+                Ok(v) => from_float(v, to, None),
+                Err(_) => Ok(null(to)),
+            },
+        },
+        Some(Category::Integer) => match i128::from_str(s) {
+            Ok(v) => from_integer(v, to, None),
+            Err(_) => Ok(null(to)),
+        },
+        Some(Category::Float) => match f64::from_str(s) {
+            Ok(v) => from_float(v, to, None),
+            Err(_) => Ok(null(to)),
+        },
+        Some(Category::Decimal) => {
+            match Decimal::from_str(s).or_else(|_| Decimal::from_scientific(s)) {
+                Ok(v) => to_decimal(v, to, scale),
+                Err(_) => Ok(null(to)),
+            }
+        }
+        _ => Ok(parse_temporal(s, to).unwrap_or_else(|| null(to))),
+    }
+}
+
+fn cast_scalar(scalar: &ScalarImpl, to: DataType, scale: Option<u32>) -> Result<ScalarImpl> {
+    let from = scalar.data_type();
+    check(from, to)?;
+
+    if from == to && scale.is_none() {
+        return Ok(scalar.clone());
+    }
+
+    if scalar.is_null() {
+        return Ok(null(to));
+    }
+
+    let text = category(to) == Some(Category::Text);
+
+    match scalar {
+        ScalarImpl::Symbol(v) => from_text(v.as_ref().as_deref().unwrap_or_default(), to, scale),
+        ScalarImpl::String(v) => from_text(v.as_ref().as_deref().unwrap_or_default(), to, scale),
+        ScalarImpl::Blob(v) => {
+            let bytes = v.as_ref().clone().unwrap_or_default();
+            match to {
+                DataType::Blob => Ok(scalar.clone()),
+                _ => to_text(String::from_utf8(bytes)?, to),
+            }
+        }
+        _ if text => to_text(
+            format_temporal(scalar).unwrap_or_else(|| scalar.to_string()),
+            to,
+        ),
+        ScalarImpl::Bool(v) => from_integer(v.into_inner().unwrap() as i128, to, scale),
+        ScalarImpl::Char(v) => from_integer(v.into_inner().unwrap() as i128, to, scale),
+        ScalarImpl::Short(v) => from_integer(v.into_inner().unwrap() as i128, to, scale),
+        ScalarImpl::Int(v) => from_integer(v.into_inner().unwrap() as i128, to, scale),
+        ScalarImpl::Long(v) => from_integer(v.into_inner().unwrap() as i128, to, scale),
+        ScalarImpl::Float(v) => from_float(v.into_inner().unwrap() as f64, to, scale),
+        ScalarImpl::Double(v) => from_float(v.into_inner().unwrap(), to, scale),
+        ScalarImpl::Decimal32(v) => from_decimal(v.into_inner().unwrap(), to, scale),
+        ScalarImpl::Decimal64(v) => from_decimal(v.into_inner().unwrap(), to, scale),
+        ScalarImpl::Decimal128(v) => from_decimal(v.into_inner().unwrap(), to, scale),
+        _ => {
+            // temporal types are all that remain
+            let raw = temporal_raw(scalar).ok_or_else(|| invalid(from, to))?;
+            match category(to) {
+                Some(Category::Integer) => from_integer(raw, to, None),
+                _ => from_temporal(raw, from, to),
+            }
+        }
+    }
+}
+
+impl ScalarImpl {
+    /// Converts to `data_type` following DolphinDB's `cast`.
+    ///
+    /// Floats round to integers, temporals truncate to coarser units, strings are parsed in
+    /// DolphinDB's display format and integers convert to temporals by their internal
+    /// representation. Decimals keep the scale of the source, which is 0 for integers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the types are not convertible or the value overflows `data_type`.
+    pub fn cast(&self, data_type: DataType) -> Result<ScalarImpl> {
+        cast_scalar(self, data_type, None)
+    }
+
+    /// Converts to the decimal `data_type` with `scale`, rounding half away from zero.
+    pub fn cast_decimal(&self, data_type: DataType, scale: u32) -> Result<ScalarImpl> {
+        check_scale(data_type, scale)?;
+        cast_scalar(self, data_type, Some(scale))
+    }
+}
+
+impl VectorImpl {
+    /// Converts every element to `data_type` as [`ScalarImpl::cast`].
+    ///
+    /// Elements of `ANY` vectors must be scalars, array vectors are not supported.
+    pub fn cast(&self, data_type: DataType) -> Result<VectorImpl> {
+        self.cast_with(data_type, None)
+    }
+
+    /// Converts every element to the decimal `data_type` with `scale`.
+    pub fn cast_decimal(&self, data_type: DataType, scale: u32) -> Result<VectorImpl> {
+        check_scale(data_type, scale)?;
+        self.cast_with(data_type, Some(scale))
+    }
+
+    fn cast_with(&self, to: DataType, scale: Option<u32>) -> Result<VectorImpl> {
+        let from = self.data_type();
+
+        if from == to && scale.is_none() {
+            return Ok(self.clone());
+        }
+
+        match self {
+            VectorImpl::ArrayVector(_) => return Err(invalid(from, to)),
+            VectorImpl::Any(_) => {}
+            _ if to == DataType::Any => return Ok(self.clone().break_up().into()),
+            _ => check(from, to)?,
+        }
+
+        let mut res = VectorImpl::from_type(to).ok_or_else(|| invalid(from, to))?;
+        for i in 0..self.len() {
+            match self.get(i) {
+                Some(ConstantImpl::Scalar(s)) => res.push_scalar(cast_scalar(&s, to, scale)?),
+                Some(c) => return Err(invalid(c.data_type(), to)),
+                None => return Err(invalid(from, to)),
+            }
+        }
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,607 @@
+//! Null-aware aggregations and element-wise arithmetic on [`VectorImpl`].
+//!
+//! Aggregations skip nulls and arithmetic propagates them, as on the server.
+
+use std::fmt::{self, Display};
+
+use num_traits::ToPrimitive;
+use rust_decimal::Decimal;
+
+use super::{
+    cast::{
+        category, from_float, from_integer, from_temporal_raw, null, overflow, temporal_raw,
+        to_decimal, Category,
+    },
+    Bool, Char, DataType, Date, DateHour, DateTime, Double, Float, Int, Long, Minute, Month,
+    NanoTime, NanoTimestamp, RawScalar, ScalarImpl, Second, Short, Time, Timestamp, VectorImpl,
+};
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Add => write!(f, "+"),
+            Op::Sub => write!(f, "-"),
+            Op::Mul => write!(f, "*"),
+            Op::Div => write!(f, "/"),
+        }
+    }
+}
+
+fn unsupported(op: impl Display, data_type: DataType) -> Error {
+    Error::ConstraintsViolated(format!("{op} is not supported on {data_type}"))
+}
+
+fn is_temporal(category: Option<Category>) -> bool {
+    matches!(
+        category,
+        Some(Category::Calendar | Category::Instant | Category::TimeOfDay)
+    )
+}
+
+fn is_integer(category: Option<Category>) -> bool {
+    matches!(category, Some(Category::Bool | Category::Integer))
+}
+
+fn is_numeric(category: Option<Category>) -> bool {
+    matches!(
+        category,
+        Some(Category::Bool | Category::Integer | Category::Float | Category::Decimal)
+    )
+}
+
+fn decimal_width(data_type: DataType) -> u8 {
+    match data_type {
+        DataType::Decimal32 => 1,
+        DataType::Decimal64 => 2,
+        DataType::Decimal128 => 3,
+        _ => 0,
+    }
+}
+
+fn result_type(op: Op, lhs: DataType, rhs: DataType) -> Result<DataType> {
+    let (l, r) = (category(lhs), category(rhs));
+    let unsupported = || Error::ConstraintsViolated(format!("{lhs} {op} {rhs} is not supported"));
+
+    if is_integer(l) && is_integer(r) {
+        if lhs == DataType::Long || rhs == DataType::Long {
+            Ok(DataType::Long)
+        } else {
+            Ok(DataType::Int)
+        }
+    } else if l == Some(Category::Decimal) || r == Some(Category::Decimal) {
+        match (l, r) {
+            (Some(Category::Float), _) | (_, Some(Category::Float)) => Ok(DataType::Double),
+            _ if is_numeric(l) && is_numeric(r) => {
+                if decimal_width(lhs) >= decimal_width(rhs) {
+                    Ok(lhs)
+                } else {
+                    Ok(rhs)
+                }
+            }
+            _ => Err(unsupported()),
+        }
+    } else if is_numeric(l) && is_numeric(r) {
+        if lhs == DataType::Float && rhs == DataType::Float {
+            Ok(DataType::Float)
+        } else {
+            Ok(DataType::Double)
+        }
+    } else if is_temporal(l) && is_integer(r) && matches!(op, Op::Add | Op::Sub) {
+        Ok(lhs)
+    } else if is_integer(l) && is_temporal(r) && op == Op::Add {
+        Ok(rhs)
+    } else if is_temporal(l) && lhs == rhs && op == Op::Sub {
+        match lhs {
+            DataType::Timestamp | DataType::NanoTime | DataType::NanoTimestamp => {
+                Ok(DataType::Long)
+            }
+            _ => Ok(DataType::Int),
+        }
+    } else {
+        Err(unsupported())
+    }
+}
+
+/// Non-null numeric or temporal value, temporals counting their units.
+#[derive(Debug, Clone, Copy)]
+enum Number {
+    Integer(i128),
+    Float(f64),
+    Decimal(Decimal),
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Integer(x) => write!(f, "{x}"),
+            Number::Float(x) => write!(f, "{x}"),
+            Number::Decimal(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+impl Number {
+    fn integer(self) -> Option<i128> {
+        match self {
+            Number::Integer(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    fn float(self) -> Option<f64> {
+        match self {
+            Number::Integer(x) => Some(x as f64),
+            Number::Float(x) => Some(x),
+            Number::Decimal(x) => x.to_f64(),
+        }
+    }
+
+    fn decimal(self) -> Option<Decimal> {
+        match self {
+            Number::Integer(x) => Decimal::try_from_i128_with_scale(x, 0).ok(),
+            Number::Float(_) => None,
+            Number::Decimal(x) => Some(x),
+        }
+    }
+}
+
+fn number(scalar: &ScalarImpl) -> Option<Number> {
+    Some(match scalar {
+        ScalarImpl::Bool(v) => Number::Integer(v.into_inner()? as i128),
+        ScalarImpl::Char(v) => Number::Integer(v.into_inner()? as i128),
+        ScalarImpl::Short(v) => Number::Integer(v.into_inner()? as i128),
+        ScalarImpl::Int(v) => Number::Integer(v.into_inner()? as i128),
+        ScalarImpl::Long(v) => Number::Integer(v.into_inner()? as i128),
+        ScalarImpl::Float(v) => Number::Float(v.into_inner()? as f64),
+        ScalarImpl::Double(v) => Number::Float(v.into_inner()?),
+        ScalarImpl::Decimal32(v) => Number::Decimal(v.into_inner()?),
+        ScalarImpl::Decimal64(v) => Number::Decimal(v.into_inner()?),
+        ScalarImpl::Decimal128(v) => Number::Decimal(v.into_inner()?),
+        _ if scalar.is_null() => return None,
+        _ => Number::Integer(temporal_raw(scalar)?),
+    })
+}
+
+// Reads the element from the raw buffer rather than building a scalar, None if null.
+fn number_at(vector: &VectorImpl, index: usize) -> Option<Number> {
+    macro_rules! raw {
+        ($v:expr, $struct_name:ident) => {{
+            let raw = $v.as_slice()[index];
+            if raw == <$struct_name as RawScalar>::NULL {
+                return None;
+            }
+            raw
+        }};
+    }
+
+    Some(match vector {
+        VectorImpl::Bool(v) => Number::Integer((raw!(v, Bool) != 0) as i128),
+        VectorImpl::Char(v) => Number::Integer(raw!(v, Char) as i128),
+        VectorImpl::Short(v) => Number::Integer(raw!(v, Short) as i128),
+        VectorImpl::Int(v) => Number::Integer(raw!(v, Int) as i128),
+        VectorImpl::Long(v) => Number::Integer(raw!(v, Long) as i128),
+        VectorImpl::Float(v) => Number::Float(raw!(v, Float) as f64),
+        VectorImpl::Double(v) => Number::Float(raw!(v, Double)),
+        VectorImpl::Decimal32(v) => Number::Decimal(v[index].0?),
+        VectorImpl::Decimal64(v) => Number::Decimal(v[index].0?),
+        VectorImpl::Decimal128(v) => Number::Decimal(v[index].0?),
+        VectorImpl::Date(v) => Number::Integer(raw!(v, Date) as i128),
+        VectorImpl::Month(v) => Number::Integer(raw!(v, Month) as i128),
+        VectorImpl::Time(v) => Number::Integer(raw!(v, Time) as i128),
+        VectorImpl::Minute(v) => Number::Integer(raw!(v, Minute) as i128),
+        VectorImpl::Second(v) => Number::Integer(raw!(v, Second) as i128),
+        VectorImpl::DateTime(v) => Number::Integer(raw!(v, DateTime) as i128),
+        VectorImpl::Timestamp(v) => Number::Integer(raw!(v, Timestamp) as i128),
+        VectorImpl::NanoTime(v) => Number::Integer(raw!(v, NanoTime) as i128),
+        VectorImpl::NanoTimestamp(v) => Number::Integer(raw!(v, NanoTimestamp) as i128),
+        VectorImpl::DateHour(v) => Number::Integer(raw!(v, DateHour) as i128),
+        _ => return None,
+    })
+}
+
+// Evaluates `$body` with `$v` bound to the typed vector of a vector of scalars, `$other` for
+// `ANY` and array vectors.
+macro_rules! with_scalars {
+    ($vector:expr, |$v:ident| $body:expr, $other:expr) => {
+        match $vector {
+            VectorImpl::Void($v) => $body,
+            VectorImpl::Bool($v) => $body,
+            VectorImpl::Char($v) => $body,
+            VectorImpl::Short($v) => $body,
+            VectorImpl::Int($v) => $body,
+            VectorImpl::Long($v) => $body,
+            VectorImpl::Float($v) => $body,
+            VectorImpl::Double($v) => $body,
+            VectorImpl::Symbol($v) => $body,
+            VectorImpl::String($v) => $body,
+            VectorImpl::Blob($v) => $body,
+            VectorImpl::Date($v) => $body,
+            VectorImpl::Month($v) => $body,
+            VectorImpl::Time($v) => $body,
+            VectorImpl::Minute($v) => $body,
+            VectorImpl::Second($v) => $body,
+            VectorImpl::DateTime($v) => $body,
+            VectorImpl::Timestamp($v) => $body,
+            VectorImpl::NanoTime($v) => $body,
+            VectorImpl::NanoTimestamp($v) => $body,
+            VectorImpl::DateHour($v) => $body,
+            VectorImpl::Decimal32($v) => $body,
+            VectorImpl::Decimal64($v) => $body,
+            VectorImpl::Decimal128($v) => $body,
+            VectorImpl::Any(_) | VectorImpl::ArrayVector(_) => $other,
+        }
+    };
+}
+
+// Products add the scales of their operands, which a narrow decimal type may not hold.
+fn product_type(to: DataType, scale: u32) -> DataType {
+    match to {
+        DataType::Decimal32 if scale > 9 => product_type(DataType::Decimal64, scale),
+        DataType::Decimal64 if scale > 18 => DataType::Decimal128,
+        _ => to,
+    }
+}
+
+fn operands<T>(
+    op: Op,
+    x: Number,
+    y: Number,
+    to: DataType,
+    f: fn(Number) -> Option<T>,
+) -> Result<(T, T)> {
+    f(x).zip(f(y)).ok_or_else(|| Error::InvalidConvert {
+        from: format!("{x} {op} {y}"),
+        to: to.to_string(),
+    })
+}
+
+// Integer division rounds towards negative infinity as on the server.
+fn floor_div(lhs: i128, rhs: i128) -> i128 {
+    let quotient = lhs / rhs;
+    if lhs % rhs != 0 && (lhs < 0) != (rhs < 0) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+fn apply(
+    op: Op,
+    lhs: Option<Number>,
+    rhs: Option<Number>,
+    temporal: bool,
+    to: DataType,
+) -> Result<ScalarImpl> {
+    let (Some(x), Some(y)) = (lhs, rhs) else {
+        return Ok(null(to));
+    };
+
+    if temporal {
+        let (x, y) = operands(op, x, y, to, Number::integer)?;
+        let value = match op {
+            Op::Add => x + y,
+            _ => x - y,
+        };
+        return match category(to) {
+            Some(Category::Integer) => from_integer(value, to, None),
+            _ => from_temporal_raw(value, to),
+        };
+    }
+
+    match category(to) {
+        Some(Category::Integer) => {
+            let (x, y) = operands(op, x, y, to, Number::integer)?;
+            let value = match op {
+                Op::Add => x + y,
+                Op::Sub => x - y,
+                Op::Mul => x * y,
+                Op::Div if y == 0 => return Ok(null(to)),
+                Op::Div => floor_div(x, y),
+            };
+            from_integer(value, to, None)
+        }
+        Some(Category::Float) => {
+            let (x, y) = operands(op, x, y, to, Number::float)?;
+            let value = match op {
+                Op::Add => x + y,
+                Op::Sub => x - y,
+                Op::Mul => x * y,
+                Op::Div if y == 0.0 => return Ok(null(to)),
+                Op::Div => x / y,
+            };
+            from_float(value, to, None)
+        }
+        Some(Category::Decimal) => {
+            let (x, y) = operands(op, x, y, to, Number::decimal)?;
+            let (value, scale) = match op {
+                Op::Add => (x.checked_add(y), x.scale().max(y.scale())),
+                Op::Sub => (x.checked_sub(y), x.scale().max(y.scale())),
+                Op::Mul => (x.checked_mul(y), x.scale() + y.scale()),
+                Op::Div if y.is_zero() => return Ok(null(to)),
+                // the quotient keeps the scale of the dividend
+                Op::Div => (x.checked_div(y), x.scale()),
+            };
+            let value = value.ok_or_else(|| overflow(format!("{x} {op} {y}"), to))?;
+            to_decimal(value, to, Some(scale))
+        }
+        _ => Err(unsupported(op, to)),
+    }
+}
+
+/// Right hand side of element-wise arithmetic.
+enum Operand<'a> {
+    Vector(&'a VectorImpl),
+    Scalar(&'a ScalarImpl),
+}
+
+impl Operand<'_> {
+    fn scale(&self) -> u32 {
+        match self {
+            Operand::Vector(VectorImpl::Decimal32(v)) => v.max_scale(),
+            Operand::Vector(VectorImpl::Decimal64(v)) => v.max_scale(),
+            Operand::Vector(VectorImpl::Decimal128(v)) => v.max_scale(),
+            Operand::Vector(_) => 0,
+            Operand::Scalar(s) => number(s).and_then(Number::decimal).map_or(0, |d| d.scale()),
+        }
+    }
+}
+
+impl VectorImpl {
+    fn scalar_type(&self, op: &str) -> Result<DataType> {
+        let data_type = self.data_type();
+        match self {
+            VectorImpl::Any(_) | VectorImpl::ArrayVector(_) => Err(unsupported(op, data_type)),
+            _ => Ok(data_type),
+        }
+    }
+
+    /// Iterates over non-null numeric and temporal elements.
+    fn numbers(&self) -> impl Iterator<Item = Number> + '_ {
+        (0..self.len()).filter_map(|i| number_at(self, i))
+    }
+
+    /// Returns the number of non-null elements.
+    pub fn count(&self) -> usize {
+        with_scalars!(
+            self,
+            |v| v.iter().filter(|x| !x.is_null()).count(),
+            match self {
+                VectorImpl::Any(v) => v.iter().filter(|x| !x.is_null()).count(),
+                _ => self.len(),
+            }
+        )
+    }
+
+    /// Sum of non-null elements, `LONG` for integers, `DOUBLE` for floats and the same type for
+    /// decimals. Null if all elements are null.
+    pub fn sum(&self) -> Result<ScalarImpl> {
+        let data_type = self.scalar_type("sum")?;
+
+        match category(data_type) {
+            c if is_integer(c) => {
+                let (sum, count) = self
+                    .numbers()
+                    .filter_map(Number::integer)
+                    .fold((0, 0), |(sum, count), x| (sum + x, count + 1));
+                if count == 0 {
+                    Ok(null(DataType::Long))
+                } else {
+                    from_integer(sum, DataType::Long, None)
+                }
+            }
+            Some(Category::Float) => {
+                let (sum, count) = self
+                    .numbers()
+                    .filter_map(Number::float)
+                    .fold((0.0, 0), |(sum, count), x| (sum + x, count + 1));
+                if count == 0 {
+                    Ok(null(DataType::Double))
+                } else {
+                    from_float(sum, DataType::Double, None)
+                }
+            }
+            Some(Category::Decimal) => {
+                let mut sum: Option<Decimal> = None;
+                for x in self.numbers().filter_map(Number::decimal) {
+                    sum = Some(match sum {
+                        None => x,
+                        Some(sum) => sum
+                            .checked_add(x)
+                            .ok_or_else(|| overflow(format!("{sum} + {x}"), data_type))?,
+                    });
+                }
+                match sum {
+                    None => Ok(null(data_type)),
+                    Some(sum) => to_decimal(sum, data_type, None),
+                }
+            }
+            _ => Err(unsupported("sum", data_type)),
+        }
+    }
+
+    /// Mean of non-null numeric elements as `DOUBLE`.
+    pub fn avg(&self) -> Result<ScalarImpl> {
+        let values = self.floats("avg")?;
+        if values.is_empty() {
+            return Ok(null(DataType::Double));
+        }
+
+        let avg = values.iter().sum::<f64>() / values.len() as f64;
+        from_float(avg, DataType::Double, None)
+    }
+
+    /// Sample standard deviation of non-null numeric elements as `DOUBLE`, null if there are
+    /// less than two of them.
+    pub fn std(&self) -> Result<ScalarImpl> {
+        let values = self.floats("std")?;
+        if values.len() < 2 {
+            return Ok(null(DataType::Double));
+        }
+
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        from_float(variance.sqrt(), DataType::Double, None)
+    }
+
+    fn floats(&self, op: &str) -> Result<Vec<f64>> {
+        let data_type = self.scalar_type(op)?;
+        if !is_numeric(category(data_type)) {
+            return Err(unsupported(op, data_type));
+        }
+
+        Ok(self.numbers().filter_map(Number::float).collect())
+    }
+
+    /// Smallest non-null element, null if all elements are null.
+    pub fn min(&self) -> Result<ScalarImpl> {
+        let data_type = self.scalar_type("min")?;
+        let min = with_scalars!(
+            self,
+            |v| v
+                .iter()
+                .filter(|x| !x.is_null())
+                .reduce(|a, b| if b < a { b } else { a })
+                .cloned()
+                .map(ScalarImpl::from),
+            None
+        );
+        Ok(min.unwrap_or_else(|| null(data_type)))
+    }
+
+    /// Largest non-null element, null if all elements are null.
+    pub fn max(&self) -> Result<ScalarImpl> {
+        let data_type = self.scalar_type("max")?;
+        let max = with_scalars!(
+            self,
+            |v| v
+                .iter()
+                .filter(|x| !x.is_null())
+                .reduce(|a, b| if b > a { b } else { a })
+                .cloned()
+                .map(ScalarImpl::from),
+            None
+        );
+        Ok(max.unwrap_or_else(|| null(data_type)))
+    }
+
+    /// First non-null element, null if all elements are null.
+    pub fn first(&self) -> Result<ScalarImpl> {
+        let data_type = self.scalar_type("first")?;
+        let first = with_scalars!(
+            self,
+            |v| v
+                .iter()
+                .find(|x| !x.is_null())
+                .cloned()
+                .map(ScalarImpl::from),
+            None
+        );
+        Ok(first.unwrap_or_else(|| null(data_type)))
+    }
+
+    /// Last non-null element, null if all elements are null.
+    pub fn last(&self) -> Result<ScalarImpl> {
+        let data_type = self.scalar_type("last")?;
+        let last = with_scalars!(
+            self,
+            |v| v
+                .iter()
+                .rev()
+                .find(|x| !x.is_null())
+                .cloned()
+                .map(ScalarImpl::from),
+            None
+        );
+        Ok(last.unwrap_or_else(|| null(data_type)))
+    }
+
+    /// Element-wise `self + rhs`, where `rhs` has the same length.
+    ///
+    /// Integers add up to `INT` or `LONG`, decimals keep the wider type and temporals move by
+    /// integers in their own unit. Nulls propagate.
+    pub fn add(&self, rhs: &VectorImpl) -> Result<VectorImpl> {
+        self.arithmetic(Op::Add, Operand::Vector(rhs))
+    }
+
+    /// Element-wise `self - rhs`, where `rhs` has the same length.
+    ///
+    /// The difference of two temporal vectors of the same type counts their units.
+    pub fn sub(&self, rhs: &VectorImpl) -> Result<VectorImpl> {
+        self.arithmetic(Op::Sub, Operand::Vector(rhs))
+    }
+
+    /// Element-wise `self * rhs`, where `rhs` has the same length.
+    pub fn mul(&self, rhs: &VectorImpl) -> Result<VectorImpl> {
+        self.arithmetic(Op::Mul, Operand::Vector(rhs))
+    }
+
+    /// Element-wise `self / rhs`, where `rhs` has the same length.
+    ///
+    /// Integer division rounds down and division by zero is null, as on the server.
+    pub fn div(&self, rhs: &VectorImpl) -> Result<VectorImpl> {
+        self.arithmetic(Op::Div, Operand::Vector(rhs))
+    }
+
+    /// Adds `rhs` to every element, see [`VectorImpl::add`].
+    pub fn add_scalar(&self, rhs: &ScalarImpl) -> Result<VectorImpl> {
+        self.arithmetic(Op::Add, Operand::Scalar(rhs))
+    }
+
+    /// Subtracts `rhs` from every element, see [`VectorImpl::sub`].
+    pub fn sub_scalar(&self, rhs: &ScalarImpl) -> Result<VectorImpl> {
+        self.arithmetic(Op::Sub, Operand::Scalar(rhs))
+    }
+
+    /// Multiplies every element by `rhs`, see [`VectorImpl::mul`].
+    pub fn mul_scalar(&self, rhs: &ScalarImpl) -> Result<VectorImpl> {
+        self.arithmetic(Op::Mul, Operand::Scalar(rhs))
+    }
+
+    /// Divides every element by `rhs`, see [`VectorImpl::div`].
+    pub fn div_scalar(&self, rhs: &ScalarImpl) -> Result<VectorImpl> {
+        self.arithmetic(Op::Div, Operand::Scalar(rhs))
+    }
+
+    fn arithmetic(&self, op: Op, rhs: Operand<'_>) -> Result<VectorImpl> {
+        let lhs_type = self.scalar_type(&op.to_string())?;
+        let rhs_type = match rhs {
+            Operand::Vector(v) => {
+                if v.len() != self.len() {
+                    return Err(Error::ConstraintsViolated("mismatch vector size".into()));
+                }
+                v.scalar_type(&op.to_string())?
+            }
+            Operand::Scalar(s) => s.data_type(),
+        };
+        let mut to = result_type(op, lhs_type, rhs_type)?;
+        if op == Op::Mul {
+            to = product_type(to, Operand::Vector(self).scale() + rhs.scale());
+        }
+        let temporal = is_temporal(category(lhs_type)) || is_temporal(category(rhs_type));
+
+        let scalar = match rhs {
+            Operand::Scalar(s) => number(s),
+            Operand::Vector(_) => None,
+        };
+        let mut res = VectorImpl::from_type(to).ok_or_else(|| unsupported(op, to))?;
+        for i in 0..self.len() {
+            let y = match rhs {
+                Operand::Vector(v) => number_at(v, i),
+                Operand::Scalar(_) => scalar,
+            };
+            res.push_scalar(apply(op, number_at(self, i), y, temporal, to)?);
+        }
+
+        Ok(res)
+    }
+}
@@ -34,6 +34,8 @@ pub enum ConstantImpl {
     Dictionary(DictionaryImpl),
     Set(SetImpl),
     Table(Table),
+    FunctionDef(FunctionDef),
+    Opaque(Opaque),
 }
 
 impl Default for ConstantImpl {
@@ -52,6 +54,12 @@ impl ConstantImpl {
 
     fn from_category(data_type: DataType, data_form: DataForm) -> Option<Self> {
         match data_form {
+            DataForm::Scalar if data_type == FunctionDef::DATA_BYTE => {
+                Some(Self::FunctionDef(FunctionDef::default()))
+            }
+            DataForm::Scalar if Opaque::is_opaque(data_type) => {
+                Opaque::from_type(data_type).map(Self::Opaque)
+            }
             DataForm::Scalar => ScalarImpl::from_type(data_type).map(Self::Scalar),
             DataForm::Vector => VectorImpl::from_type(data_type).map(Self::Vector),
             DataForm::Pair => PairImpl::from_type(data_type).map(Self::Pair),
@@ -344,7 +352,9 @@ macro_rules! for_all_constants {
             (Pair, PairImpl),
             (Set, SetImpl),
             (Dictionary, DictionaryImpl),
-            (Table, Table)
+            (Table, Table),
+            (FunctionDef, FunctionDef),
+            (Opaque, Opaque)
         );
     };
 }
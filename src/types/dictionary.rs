@@ -452,7 +452,7 @@ macro_rules! dispatch_data_type {
 macro_rules! dispatch_to_vecs {
     ($(($enum_name:ident, $struct_name:ident)),*) => {
         impl DictionaryImpl {
-            fn to_vecs(&self) -> (VectorImpl, VectorImpl) {
+            pub(crate) fn to_vecs(&self) -> (VectorImpl, VectorImpl) {
                 match self {
                     $(
                         DictionaryImpl::$enum_name(d) => d.to_vecs(),
@@ -466,7 +466,7 @@ macro_rules! dispatch_to_vecs {
 macro_rules! dispatch_from_vecs {
     ($(($enum_name:ident, $struct_name:ident)),*) => {
         impl DictionaryImpl {
-            fn from_vecs(keys: VectorImpl, values: VectorImpl) -> Option<Self> {
+            pub(crate) fn from_vecs(keys: VectorImpl, values: VectorImpl) -> Option<Self> {
                 match keys {
                     $(
                         VectorImpl::$enum_name(keys) => Some(DictionaryImpl::$enum_name(
@@ -28,6 +28,11 @@ pub enum DataType {
     Double = 16,
     Symbol = 17,
     String = 18,
+    FunctionDef = 20,
+    Handle = 21,
+    Code = 22,
+    DataSource = 23,
+    Resource = 24,
     Any = 25,
     AnyDictionary = 27,
     DateHour = 28,
@@ -109,6 +114,11 @@ macro_rules! for_all_types {
             (Double, 16),
             (Symbol, 17),
             (String, 18),
+            (FunctionDef, 20),
+            (Handle, 21),
+            (Code, 22),
+            (DataSource, 23),
+            (Resource, 24),
             (Any, 25),
             (AnyDictionary, 27),
             (DateHour, 28),
@@ -0,0 +1,587 @@
+//! JSON conversion of [`ConstantImpl`]s with [`serde_json`].
+//!
+//! | DolphinDB              | JSON                                                |
+//! |------------------------|-----------------------------------------------------|
+//! | null                   | `null`                                              |
+//! | `BOOL`                 | boolean                                             |
+//! | `CHAR` ~ `DOUBLE`      | number, non-finite floats become `null`             |
+//! | `SYMBOL`, `STRING`     | string                                              |
+//! | `BLOB`                 | string, invalid UTF-8 is replaced                   |
+//! | `DECIMAL32` ~ `128`    | number, or string with exact decimal representation |
+//! | temporal types         | see [`JsonTemporalFormat`]                          |
+//! | vector, pair, set      | array                                               |
+//! | array vector           | array of arrays                                     |
+//! | dictionary             | object keyed by the text of the keys                |
+//! | table                  | see [`JsonTableOrient`]                             |
+//!
+//! Object keys are sorted, the order of table columns is kept by [`JsonType::Table`].
+
+use std::str::FromStr;
+
+use num_traits::ToPrimitive;
+use rust_decimal::Decimal;
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+
+use super::*;
+
+/// Layout of tables.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonTableOrient {
+    /// `{"column": [value, ...], ...}`
+    #[default]
+    Columns,
+    /// `[{"column": value, ...}, ...]`
+    Records,
+}
+
+/// Representation of temporal values.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonTemporalFormat {
+    /// ISO 8601 strings, e.g. `2024-03-15T09:30:15.250`.
+    #[default]
+    Iso8601,
+    /// DolphinDB literals, e.g. `2024.03.15T09:30:15.250`.
+    DolphinDB,
+    /// Elapsed units as numbers, e.g. [`Timestamp::elapsed`].
+    Elapsed,
+}
+
+/// Options of [`ConstantImpl::to_json_with`] and [`ConstantImpl::from_json_with`].
+#[derive(Default, Debug, Clone, Copy)]
+pub struct JsonOptions {
+    table_orient: JsonTableOrient,
+    temporal_format: JsonTemporalFormat,
+    decimal_as_string: bool,
+}
+
+impl JsonOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Layout of tables, only applies to output since both are accepted as input.
+    pub fn with_table_orient(&mut self, table_orient: JsonTableOrient) -> &mut Self {
+        self.table_orient = table_orient;
+        self
+    }
+
+    pub fn with_temporal_format(&mut self, temporal_format: JsonTemporalFormat) -> &mut Self {
+        self.temporal_format = temporal_format;
+        self
+    }
+
+    /// Writes decimals as strings keeping their exact digits and scale, e.g. `"1.50"`.
+    pub fn with_decimal_as_string(&mut self, decimal_as_string: bool) -> &mut Self {
+        self.decimal_as_string = decimal_as_string;
+        self
+    }
+}
+
+/// Expected type of [`ConstantImpl::from_json`].
+///
+/// `ANY` elements are inferred as `BOOL`, `LONG`, `DOUBLE`, `STRING` or nested `ANY` vectors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonType {
+    Scalar(DataType),
+    Vector(DataType),
+    Pair(DataType),
+    Set(DataType),
+    /// Key and value types.
+    Dictionary(DataType, DataType),
+    /// Column names and types.
+    Table(Vec<(String, DataType)>),
+}
+
+fn invalid(value: &Value, to: impl ToString) -> Error {
+    Error::InvalidConvert {
+        from: value.to_string(),
+        to: to.to_string(),
+    }
+}
+
+fn unsupported(data_form: DataForm, data_type: DataType) -> Error {
+    Error::Unsupported {
+        data_form: data_form.to_string(),
+        data_type: data_type.to_string(),
+    }
+}
+
+fn json_integer(value: &Value) -> Option<i64> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        _ => value.as_i64(),
+    }
+}
+
+fn json_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        _ => value.as_f64(),
+    }
+}
+
+fn json_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        _ => value.as_bool(),
+    }
+}
+
+fn json_decimal(value: &Value) -> Option<Decimal> {
+    let s = match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+
+    Decimal::from_str(&s)
+        .or_else(|_| Decimal::from_scientific(&s))
+        .ok()
+}
+
+fn decimal_to_json(d: Option<Decimal>, options: &JsonOptions) -> Value {
+    match d {
+        Some(d) if options.decimal_as_string => Value::String(d.to_string()),
+        Some(d) => d.to_f64().into(),
+        None => Value::Null,
+    }
+}
+
+trait TemporalJson: Sized {
+    const ISO_FORMAT: &'static str;
+
+    fn to_json(&self, options: &JsonOptions) -> Value;
+
+    fn from_json(value: &Value, options: &JsonOptions) -> Option<Self>;
+}
+
+macro_rules! temporal_json_impl {
+    ($(($struct_name:ident, $iso_format:expr)), *) => {
+        $(
+            impl TemporalJson for $struct_name {
+                const ISO_FORMAT: &'static str = $iso_format;
+
+                fn to_json(&self, options: &JsonOptions) -> Value {
+                    match options.temporal_format {
+                        JsonTemporalFormat::Iso8601 => self.format_with(Self::ISO_FORMAT).into(),
+                        JsonTemporalFormat::DolphinDB => self.format_with(Self::DDB_FORMAT).into(),
+                        JsonTemporalFormat::Elapsed => self.elapsed().into(),
+                    }
+                }
+
+                fn from_json(value: &Value, options: &JsonOptions) -> Option<Self> {
+                    match options.temporal_format {
+                        JsonTemporalFormat::Iso8601 => {
+                            Self::parse_with(value.as_str()?, Self::ISO_FORMAT)
+                        }
+                        JsonTemporalFormat::DolphinDB => {
+                            Self::parse_with(value.as_str()?, Self::DDB_FORMAT)
+                        }
+                        JsonTemporalFormat::Elapsed => {
+                            Self::from_raw(json_integer(value)?.try_into().ok()?)
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+temporal_json_impl!(
+    (Date, "%Y-%m-%d"),
+    (Month, "%Y-%m"),
+    (Time, "%H:%M:%S%.3f"),
+    (Minute, "%H:%M"),
+    (Second, "%H:%M:%S"),
+    (DateTime, "%Y-%m-%dT%H:%M:%S"),
+    (Timestamp, "%Y-%m-%dT%H:%M:%S%.3f"),
+    (NanoTime, "%H:%M:%S%.9f"),
+    (NanoTimestamp, "%Y-%m-%dT%H:%M:%S%.9f"),
+    (DateHour, "%Y-%m-%dT%H")
+);
+
+fn scalar_to_json(s: &ScalarImpl, options: &JsonOptions) -> Value {
+    match s {
+        ScalarImpl::Void(_) => Value::Null,
+        ScalarImpl::Bool(x) => x.into_inner().into(),
+        ScalarImpl::Char(x) => x.into_inner().into(),
+        ScalarImpl::Short(x) => x.into_inner().into(),
+        ScalarImpl::Int(x) => x.into_inner().into(),
+        ScalarImpl::Long(x) => x.into_inner().into(),
+        ScalarImpl::Float(x) => x.into_inner().into(),
+        ScalarImpl::Double(x) => x.into_inner().into(),
+        ScalarImpl::Date(x) => x.to_json(options),
+        ScalarImpl::Month(x) => x.to_json(options),
+        ScalarImpl::Time(x) => x.to_json(options),
+        ScalarImpl::Minute(x) => x.to_json(options),
+        ScalarImpl::Second(x) => x.to_json(options),
+        ScalarImpl::DateTime(x) => x.to_json(options),
+        ScalarImpl::Timestamp(x) => x.to_json(options),
+        ScalarImpl::NanoTime(x) => x.to_json(options),
+        ScalarImpl::NanoTimestamp(x) => x.to_json(options),
+        ScalarImpl::DateHour(x) => x.to_json(options),
+        ScalarImpl::Symbol(x) => x.0.clone().into(),
+        ScalarImpl::String(x) => x.0.clone().into(),
+        ScalarImpl::Blob(x) => {
+            x.0.as_ref()
+                .map(|b| String::from_utf8_lossy(b).into_owned())
+                .into()
+        }
+        ScalarImpl::Decimal32(x) => decimal_to_json(x.into_inner(), options),
+        ScalarImpl::Decimal64(x) => decimal_to_json(x.into_inner(), options),
+        ScalarImpl::Decimal128(x) => decimal_to_json(x.into_inner(), options),
+    }
+}
+
+fn scalar_from_json(
+    value: &Value,
+    data_type: DataType,
+    options: &JsonOptions,
+) -> Result<ScalarImpl> {
+    if value.is_null() {
+        return ScalarImpl::from_type(data_type)
+            .ok_or_else(|| unsupported(DataForm::Scalar, data_type));
+    }
+
+    let s: Option<ScalarImpl> = match data_type {
+        DataType::Bool => json_bool(value).map(|x| Bool::new(x).into()),
+        DataType::Char => json_integer(value)
+            .and_then(|x| i8::try_from(x).ok())
+            .map(|x| Char::new(x).into()),
+        DataType::Short => json_integer(value)
+            .and_then(|x| i16::try_from(x).ok())
+            .map(|x| Short::new(x).into()),
+        DataType::Int => json_integer(value)
+            .and_then(|x| i32::try_from(x).ok())
+            .map(|x| Int::new(x).into()),
+        DataType::Long => json_integer(value).map(|x| Long::new(x).into()),
+        DataType::Float => json_float(value).map(|x| Float::new(x as f32).into()),
+        DataType::Double => json_float(value).map(|x| Double::new(x).into()),
+        DataType::Date => Date::from_json(value, options).map(Into::into),
+        DataType::Month => Month::from_json(value, options).map(Into::into),
+        DataType::Time => Time::from_json(value, options).map(Into::into),
+        DataType::Minute => Minute::from_json(value, options).map(Into::into),
+        DataType::Second => Second::from_json(value, options).map(Into::into),
+        DataType::DateTime => DateTime::from_json(value, options).map(Into::into),
+        DataType::Timestamp => Timestamp::from_json(value, options).map(Into::into),
+        DataType::NanoTime => NanoTime::from_json(value, options).map(Into::into),
+        DataType::NanoTimestamp => NanoTimestamp::from_json(value, options).map(Into::into),
+        DataType::DateHour => DateHour::from_json(value, options).map(Into::into),
+        DataType::Symbol => value.as_str().map(|x| Symbol::new(x.to_string()).into()),
+        DataType::String => value
+            .as_str()
+            .map(|x| DolphinString::new(x.to_string()).into()),
+        DataType::Blob => value
+            .as_str()
+            .map(|x| Blob::new(x.as_bytes().to_vec()).into()),
+        DataType::Decimal32 => json_decimal(value)
+            .and_then(|d| Decimal32::from_raw(d.mantissa().try_into().ok()?, d.scale()))
+            .map(Into::into),
+        DataType::Decimal64 => json_decimal(value)
+            .and_then(|d| Decimal64::from_raw(d.mantissa().try_into().ok()?, d.scale()))
+            .map(Into::into),
+        DataType::Decimal128 => json_decimal(value)
+            .and_then(|d| Decimal128::from_raw(d.mantissa(), d.scale()))
+            .map(Into::into),
+        DataType::Void => None,
+        _ => return Err(unsupported(DataForm::Scalar, data_type)),
+    };
+
+    s.ok_or_else(|| invalid(value, data_type))
+}
+
+fn infer_from_json(value: &Value) -> Result<ConstantImpl> {
+    let c: ConstantImpl = match value {
+        Value::Null => Void::default().into(),
+        Value::Bool(x) => Bool::new(*x).into(),
+        Value::Number(n) => match n.as_i64() {
+            Some(x) => Long::new(x).into(),
+            None => Double::new(n.as_f64().unwrap_or(f64::NAN)).into(),
+        },
+        Value::String(x) => DolphinString::new(x.clone()).into(),
+        Value::Array(_) => vector_from_json(value, DataType::Any, &JsonOptions::default())?.into(),
+        Value::Object(_) => return Err(invalid(value, DataType::Any)),
+    };
+
+    Ok(c)
+}
+
+macro_rules! array_vector_to_json {
+    ($v:expr, $raw_type:ty) => {
+        (0..$v.len())
+            .map(|i| {
+                $v[i]
+                    .iter()
+                    .map(|x| {
+                        if *x == <$raw_type>::MIN {
+                            Value::Null
+                        } else {
+                            Value::from(*x)
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+}
+
+fn vector_to_json(v: &VectorImpl, options: &JsonOptions) -> Result<Vec<Value>> {
+    let values = match v {
+        VectorImpl::ArrayVector(a) => match a {
+            ArrayVectorImpl::Char(a) => array_vector_to_json!(a, i8),
+            ArrayVectorImpl::Short(a) => array_vector_to_json!(a, i16),
+            ArrayVectorImpl::Int(a) => array_vector_to_json!(a, i32),
+            ArrayVectorImpl::Long(a) => array_vector_to_json!(a, i64),
+            ArrayVectorImpl::Float(a) => array_vector_to_json!(a, f32),
+            ArrayVectorImpl::Double(a) => array_vector_to_json!(a, f64),
+        },
+        // always some within bounds for other vectors
+        _ => (0..v.len())
+            .map(|i| constant_to_json(&v.get(i).unwrap(), options))
+            .collect::<Result<_>>()?,
+    };
+
+    Ok(values)
+}
+
+macro_rules! array_vector_from_json {
+    ($values:expr, $data_type:expr, $raw_type:ty, $parse:expr) => {{
+        let mut v = ArrayVector::<$raw_type>::new();
+        for row in $values {
+            let row = match row {
+                Value::Null => vec![],
+                Value::Array(xs) => xs
+                    .iter()
+                    .map(|x| match x {
+                        Value::Null => Some(<$raw_type>::MIN),
+                        x => $parse(x),
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| invalid(row, $data_type))?,
+                _ => return Err(invalid(row, $data_type)),
+            };
+            v.push(row);
+        }
+        v.into()
+    }};
+}
+
+fn vector_from_values<'a>(
+    values: impl IntoIterator<Item = &'a Value>,
+    data_type: DataType,
+    options: &JsonOptions,
+) -> Result<VectorImpl> {
+    let mut v: VectorImpl = match data_type {
+        DataType::Any => values
+            .into_iter()
+            .map(|x| infer_from_json(x).map(Any::new))
+            .collect::<Result<Vector<Any>>>()?
+            .into(),
+        DataType::CharArray => array_vector_from_json!(values, data_type, i8, |x| {
+            json_integer(x).and_then(|x| i8::try_from(x).ok())
+        }),
+        DataType::ShortArray => array_vector_from_json!(values, data_type, i16, |x| {
+            json_integer(x).and_then(|x| i16::try_from(x).ok())
+        }),
+        DataType::IntArray => array_vector_from_json!(values, data_type, i32, |x| {
+            json_integer(x).and_then(|x| i32::try_from(x).ok())
+        }),
+        DataType::LongArray => array_vector_from_json!(values, data_type, i64, json_integer),
+        DataType::FloatArray => array_vector_from_json!(values, data_type, f32, |x| {
+            json_float(x).map(|x| x as f32)
+        }),
+        DataType::DoubleArray => array_vector_from_json!(values, data_type, f64, json_float),
+        _ if ScalarImpl::from_type(data_type).is_some() => {
+            // always some for scalar types
+            let mut v = VectorImpl::from_type(data_type).unwrap();
+            for x in values {
+                v.push_scalar(scalar_from_json(x, data_type, options)?);
+            }
+            v
+        }
+        _ => return Err(unsupported(DataForm::Vector, data_type)),
+    };
+
+    match &mut v {
+        VectorImpl::Decimal32(v) => v.scale_to_same(),
+        VectorImpl::Decimal64(v) => v.scale_to_same(),
+        VectorImpl::Decimal128(v) => v.scale_to_same(),
+        _ => {}
+    }
+
+    Ok(v)
+}
+
+fn vector_from_json(
+    value: &Value,
+    data_type: DataType,
+    options: &JsonOptions,
+) -> Result<VectorImpl> {
+    let values = value
+        .as_array()
+        .ok_or_else(|| invalid(value, DataForm::Vector))?;
+    vector_from_values(values, data_type, options)
+}
+
+// Object keys are the text of the keys.
+fn key_text(key: Value) -> String {
+    match key {
+        Value::String(s) => s,
+        key => key.to_string(),
+    }
+}
+
+fn table_to_json(table: &Table, options: &JsonOptions) -> Result<Value> {
+    let columns = table
+        .columns()
+        .iter()
+        .map(|c| vector_to_json(c, options))
+        .collect::<Result<Vec<_>>>()?;
+
+    let value = match options.table_orient {
+        JsonTableOrient::Columns => Value::Object(
+            table
+                .column_names()
+                .iter()
+                .cloned()
+                .zip(columns.into_iter().map(Value::Array))
+                .collect(),
+        ),
+        JsonTableOrient::Records => {
+            let mut columns = columns.into_iter().map(Vec::into_iter).collect::<Vec<_>>();
+            let mut records = Vec::with_capacity(table.len());
+            for _ in 0..table.len() {
+                let record = table
+                    .column_names()
+                    .iter()
+                    .cloned()
+                    .zip(columns.iter_mut().map(|c| c.next().unwrap_or_default()))
+                    .collect::<Map<_, _>>();
+                records.push(Value::Object(record));
+            }
+            Value::Array(records)
+        }
+    };
+
+    Ok(value)
+}
+
+fn table_from_json(
+    value: &Value,
+    columns: &[(String, DataType)],
+    options: &JsonOptions,
+) -> Result<Table> {
+    let mut vectors = Vec::with_capacity(columns.len());
+
+    match value {
+        Value::Object(object) => {
+            for (name, data_type) in columns {
+                let column = object.get(name).ok_or_else(|| {
+                    Error::ConstraintsViolated(format!("column {} not found in json", name))
+                })?;
+                vectors.push(vector_from_json(column, *data_type, options)?);
+            }
+        }
+        Value::Array(records) => {
+            if let Some(record) = records.iter().find(|r| !r.is_object()) {
+                return Err(invalid(record, "record"));
+            }
+            // missing fields are null
+            for (name, data_type) in columns {
+                let column = records.iter().map(|r| r.get(name).unwrap_or(&Value::Null));
+                vectors.push(vector_from_values(column, *data_type, options)?);
+            }
+        }
+        _ => return Err(invalid(value, DataForm::Table)),
+    }
+
+    let mut builder = TableBuilder::new();
+    builder.with_contents(vectors, columns.iter().map(|(n, _)| n.clone()).collect());
+    builder.build()
+}
+
+fn constant_to_json(c: &ConstantImpl, options: &JsonOptions) -> Result<Value> {
+    let value = match c {
+        ConstantImpl::Scalar(s) => scalar_to_json(s, options),
+        ConstantImpl::Vector(v) => Value::Array(vector_to_json(v, options)?),
+        ConstantImpl::Pair(p) => Value::Array(vector_to_json(&p.clone().into(), options)?),
+        ConstantImpl::Set(s) => Value::Array(vector_to_json(&s.clone().into(), options)?),
+        ConstantImpl::Dictionary(d) => {
+            let (keys, values) = d.to_vecs();
+            Value::Object(
+                vector_to_json(&keys, options)?
+                    .into_iter()
+                    .map(key_text)
+                    .zip(vector_to_json(&values, options)?)
+                    .collect(),
+            )
+        }
+        ConstantImpl::Table(t) => table_to_json(t, options)?,
+        ConstantImpl::FunctionDef(_) | ConstantImpl::Opaque(_) => {
+            return Err(unsupported(c.data_form(), c.data_type()))
+        }
+    };
+
+    Ok(value)
+}
+
+impl ConstantImpl {
+    /// Converts to JSON with default [`JsonOptions`].
+    pub fn to_json(&self) -> Result<Value> {
+        self.to_json_with(&JsonOptions::default())
+    }
+
+    /// Converts to JSON with `options`.
+    pub fn to_json_with(&self, options: &JsonOptions) -> Result<Value> {
+        constant_to_json(self, options)
+    }
+
+    /// Converts from JSON with default [`JsonOptions`].
+    pub fn from_json(value: &Value, expected_type: &JsonType) -> Result<Self> {
+        Self::from_json_with(value, expected_type, &JsonOptions::default())
+    }
+
+    /// Converts from JSON as `expected_type`.
+    ///
+    /// Numbers are also accepted as strings, and tables in both [`JsonTableOrient`]s.
+    pub fn from_json_with(
+        value: &Value,
+        expected_type: &JsonType,
+        options: &JsonOptions,
+    ) -> Result<Self> {
+        let c: ConstantImpl = match expected_type {
+            JsonType::Scalar(DataType::Any) => return infer_from_json(value),
+            JsonType::Scalar(data_type) => scalar_from_json(value, *data_type, options)?.into(),
+            JsonType::Vector(data_type) => vector_from_json(value, *data_type, options)?.into(),
+            JsonType::Pair(data_type) => {
+                let v = vector_from_json(value, *data_type, options)?;
+                if v.len() != 2 {
+                    return Err(invalid(value, DataForm::Pair));
+                }
+                PairImpl::try_from(v)?.into()
+            }
+            JsonType::Set(data_type) => {
+                SetImpl::try_from(vector_from_json(value, *data_type, options)?)?.into()
+            }
+            JsonType::Dictionary(key_type, value_type) => {
+                let object = value
+                    .as_object()
+                    .ok_or_else(|| invalid(value, DataForm::Dictionary))?;
+                let keys = object
+                    .keys()
+                    .map(|k| Value::String(k.clone()))
+                    .collect::<Vec<_>>();
+                let keys = vector_from_values(&keys, *key_type, options)?;
+                let values = vector_from_values(object.values(), *value_type, options)?;
+                DictionaryImpl::from_vecs(keys, values)
+                    .ok_or_else(|| unsupported(DataForm::Dictionary, *key_type))?
+                    .into()
+            }
+            JsonType::Table(columns) => table_from_json(value, columns, options)?.into(),
+        };
+
+        Ok(c)
+    }
+}
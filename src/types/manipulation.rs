@@ -0,0 +1,264 @@
+//! Client-side manipulation of [`VectorImpl`] and [`Table`].
+//!
+//! All operations return new values with the same column types, nulls included.
+
+use std::ops::{Bound, RangeBounds};
+
+use super::{vector::for_all_vectors, ColumnIndex, Row, Table, TableBuilder, VectorImpl};
+use crate::error::{Error, Result};
+
+fn check_range(range: impl RangeBounds<usize>, len: usize) -> Result<(usize, usize)> {
+    let overflow = || Error::ConstraintsViolated("index overflow".into());
+    let start = match range.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s.checked_add(1).ok_or_else(overflow)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&e) => e.checked_add(1).ok_or_else(overflow)?,
+        Bound::Excluded(&e) => e,
+        Bound::Unbounded => len,
+    };
+
+    if start > end || end > len {
+        return Err(overflow());
+    }
+
+    Ok((start, end))
+}
+
+fn mask_indices(mask: &[bool], len: usize) -> Result<Vec<usize>> {
+    if mask.len() != len {
+        return Err(Error::ConstraintsViolated("mismatch mask size".into()));
+    }
+
+    Ok((0..len).filter(|&i| mask[i]).collect())
+}
+
+macro_rules! dispatch_gather {
+    ($(($enum_name:ident, $struct_name:ident)),*) => {
+        impl VectorImpl {
+            /// Elements at `indices`, which must be in bounds.
+            pub(crate) fn gather(&self, indices: &[usize]) -> Self {
+                match self {
+                    $(
+                        VectorImpl::$enum_name(v) => {
+                            VectorImpl::$enum_name(indices.iter().map(|&i| v[i].clone()).collect())
+                        }
+                    )*
+                    VectorImpl::ArrayVector(v) => VectorImpl::ArrayVector(v.gather(indices)),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! dispatch_extend {
+    ($(($enum_name:ident, $struct_name:ident)),*) => {
+        impl VectorImpl {
+            /// Appends elements of `other`, which must have the same data type.
+            pub fn extend_from(&mut self, other: &VectorImpl) -> Result<()> {
+                match (self, other) {
+                    $(
+                        (VectorImpl::$enum_name(v), VectorImpl::$enum_name(o)) => {
+                            v.extend(o.iter().cloned());
+                            Ok(())
+                        }
+                    )*
+                    (VectorImpl::ArrayVector(v), VectorImpl::ArrayVector(o)) => v.extend_from(o),
+                    (v, o) => Err(Error::InvalidConvert {
+                        from: o.data_type().to_string(),
+                        to: v.data_type().to_string(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! dispatch_argsort {
+    ($(($enum_name:ident, $struct_name:ident)),*) => {
+        impl VectorImpl {
+            /// Indices that sort the vector, nulls first in ascending order.
+            ///
+            /// The sort is stable. ANY vectors and array vectors are not sortable.
+            pub fn argsort(&self, ascending: bool) -> Result<Vec<usize>> {
+                let mut indices = (0..self.len()).collect::<Vec<_>>();
+
+                match self {
+                    $(
+                        VectorImpl::$enum_name(v) => {
+                            if ascending {
+                                indices.sort_by(|&a, &b| v[a].cmp(&v[b]));
+                            } else {
+                                indices.sort_by(|&a, &b| v[b].cmp(&v[a]));
+                            }
+                        }
+                    )*
+                    _ => {
+                        return Err(Error::Unsupported {
+                            data_form: "sort".into(),
+                            data_type: self.data_type().to_string(),
+                        })
+                    }
+                }
+
+                Ok(indices)
+            }
+        }
+    };
+}
+
+for_all_vectors!(dispatch_gather);
+
+for_all_vectors!(dispatch_extend);
+
+dispatch_argsort!(
+    (Void, Void),
+    (Bool, Bool),
+    (Date, Date),
+    (Month, Month),
+    (Time, Time),
+    (Minute, Minute),
+    (Second, Second),
+    (DateTime, DateTime),
+    (Timestamp, Timestamp),
+    (NanoTime, NanoTime),
+    (NanoTimestamp, NanoTimestamp),
+    (Symbol, Symbol),
+    (String, DolphinString),
+    (DateHour, DateHour),
+    (Decimal32, Decimal32),
+    (Decimal64, Decimal64),
+    (Decimal128, Decimal128),
+    (Char, Char),
+    (Short, Short),
+    (Int, Int),
+    (Long, Long),
+    (Float, Float),
+    (Double, Double),
+    (Blob, Blob)
+);
+
+impl VectorImpl {
+    /// Returns elements at `indices`, in that order.
+    pub fn take(&self, indices: &[usize]) -> Result<VectorImpl> {
+        if indices.iter().any(|&i| i >= self.len()) {
+            return Err(Error::ConstraintsViolated("index overflow".into()));
+        }
+
+        Ok(self.gather(indices))
+    }
+
+    /// Returns elements in `range`.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Result<VectorImpl> {
+        let (start, end) = check_range(range, self.len())?;
+        Ok(self.gather(&(start..end).collect::<Vec<_>>()))
+    }
+
+    /// Returns elements whose `mask` is [`true`].
+    pub fn filter(&self, mask: &[bool]) -> Result<VectorImpl> {
+        Ok(self.gather(&mask_indices(mask, self.len())?))
+    }
+
+    /// Returns a new vector with elements of `other` appended.
+    pub fn concat(&self, other: &VectorImpl) -> Result<VectorImpl> {
+        let mut res = self.clone();
+        res.extend_from(other)?;
+        Ok(res)
+    }
+
+    /// Returns a sorted copy, see [`VectorImpl::argsort`].
+    pub fn sort(&self, ascending: bool) -> Result<VectorImpl> {
+        Ok(self.gather(&self.argsort(ascending)?))
+    }
+}
+
+impl Table {
+    fn with_columns(&self, columns: Vec<VectorImpl>, column_names: Vec<String>) -> Result<Table> {
+        let mut builder = TableBuilder::new();
+        builder
+            .with_name(self.name().clone())
+            .with_contents(columns, column_names);
+        builder.build()
+    }
+
+    fn gather(&self, indices: &[usize]) -> Result<Table> {
+        let columns = self.columns().iter().map(|c| c.gather(indices)).collect();
+        self.with_columns(columns, self.column_names().clone())
+    }
+
+    /// Returns a table with the given columns, in that order.
+    pub fn select<C: ColumnIndex>(&self, columns: impl IntoIterator<Item = C>) -> Result<Table> {
+        let mut selected = Vec::new();
+        let mut names = Vec::new();
+
+        for column in columns {
+            let i = column.column_index(self).ok_or(Error::ConstraintsViolated(
+                "column not found in table".into(),
+            ))?;
+            selected.push(self.columns()[i].clone());
+            names.push(self.column_names()[i].clone());
+        }
+
+        self.with_columns(selected, names)
+    }
+
+    /// Returns rows at `indices`, in that order.
+    pub fn take(&self, indices: &[usize]) -> Result<Table> {
+        if indices.iter().any(|&i| i >= self.len()) {
+            return Err(Error::ConstraintsViolated("index overflow".into()));
+        }
+
+        self.gather(indices)
+    }
+
+    /// Returns rows in `range`.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Result<Table> {
+        let (start, end) = check_range(range, self.len())?;
+        self.gather(&(start..end).collect::<Vec<_>>())
+    }
+
+    /// Returns rows for which `predicate` returns [`true`].
+    pub fn filter(&self, mut predicate: impl FnMut(&Row<'_>) -> bool) -> Result<Table> {
+        let indices = self
+            .rows()
+            .filter(|row| predicate(row))
+            .map(|row| row.index())
+            .collect::<Vec<_>>();
+
+        self.gather(&indices)
+    }
+
+    /// Returns rows whose `mask` is [`true`].
+    pub fn filter_mask(&self, mask: &[bool]) -> Result<Table> {
+        self.gather(&mask_indices(mask, self.len())?)
+    }
+
+    /// Returns a new table with rows of `other` appended.
+    ///
+    /// Both tables must have the same column names and types, in the same order.
+    pub fn concat(&self, other: &Table) -> Result<Table> {
+        if self.column_names() != other.column_names() {
+            return Err(Error::ConstraintsViolated("mismatch column names".into()));
+        }
+
+        let columns = self
+            .columns()
+            .iter()
+            .zip(other.columns())
+            .map(|(a, b)| a.concat(b))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.with_columns(columns, self.column_names().clone())
+    }
+
+    /// Returns a table sorted by `column`, see [`VectorImpl::argsort`].
+    pub fn sort_by(&self, column: impl ColumnIndex, ascending: bool) -> Result<Table> {
+        let i = column.column_index(self).ok_or(Error::ConstraintsViolated(
+            "column not found in table".into(),
+        ))?;
+
+        self.gather(&self.columns()[i].argsort(ascending)?)
+    }
+}
@@ -4,15 +4,31 @@
 
 mod any;
 mod array_vector;
+mod cast;
+mod compute;
 mod constant;
 mod decimal;
 mod dictionary;
 mod enums;
+#[cfg(feature = "json")]
+mod json;
+mod manipulation;
+mod opaque;
 mod pair;
 mod primitive;
+mod raw;
+mod row;
 mod scalar;
 mod set;
 mod table;
+#[cfg(feature = "arrow")]
+mod table_arrow;
+#[cfg(feature = "csv")]
+mod table_csv;
+#[cfg(feature = "polars")]
+mod table_polars;
+#[cfg(feature = "serde")]
+mod table_serde;
 mod temporal;
 mod vector;
 
@@ -26,14 +42,30 @@ pub use constant::*;
 pub use decimal::*;
 pub use dictionary::*;
 pub use enums::*;
+#[cfg(feature = "json")]
+pub use json::*;
+pub use opaque::*;
 pub use pair::*;
 pub use primitive::*;
+pub use raw::{OptionMut, OptionRef, RawScalar, RawValue};
+pub use row::*;
 pub use scalar::*;
 pub use set::*;
 pub use table::*;
+#[cfg(feature = "arrow")]
+pub use table_arrow::*;
+#[cfg(feature = "csv")]
+pub use table_csv::*;
+#[cfg(feature = "polars")]
+pub use table_polars::*;
+#[cfg(feature = "serde")]
+pub use table_serde::*;
 pub use temporal::*;
 pub use vector::*;
 
+#[cfg(feature = "derive")]
+pub use dolphindb_derive::DolphinRow;
+
 macro_rules! to_scalar_impl {
     (DolphinString, DolphinString) => {
         to_scalar_impl!(DolphinString, String);
@@ -75,10 +107,22 @@ macro_rules! display_impl {
 
     (Char) => {};
 
+    (Symbol) => {
+        display_impl!(Symbol, as_ref);
+    };
+
+    (DolphinString) => {
+        display_impl!(DolphinString, as_ref);
+    };
+
     ($struct_name:ident) => {
+        display_impl!($struct_name, into_inner);
+    };
+
+    ($struct_name:ident, $getter:ident) => {
         impl Display for $struct_name {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                if let Some(val) = self.as_ref() {
+                if let Some(val) = self.$getter() {
                     write!(f, "{}", val)
                 } else {
                     write!(f, "null")
@@ -112,8 +156,8 @@ impl Display for Blob {
 
 impl Display for Char {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(val) = self.as_ref() {
-            write!(f, "{}", *val as u8 as char)
+        if let Some(val) = self.into_inner() {
+            write!(f, "{}", val as u8 as char)
         } else {
             write!(f, "null")
         }
@@ -128,7 +172,7 @@ macro_rules! scalar_trait_impl {
             }
 
             fn is_null(&self) -> bool {
-                self.0.is_none()
+                $struct_name::is_null(self)
             }
         }
     };
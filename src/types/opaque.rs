@@ -0,0 +1,245 @@
+//! Server side objects that have no client representation, e.g. function definitions,
+//! file handles, metacode and data sources.
+//!
+//! The client keeps the payload exactly as the server serialized it, so these values
+//! could be passed back to the server through [`run_function`](crate::client::Client::run_function).
+
+use std::fmt::{self, Display};
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+use crate::{
+    error::{Error, Result},
+    Deserialize, Serialize,
+};
+
+use super::{Constant, DataForm, DataType, DolphinString};
+
+/// DolphinDB's `FUNCTIONDEF` object.
+///
+/// Built-in functions could be passed as higher-order arguments:
+///
+/// ```
+/// use dolphindb::types::{ConstantImpl, FunctionDef, Int};
+///
+/// let args: Vec<ConstantImpl> = vec![FunctionDef::system("add").into(), Int::new(1).into()];
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct FunctionDef {
+    kind: u8,
+    body: String,
+}
+
+impl FunctionDef {
+    pub const DATA_BYTE: DataType = DataType::FunctionDef;
+
+    /// Function kind of built-in (system) functions.
+    pub const SYSTEM: u8 = 0;
+
+    /// Function kind of user defined functions.
+    pub const USER: u8 = 1;
+
+    /// Makes a new [`FunctionDef`] referring to a built-in function, e.g. `add`.
+    pub fn system(name: impl Into<String>) -> Self {
+        Self::from_raw(Self::SYSTEM, name)
+    }
+
+    /// Makes a new [`FunctionDef`] from the function kind and serialized body.
+    pub fn from_raw(kind: u8, body: impl Into<String>) -> Self {
+        Self {
+            kind,
+            body: body.into(),
+        }
+    }
+
+    pub const fn data_type() -> DataType {
+        Self::DATA_BYTE
+    }
+
+    pub const fn data_form() -> DataForm {
+        DataForm::Scalar
+    }
+
+    /// Function kind flag sent by server.
+    pub fn kind(&self) -> u8 {
+        self.kind
+    }
+
+    /// Function name for built-in functions, or the definition of user defined functions.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+}
+
+impl Display for FunctionDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.body)
+    }
+}
+
+impl Constant for FunctionDef {
+    fn data_form(&self) -> DataForm {
+        Self::data_form()
+    }
+
+    fn data_type(&self) -> DataType {
+        Self::data_type()
+    }
+
+    fn len(&self) -> usize {
+        1
+    }
+
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl Serialize for FunctionDef {
+    fn serialize<B>(&self, buffer: &mut B) -> Result<usize>
+    where
+        B: bytes::BufMut,
+    {
+        (self.data_type(), self.data_form()).serialize(buffer)?;
+
+        buffer.put_u8(self.kind);
+        DolphinString::new(self.body.clone()).serialize(buffer)
+    }
+
+    fn serialize_le<B>(&self, buffer: &mut B) -> Result<usize>
+    where
+        B: bytes::BufMut,
+    {
+        (self.data_type(), self.data_form()).serialize_le(buffer)?;
+
+        buffer.put_u8(self.kind);
+        DolphinString::new(self.body.clone()).serialize_le(buffer)
+    }
+}
+
+impl Deserialize for FunctionDef {
+    async fn deserialize<R>(&mut self, reader: &mut R) -> Result<()>
+    where
+        R: AsyncBufReadExt + Unpin,
+    {
+        self.kind = reader.read_u8().await?;
+
+        let mut body = DolphinString::default();
+        body.deserialize(reader).await?;
+        self.body = body.into_inner().unwrap_or_default();
+
+        Ok(())
+    }
+}
+
+/// Scalar objects of type `HANDLE`, `CODE`, `DATASOURCE` or `RESOURCE`.
+///
+/// The content is the string the server serialized and is not interpreted on the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opaque {
+    data_type: DataType,
+    content: String,
+}
+
+impl Opaque {
+    /// Makes a new [`Opaque`] of `data_type` holding server serialized `content`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Unsupported`] if `data_type` is not an opaque type.
+    pub fn new(data_type: DataType, content: impl Into<String>) -> Result<Self> {
+        if !Self::is_opaque(data_type) {
+            return Err(Error::Unsupported {
+                data_form: Self::data_form().to_string(),
+                data_type: data_type.to_string(),
+            });
+        }
+
+        Ok(Self {
+            data_type,
+            content: content.into(),
+        })
+    }
+
+    /// Returns `true` if objects of `data_type` could be held by [`Opaque`].
+    pub fn is_opaque(data_type: DataType) -> bool {
+        matches!(
+            data_type,
+            DataType::Handle | DataType::Code | DataType::DataSource | DataType::Resource
+        )
+    }
+
+    pub const fn data_form() -> DataForm {
+        DataForm::Scalar
+    }
+
+    pub fn data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    /// The server serialized content.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub(crate) fn from_type(data_type: DataType) -> Option<Self> {
+        Self::new(data_type, String::new()).ok()
+    }
+}
+
+impl Display for Opaque {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.content)
+    }
+}
+
+impl Constant for Opaque {
+    fn data_form(&self) -> DataForm {
+        Self::data_form()
+    }
+
+    fn data_type(&self) -> DataType {
+        self.data_type()
+    }
+
+    fn len(&self) -> usize {
+        1
+    }
+
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl Serialize for Opaque {
+    fn serialize<B>(&self, buffer: &mut B) -> Result<usize>
+    where
+        B: bytes::BufMut,
+    {
+        (self.data_type(), self.data_form()).serialize(buffer)?;
+
+        DolphinString::new(self.content.clone()).serialize(buffer)
+    }
+
+    fn serialize_le<B>(&self, buffer: &mut B) -> Result<usize>
+    where
+        B: bytes::BufMut,
+    {
+        (self.data_type(), self.data_form()).serialize_le(buffer)?;
+
+        DolphinString::new(self.content.clone()).serialize_le(buffer)
+    }
+}
+
+impl Deserialize for Opaque {
+    async fn deserialize<R>(&mut self, reader: &mut R) -> Result<()>
+    where
+        R: AsyncBufReadExt + Unpin,
+    {
+        let mut content = DolphinString::default();
+        content.deserialize(reader).await?;
+        self.content = content.into_inner().unwrap_or_default();
+
+        Ok(())
+    }
+}
@@ -1,10 +1,11 @@
-use super::{DataType, Scalar};
+use super::{DataType, OptionMut, OptionRef, RawScalar, Scalar};
 use crate::error::Error;
 use num_traits::{FromPrimitive, ToPrimitive};
 
 use std::any::type_name;
 use std::{
     cmp::Ordering,
+    fmt::{self, Debug},
     hash::{Hash, Hasher},
 };
 
@@ -22,41 +23,185 @@ pub trait Primitive:
 
 type U8Vec = Vec<u8>;
 
-macro_rules! integer_impl {
-    ($(($raw_type:tt, $struct_name:ident, $enum_name:ident)), *) => {
+#[derive(Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Void(pub(crate) Option<()>);
+
+macro_rules! numeric_impl {
+    ($(($raw_type:tt, $wire_type:tt, $struct_name:ident, $enum_name:ident)), *) => {
         $(
-            #[derive(Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-            pub struct $struct_name(pub(crate) Option<$raw_type>);
+            #[derive(Clone, Copy)]
+            #[repr(transparent)]
+            pub struct $struct_name(pub(crate) $wire_type);
+
+            impl Default for $struct_name {
+                fn default() -> Self {
+                    Self($wire_type::MIN)
+                }
+            }
+
+            impl Debug for $struct_name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_tuple(stringify!($struct_name))
+                        .field(&self.into_inner())
+                        .finish()
+                }
+            }
+
+            // SAFETY: the struct is repr(transparent) over the wire type.
+            unsafe impl RawScalar for $struct_name {
+                type Raw = $wire_type;
+
+                const NULL: Self::Raw = $wire_type::MIN;
+
+                #[inline]
+                fn from_raw_value(raw: Self::Raw) -> Self {
+                    Self(raw)
+                }
+
+                #[inline]
+                fn raw_value(&self) -> Self::Raw {
+                    self.0
+                }
+            }
+
+            impl $struct_name {
+                pub const DATA_BYTE: DataType = DataType::$enum_name;
+
+                /// Makes a new scalar.
+                ///
+                /// DolphinDB stores null as `MIN` of the wire type, so `new(MIN)` is the same
+                /// as [`from_raw`](Self::from_raw)`(MIN)`: it is null, and its [`ToPrimitive`]
+                /// conversions still yield `MIN`.
+                pub fn new(val: $raw_type) -> Self {
+                    Self(val.into())
+                }
+
+                /// Wraps a value as DolphinDB stores it, `MIN` being null.
+                pub const fn from_raw(raw: $wire_type) -> Self {
+                    Self(raw)
+                }
+
+                pub const fn data_type() -> DataType {
+                    Self::DATA_BYTE
+                }
+
+                pub const fn is_null(&self) -> bool {
+                    self.0 == $wire_type::MIN
+                }
+            }
         )*
     };
 }
 
-integer_impl!(
-    ((), Void, Void),
-    (bool, Bool, Bool),
-    (i8, Char, Char),
-    (i16, Short, Short),
-    (i32, Int, Int),
-    (i64, Long, Long)
+numeric_impl!(
+    (bool, i8, Bool, Bool),
+    (i8, i8, Char, Char),
+    (i16, i16, Short, Short),
+    (i32, i32, Int, Int),
+    (i64, i64, Long, Long),
+    (f32, f32, Float, Float),
+    (f64, f64, Double, Double)
 );
 
-macro_rules! float_impl {
-    ($(($raw_type:tt, $struct_name:ident, $enum_name:ident)), *) => {
+impl Bool {
+    pub fn into_inner(self) -> Option<bool> {
+        (!self.is_null()).then_some(self.0 != 0)
+    }
+}
+
+macro_rules! into_inner_impl {
+    ($(($raw_type:tt, $struct_name:ident)), *) => {
         $(
-            #[derive(Default, Clone, Copy, Debug)]
-            pub struct $struct_name(pub(crate) Option<$raw_type>);
+            impl $struct_name {
+                pub fn into_inner(self) -> Option<$raw_type> {
+                    (!self.is_null()).then_some(self.0)
+                }
+            }
+        )*
+    };
+}
+
+into_inner_impl!(
+    (i8, Char),
+    (i16, Short),
+    (i32, Int),
+    (i64, Long),
+    (f32, Float),
+    (f64, Double)
+);
+
+macro_rules! option_view_impl {
+    ($(($raw_type:tt, $struct_name:ident)), *) => {
+        $(
+            impl $struct_name {
+                /// Views the value as an [`Option`], [`None`] if null.
+                pub fn as_ref(&self) -> OptionRef<'_, $raw_type> {
+                    OptionRef::new(self.into_inner())
+                }
+
+                /// Views the value as a mutable [`Option`], written back when the view is dropped.
+                pub fn as_mut(&mut self) -> OptionMut<'_, Self, $raw_type> {
+                    let value = self.into_inner();
+                    OptionMut::new(self, value)
+                }
+            }
+        )*
+    };
+}
+
+option_view_impl!(
+    (bool, Bool),
+    (i8, Char),
+    (i16, Short),
+    (i32, Int),
+    (i64, Long),
+    (f32, Float),
+    (f64, Double)
+);
+
+macro_rules! eq_ord_hash_integer_impl {
+    ($($struct_name:ident), *) => {
+        $(
+            impl PartialEq for $struct_name {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    self.into_inner() == other.into_inner()
+                }
+            }
+
+            impl Eq for $struct_name {}
+
+            impl PartialOrd for $struct_name {
+                #[inline]
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for $struct_name {
+                #[inline]
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.into_inner().cmp(&other.into_inner())
+                }
+            }
+
+            impl Hash for $struct_name {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.into_inner().hash(state)
+                }
+            }
         )*
     };
 }
 
-float_impl!((f32, Float, Float), (f64, Double, Double));
+eq_ord_hash_integer_impl!(Bool, Char, Short, Int, Long);
 
 macro_rules! eq_ord_hash_impl {
     ($raw_type:tt, $struct_name:ident, $enum_name:ident) => {
         impl PartialEq for $struct_name {
             #[inline]
             fn eq(&self, other: &$struct_name) -> bool {
-                match (self.0, other.0) {
+                match (self.into_inner(), other.into_inner()) {
                     (None, None) => true,
                     (Some(a), Some(b)) => {
                         if a.is_nan() {
@@ -100,7 +245,7 @@ macro_rules! eq_ord_hash_impl {
                 // self >= other is correct. If other is also not NaN it is trivially
                 // correct, and if it is we note that nothing can be greater or
                 // equal to NaN except NaN itself, which we already handled earlier.
-                match (self.0, other.0) {
+                match (self.into_inner(), other.into_inner()) {
                     (None, None) => true,
                     (None, Some(_)) => false,
                     (Some(_), None) => true,
@@ -123,7 +268,7 @@ macro_rules! eq_ord_hash_impl {
 
         impl Hash for $struct_name {
             fn hash<H: Hasher>(&self, state: &mut H) {
-                let bits = if let Some(f) = self.0 {
+                let bits = if let Some(f) = self.into_inner() {
                     if f.is_nan() {
                         0x7ff8000000000000u64
                     } else {
@@ -228,14 +373,14 @@ macro_rules! from_raw_impl {
     ($raw_type:tt, $struct_name:ident) => {
         impl From<$raw_type> for $struct_name {
             fn from(value: $raw_type) -> Self {
-                Self::new(value.into())
+                Self::new(value)
             }
         }
 
         impl From<Option<$raw_type>> for $struct_name {
             fn from(value: Option<$raw_type>) -> Self {
                 match value {
-                    Some(val) => Self::new(val.into()),
+                    Some(val) => Self::new(val),
                     None => Self::default(),
                 }
             }
@@ -354,44 +499,56 @@ macro_rules! for_all_types {
     };
 }
 
-for_all_types!(common_impl);
+macro_rules! for_literal_types {
+    ($macro:tt) => {
+        $macro!(
+            ((), Void, Void),
+            (String, Symbol, Symbol),
+            (String, DolphinString, String),
+            (U8Vec, Blob, Blob)
+        );
+    };
+}
+
+for_literal_types!(common_impl);
 
-for_all_types!(as_ref_impl);
+for_literal_types!(as_ref_impl);
 
-for_all_types!(as_mut_impl);
+for_literal_types!(as_mut_impl);
 
 for_all_types!(from_raw_impl);
 
 for_all_types!(primitive_impl);
 
+// Integers convert their stored value, so null converts as the `MIN` sentinel.
 macro_rules! to_from_primitive_impl {
     ($(($raw_type:tt, $struct_name:ident, $enum_name:ident)), *) => {
         $(
             impl ToPrimitive for $struct_name {
                 fn to_i64(&self) -> Option<i64> {
-                    self.0.and_then(|v| v.to_i64())
+                    self.0.to_i64()
                 }
 
                 fn to_u64(&self) -> Option<u64> {
-                    self.0.and_then(|v| v.to_u64())
+                    self.0.to_u64()
                 }
 
                 fn to_f64(&self) -> Option<f64> {
-                    self.0.and_then(|v| v.to_f64())
+                    self.0.to_f64()
                 }
             }
 
             impl FromPrimitive for $struct_name {
                 fn from_i64(n: i64) -> Option<Self> {
-                    n.to_i64().map(|v| Self(Some(v as $raw_type)))
+                    n.to_i64().map(|v| Self::new(v as $raw_type))
                 }
 
                 fn from_u64(n: u64) -> Option<Self> {
-                    n.to_u64().map(|v| Self(Some(v as $raw_type)))
+                    n.to_u64().map(|v| Self::new(v as $raw_type))
                 }
 
                 fn from_f64(n: f64) -> Option<Self> {
-                    n.to_f64().map(|v| Self(Some(v as $raw_type)))
+                    n.to_f64().map(|v| Self::new(v as $raw_type))
                 }
             }
         )*
@@ -411,29 +568,29 @@ macro_rules! to_from_primitive_float_impl {
         $(
             impl ToPrimitive for $struct_name {
                 fn to_i64(&self) -> Option<i64> {
-                    self.0.and_then(|v| v.to_i64())
+                    self.into_inner().and_then(|v| v.to_i64())
                 }
 
                 fn to_u64(&self) -> Option<u64> {
-                    self.0.and_then(|v| v.to_u64())
+                    self.into_inner().and_then(|v| v.to_u64())
                 }
 
                 fn to_f64(&self) -> Option<f64> {
-                    self.0.map(|v| v as f64)
+                    self.into_inner().map(|v| v as f64)
                 }
             }
 
             impl FromPrimitive for $struct_name {
                 fn from_i64(n: i64) -> Option<Self> {
-                    n.to_f64().map(|v| Self(Some(v as $raw_type)))
+                    n.to_f64().map(|v| Self::new(v as $raw_type))
                 }
 
                 fn from_u64(n: u64) -> Option<Self> {
-                    n.to_f64().map(|v| Self(Some(v as $raw_type)))
+                    n.to_f64().map(|v| Self::new(v as $raw_type))
                 }
 
                 fn from_f64(n: f64) -> Option<Self> {
-                    Some(Self(Some(n as $raw_type)))
+                    Some(Self::new(n as $raw_type))
                 }
             }
         )*
@@ -0,0 +1,180 @@
+//! Raw in-memory representation of fixed-width scalars.
+
+use crate::error::Result;
+use bytes::BufMut;
+use std::marker::PhantomData;
+use std::mem::size_of_val;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+/// Fixed-width value as it is encoded on the wire.
+pub trait RawValue: Copy + Send + Sync + PartialEq + 'static {
+    /// Reverses the byte order of the value.
+    fn swap_bytes(self) -> Self;
+}
+
+macro_rules! raw_value_integer {
+    ($($raw_type:ty), *) => {
+        $(
+            impl RawValue for $raw_type {
+                #[inline]
+                fn swap_bytes(self) -> Self {
+                    <$raw_type>::swap_bytes(self)
+                }
+            }
+        )*
+    };
+}
+
+raw_value_integer!(i8, i16, i32, i64);
+
+macro_rules! raw_value_float {
+    ($($raw_type:ty), *) => {
+        $(
+            impl RawValue for $raw_type {
+                #[inline]
+                fn swap_bytes(self) -> Self {
+                    <$raw_type>::from_bits(self.to_bits().swap_bytes())
+                }
+            }
+        )*
+    };
+}
+
+raw_value_float!(f32, f64);
+
+/// Scalars stored as their raw DolphinDB value, with null encoded as a sentinel.
+///
+/// A [`Vector`](super::Vector) of such scalars is a contiguous buffer of raw values, see
+/// [`Vector::as_slice`](super::Vector::as_slice).
+///
+/// # Safety
+///
+/// Implementors must be `#[repr(transparent)]` over [`RawScalar::Raw`].
+pub unsafe trait RawScalar: Copy {
+    type Raw: RawValue;
+
+    /// Raw value representing null.
+    const NULL: Self::Raw;
+
+    /// Wraps a raw value without checking it.
+    fn from_raw_value(raw: Self::Raw) -> Self;
+
+    /// Returns the raw value, [`RawScalar::NULL`] if null.
+    fn raw_value(&self) -> Self::Raw;
+}
+
+pub(crate) fn as_raw_slice<S: RawScalar>(data: &[S]) -> &[S::Raw] {
+    // SAFETY: S is repr(transparent) over S::Raw.
+    unsafe { slice::from_raw_parts(data.as_ptr() as *const S::Raw, data.len()) }
+}
+
+pub(crate) fn as_raw_slice_mut<S: RawScalar>(data: &mut [S]) -> &mut [S::Raw] {
+    // SAFETY: S is repr(transparent) over S::Raw.
+    unsafe { slice::from_raw_parts_mut(data.as_mut_ptr() as *mut S::Raw, data.len()) }
+}
+
+fn raw_bytes<T: RawValue>(data: &[T]) -> &[u8] {
+    // SAFETY: raw values are plain integers or floats without padding.
+    unsafe { slice::from_raw_parts(data.as_ptr() as *const u8, size_of_val(data)) }
+}
+
+fn raw_bytes_mut<T: RawValue>(data: &mut [T]) -> &mut [u8] {
+    // SAFETY: raw values are plain integers or floats, any bit pattern is valid.
+    unsafe { slice::from_raw_parts_mut(data.as_mut_ptr() as *mut u8, size_of_val(data)) }
+}
+
+/// Reads `slots.len()` raw values at once, swapping bytes if `big_endian` differs from the host.
+pub(crate) async fn read_raw_slice<S, R>(
+    slots: &mut [S],
+    reader: &mut R,
+    big_endian: bool,
+) -> Result<()>
+where
+    S: RawScalar,
+    R: AsyncBufReadExt + Unpin,
+{
+    let raw = as_raw_slice_mut(slots);
+    reader.read_exact(raw_bytes_mut(raw)).await?;
+
+    if big_endian != cfg!(target_endian = "big") {
+        for v in raw {
+            *v = v.swap_bytes();
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes raw values at once, swapping bytes if `big_endian` differs from the host.
+pub(crate) fn write_raw_slice<S, B>(data: &[S], buffer: &mut B, big_endian: bool)
+where
+    S: RawScalar,
+    B: BufMut,
+{
+    if big_endian == cfg!(target_endian = "big") {
+        buffer.put_slice(raw_bytes(as_raw_slice(data)));
+    } else {
+        for v in as_raw_slice(data) {
+            buffer.put_slice(raw_bytes(&[v.swap_bytes()]));
+        }
+    }
+}
+
+/// Value of a raw scalar as an [`Option`], returned by its `as_ref` method.
+pub struct OptionRef<'a, T> {
+    value: Option<T>,
+    _scalar: PhantomData<&'a ()>,
+}
+
+impl<T> OptionRef<'_, T> {
+    pub(crate) fn new(value: Option<T>) -> Self {
+        Self {
+            value,
+            _scalar: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for OptionRef<'_, T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Value of a raw scalar as an [`Option`], returned by its `as_mut` method.
+///
+/// Changes are written back to the scalar when the view is dropped.
+pub struct OptionMut<'a, S: From<Option<T>>, T: Copy> {
+    scalar: &'a mut S,
+    value: Option<T>,
+}
+
+impl<'a, S: From<Option<T>>, T: Copy> OptionMut<'a, S, T> {
+    pub(crate) fn new(scalar: &'a mut S, value: Option<T>) -> Self {
+        Self { scalar, value }
+    }
+}
+
+impl<S: From<Option<T>>, T: Copy> Deref for OptionMut<'_, S, T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<S: From<Option<T>>, T: Copy> DerefMut for OptionMut<'_, S, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<S: From<Option<T>>, T: Copy> Drop for OptionMut<'_, S, T> {
+    fn drop(&mut self) {
+        *self.scalar = S::from(self.value);
+    }
+}
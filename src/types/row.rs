@@ -0,0 +1,476 @@
+//! Typed table rows.
+//!
+//! [`DolphinRow`] is usually implemented with `#[derive(DolphinRow)]`, which requires the
+//! `derive` feature:
+//!
+//! ```ignore
+//! use chrono::NaiveDateTime;
+//! use dolphindb::types::DolphinRow;
+//! use rust_decimal::Decimal;
+//!
+//! #[derive(DolphinRow)]
+//! struct Trade {
+//!     #[dolphindb(symbol)]
+//!     sym: String,
+//!     #[dolphindb(rename = "ts", precision = "nano")]
+//!     time: NaiveDateTime,
+//!     #[dolphindb(precision = 9, scale = 4)]
+//!     price: Decimal,
+//!     qty: Option<i32>,
+//! }
+//!
+//! let table = Trade::to_table(&trades)?;
+//! let trades = Trade::from_table(&table)?;
+//! ```
+//!
+//! Supported field types are [`bool`], integers, floats, [`String`], [`NaiveDate`],
+//! [`NaiveTime`], [`NaiveDateTime`], [`Decimal`], `Vec` of integers or floats (array vectors)
+//! and [`Option`] of scalar types for nullable columns.
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use paste::paste;
+use rust_decimal::Decimal;
+
+use crate::error::{Error, Result};
+
+use super::*;
+
+/// Types that could be converted to and from [`Table`] rows.
+pub trait DolphinRow: Sized {
+    /// Column names in field order.
+    fn column_names() -> Vec<String>;
+
+    /// Column types in field order.
+    fn column_types() -> Vec<DataType>;
+
+    /// Appends this row to `columns`, which are built from [`column_types`](DolphinRow::column_types).
+    fn push_row(&self, columns: &mut [VectorImpl]) -> Result<()>;
+
+    /// Reads the `index`th row from `columns`, which are ordered as
+    /// [`column_names`](DolphinRow::column_names).
+    fn from_row(columns: &[&VectorImpl], index: usize) -> Result<Self>;
+
+    /// Builds a [`Table`] from `rows`.
+    fn to_table(rows: &[Self]) -> Result<Table> {
+        let mut columns = Self::column_types()
+            .into_iter()
+            .map(|data_type| {
+                VectorImpl::from_type(data_type).ok_or(Error::Unsupported {
+                    data_form: DataForm::Vector.to_string(),
+                    data_type: data_type.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for row in rows {
+            row.push_row(&mut columns)?;
+        }
+
+        let mut builder = TableBuilder::new();
+        builder.with_contents(columns, Self::column_names());
+        builder.build()
+    }
+
+    /// Extracts every row of `table`, matching columns by name.
+    ///
+    /// Columns without a corresponding field are ignored.
+    fn from_table(table: &Table) -> Result<Vec<Self>> {
+        let columns = Self::column_names()
+            .iter()
+            .map(|name| {
+                table
+                    .column_names()
+                    .iter()
+                    .position(|n| n == name)
+                    .map(|i| table.get_columns_by_index(i))
+                    .ok_or(Error::ConstraintsViolated(format!(
+                        "column `{}` not found in table",
+                        name
+                    )))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        (0..table.len())
+            .map(|i| Self::from_row(&columns, i))
+            .collect()
+    }
+}
+
+/// Precision of temporal columns.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimePrecision {
+    /// `SECOND` and `DATETIME`.
+    Second,
+    /// `TIME` and `TIMESTAMP`.
+    #[default]
+    Milli,
+    /// `NANOTIME` and `NANOTIMESTAMP`.
+    Nano,
+}
+
+/// Column options of a [`RowField`], set by `#[dolphindb(...)]` field attributes.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldOptions {
+    /// Stores [`String`] fields as `SYMBOL` instead of `STRING`.
+    pub symbol: bool,
+    /// Rescales [`Decimal`] fields.
+    pub scale: Option<u32>,
+    /// Precision of [`NaiveTime`] and [`NaiveDateTime`] fields.
+    pub precision: TimePrecision,
+    /// Maximum number of digits of [`Decimal`] fields, which selects `DECIMAL32` up to 9,
+    /// `DECIMAL64` up to 18 and `DECIMAL128` up to 38.
+    ///
+    /// Fields are `DECIMAL64` and read from any decimal column if not set.
+    pub decimal_precision: Option<u32>,
+}
+
+/// Field types of a [`DolphinRow`].
+pub trait RowField: Sized {
+    /// Column type of the field.
+    fn field_type(options: &FieldOptions) -> DataType;
+
+    /// Appends the field to `column`.
+    fn push_field(&self, column: &mut VectorImpl, options: &FieldOptions) -> Result<()>;
+
+    /// Reads the field from the `index`th element of `column`.
+    fn get_field(column: &VectorImpl, index: usize, options: &FieldOptions) -> Result<Self>;
+}
+
+fn scalar_at(column: &VectorImpl, index: usize) -> Result<ScalarImpl> {
+    match column.get(index) {
+        Some(ConstantImpl::Scalar(s)) => Ok(s),
+        _ => Err(Error::InvalidConvert {
+            from: column.data_type().to_string(),
+            to: DataForm::Scalar.to_string(),
+        }),
+    }
+}
+
+fn push_checked(column: &mut VectorImpl, s: ScalarImpl) -> Result<()> {
+    if column.data_type() != s.data_type() {
+        return Err(Error::InvalidConvert {
+            from: s.data_type().to_string(),
+            to: column.data_type().to_string(),
+        });
+    }
+
+    column.push_scalar(s);
+    Ok(())
+}
+
+fn null_field(data_type: DataType) -> Error {
+    Error::InvalidConvert {
+        from: format!("null {}", data_type),
+        to: "non-null field".into(),
+    }
+}
+
+macro_rules! primitive_field_impl {
+    ($raw_type:ty, $struct_name:ident) => {
+        paste! {
+        impl RowField for $raw_type {
+            fn field_type(_options: &FieldOptions) -> DataType {
+                $struct_name::data_type()
+            }
+
+            fn push_field(&self, column: &mut VectorImpl, _options: &FieldOptions) -> Result<()> {
+                push_checked(column, $struct_name::new(*self).into())
+            }
+
+            fn get_field(column: &VectorImpl, index: usize, _options: &FieldOptions) -> Result<Self> {
+                let s = scalar_at(column, index)?;
+                let data_type = s.data_type();
+                s.[<as_ $struct_name:snake>]()?
+                    .into_inner()
+                    .ok_or(null_field(data_type))
+            }
+        }
+        }
+    };
+
+    ($(($raw_type:ty, $struct_name:ident)), *) => {
+        $(
+            primitive_field_impl!($raw_type, $struct_name);
+        )*
+    };
+}
+
+primitive_field_impl!(
+    (bool, Bool),
+    (i8, Char),
+    (i16, Short),
+    (i32, Int),
+    (i64, Long),
+    (f32, Float),
+    (f64, Double)
+);
+
+impl RowField for String {
+    fn field_type(options: &FieldOptions) -> DataType {
+        if options.symbol {
+            DataType::Symbol
+        } else {
+            DataType::String
+        }
+    }
+
+    fn push_field(&self, column: &mut VectorImpl, options: &FieldOptions) -> Result<()> {
+        let s: ScalarImpl = if options.symbol {
+            Symbol::new(self.clone()).into()
+        } else {
+            DolphinString::new(self.clone()).into()
+        };
+        push_checked(column, s)
+    }
+
+    fn get_field(column: &VectorImpl, index: usize, _options: &FieldOptions) -> Result<Self> {
+        let s = scalar_at(column, index)?;
+        let data_type = s.data_type();
+        match s {
+            ScalarImpl::Symbol(v) => v.into_inner(),
+            ScalarImpl::String(v) => v.into_inner(),
+            _ => {
+                return Err(Error::InvalidConvert {
+                    from: data_type.to_string(),
+                    to: "String".into(),
+                })
+            }
+        }
+        .ok_or(null_field(data_type))
+    }
+}
+
+impl RowField for NaiveDate {
+    fn field_type(_options: &FieldOptions) -> DataType {
+        DataType::Date
+    }
+
+    fn push_field(&self, column: &mut VectorImpl, _options: &FieldOptions) -> Result<()> {
+        push_checked(column, Date::from(*self).into())
+    }
+
+    fn get_field(column: &VectorImpl, index: usize, _options: &FieldOptions) -> Result<Self> {
+        let s = scalar_at(column, index)?;
+        let data_type = s.data_type();
+        match s {
+            ScalarImpl::Date(v) => v.into_inner(),
+            ScalarImpl::Month(v) => v.into_inner(),
+            _ => {
+                return Err(Error::InvalidConvert {
+                    from: data_type.to_string(),
+                    to: "NaiveDate".into(),
+                })
+            }
+        }
+        .ok_or(null_field(data_type))
+    }
+}
+
+impl RowField for NaiveTime {
+    fn field_type(options: &FieldOptions) -> DataType {
+        match options.precision {
+            TimePrecision::Second => DataType::Second,
+            TimePrecision::Milli => DataType::Time,
+            TimePrecision::Nano => DataType::NanoTime,
+        }
+    }
+
+    fn push_field(&self, column: &mut VectorImpl, options: &FieldOptions) -> Result<()> {
+        let s: ScalarImpl = match options.precision {
+            TimePrecision::Second => Second::from(*self).into(),
+            TimePrecision::Milli => Time::from(*self).into(),
+            TimePrecision::Nano => NanoTime::from(*self).into(),
+        };
+        push_checked(column, s)
+    }
+
+    fn get_field(column: &VectorImpl, index: usize, _options: &FieldOptions) -> Result<Self> {
+        let s = scalar_at(column, index)?;
+        let data_type = s.data_type();
+        match s {
+            ScalarImpl::Time(v) => v.into_inner(),
+            ScalarImpl::Minute(v) => v.into_inner(),
+            ScalarImpl::Second(v) => v.into_inner(),
+            ScalarImpl::NanoTime(v) => v.into_inner(),
+            _ => {
+                return Err(Error::InvalidConvert {
+                    from: data_type.to_string(),
+                    to: "NaiveTime".into(),
+                })
+            }
+        }
+        .ok_or(null_field(data_type))
+    }
+}
+
+impl RowField for NaiveDateTime {
+    fn field_type(options: &FieldOptions) -> DataType {
+        match options.precision {
+            TimePrecision::Second => DataType::DateTime,
+            TimePrecision::Milli => DataType::Timestamp,
+            TimePrecision::Nano => DataType::NanoTimestamp,
+        }
+    }
+
+    fn push_field(&self, column: &mut VectorImpl, options: &FieldOptions) -> Result<()> {
+        let s: ScalarImpl = match options.precision {
+            TimePrecision::Second => DateTime::from(*self).into(),
+            TimePrecision::Milli => Timestamp::from(*self).into(),
+            TimePrecision::Nano => NanoTimestamp::from(*self).into(),
+        };
+        push_checked(column, s)
+    }
+
+    fn get_field(column: &VectorImpl, index: usize, _options: &FieldOptions) -> Result<Self> {
+        let s = scalar_at(column, index)?;
+        let data_type = s.data_type();
+        match s {
+            ScalarImpl::DateTime(v) => v.into_inner(),
+            ScalarImpl::Timestamp(v) => v.into_inner(),
+            ScalarImpl::NanoTimestamp(v) => v.into_inner(),
+            ScalarImpl::DateHour(v) => v.into_inner(),
+            _ => {
+                return Err(Error::InvalidConvert {
+                    from: data_type.to_string(),
+                    to: "NaiveDateTime".into(),
+                })
+            }
+        }
+        .ok_or(null_field(data_type))
+    }
+}
+
+impl RowField for Decimal {
+    fn field_type(options: &FieldOptions) -> DataType {
+        match options.decimal_precision {
+            Some(0..=9) => DataType::Decimal32,
+            Some(19..) => DataType::Decimal128,
+            _ => DataType::Decimal64,
+        }
+    }
+
+    fn push_field(&self, column: &mut VectorImpl, options: &FieldOptions) -> Result<()> {
+        let mut d = *self;
+        if let Some(scale) = options.scale {
+            d.rescale(scale);
+        }
+
+        let data_type = Self::field_type(options);
+        let s: Option<ScalarImpl> = match data_type {
+            DataType::Decimal32 => i32::try_from(d.mantissa())
+                .ok()
+                .and_then(|m| Decimal32::from_raw(m, d.scale()))
+                .map(Into::into),
+            DataType::Decimal64 => i64::try_from(d.mantissa())
+                .ok()
+                .and_then(|m| Decimal64::from_raw(m, d.scale()))
+                .map(Into::into),
+            _ => Decimal128::from_raw(d.mantissa(), d.scale()).map(Into::into),
+        };
+        let s = s.ok_or(Error::InvalidConvert {
+            from: d.to_string(),
+            to: data_type.to_string(),
+        })?;
+        push_checked(column, s)
+    }
+
+    fn get_field(column: &VectorImpl, index: usize, options: &FieldOptions) -> Result<Self> {
+        let s = scalar_at(column, index)?;
+        let data_type = s.data_type();
+        if options.decimal_precision.is_some() && data_type != Self::field_type(options) {
+            return Err(Error::InvalidConvert {
+                from: data_type.to_string(),
+                to: Self::field_type(options).to_string(),
+            });
+        }
+        match s {
+            ScalarImpl::Decimal32(v) => v.0,
+            ScalarImpl::Decimal64(v) => v.0,
+            ScalarImpl::Decimal128(v) => v.0,
+            _ => {
+                return Err(Error::InvalidConvert {
+                    from: data_type.to_string(),
+                    to: "Decimal".into(),
+                })
+            }
+        }
+        .ok_or(null_field(data_type))
+    }
+}
+
+impl<T: RowField> RowField for Option<T> {
+    fn field_type(options: &FieldOptions) -> DataType {
+        T::field_type(options)
+    }
+
+    fn push_field(&self, column: &mut VectorImpl, options: &FieldOptions) -> Result<()> {
+        match self {
+            Some(v) => v.push_field(column, options),
+            None => {
+                let s = ScalarImpl::from_type(column.data_type()).ok_or(Error::Unsupported {
+                    data_form: DataForm::Scalar.to_string(),
+                    data_type: column.data_type().to_string(),
+                })?;
+                column.push_scalar(s);
+                Ok(())
+            }
+        }
+    }
+
+    fn get_field(column: &VectorImpl, index: usize, options: &FieldOptions) -> Result<Self> {
+        match column.get(index) {
+            Some(ConstantImpl::Scalar(s)) if s.is_null() => Ok(None),
+            _ => T::get_field(column, index, options).map(Some),
+        }
+    }
+}
+
+macro_rules! array_field_impl {
+    ($raw_type:ty, $enum_name:ident) => {
+        paste! {
+        impl RowField for Vec<$raw_type> {
+            fn field_type(_options: &FieldOptions) -> DataType {
+                DataType::[<$enum_name Array>]
+            }
+
+            fn push_field(&self, column: &mut VectorImpl, _options: &FieldOptions) -> Result<()> {
+                match column {
+                    VectorImpl::ArrayVector(ArrayVectorImpl::$enum_name(v)) => {
+                        v.push(self.clone());
+                        Ok(())
+                    }
+                    _ => Err(Error::InvalidConvert {
+                        from: DataType::[<$enum_name Array>].to_string(),
+                        to: column.data_type().to_string(),
+                    }),
+                }
+            }
+
+            fn get_field(column: &VectorImpl, index: usize, _options: &FieldOptions) -> Result<Self> {
+                match column {
+                    VectorImpl::ArrayVector(ArrayVectorImpl::$enum_name(v)) => Ok(v[index].to_vec()),
+                    _ => Err(Error::InvalidConvert {
+                        from: column.data_type().to_string(),
+                        to: DataType::[<$enum_name Array>].to_string(),
+                    }),
+                }
+            }
+        }
+        }
+    };
+
+    ($(($raw_type:ty, $enum_name:ident)), *) => {
+        $(
+            array_field_impl!($raw_type, $enum_name);
+        )*
+    };
+}
+
+array_field_impl!(
+    (i8, Char),
+    (i16, Short),
+    (i32, Int),
+    (i64, Long),
+    (f32, Float),
+    (f64, Double)
+);
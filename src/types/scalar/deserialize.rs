@@ -4,7 +4,14 @@ use crate::{
 };
 use tokio::io::{AsyncBufReadExt, AsyncReadExt};
 
-use crate::types::{any::Any, decimal::*, primitive::*, temporal::*};
+use crate::types::{
+    any::Any,
+    decimal::*,
+    primitive::*,
+    raw::{as_raw_slice, read_raw_slice, RawScalar},
+    temporal::*,
+};
+use std::slice;
 
 impl Deserialize for Void {
     async fn deserialize<R>(&mut self, reader: &mut R) -> Result<()>
@@ -16,40 +23,6 @@ impl Deserialize for Void {
     }
 }
 
-impl Deserialize for Bool {
-    async fn deserialize<R>(&mut self, reader: &mut R) -> Result<()>
-    where
-        R: AsyncBufReadExt + Unpin,
-    {
-        let val = reader.read_i8().await?;
-
-        match val {
-            0 => self.0 = Some(false),
-            i8::MIN => self.0 = None,
-            _ => self.0 = Some(true),
-        }
-
-        Ok(())
-    }
-}
-
-impl Deserialize for Char {
-    async fn deserialize<R>(&mut self, reader: &mut R) -> Result<()>
-    where
-        R: AsyncBufReadExt + Unpin,
-    {
-        let val = reader.read_i8().await?;
-
-        if val == i8::MIN {
-            self.0 = None;
-        } else {
-            self.0 = Some(val as i8);
-        }
-
-        Ok(())
-    }
-}
-
 macro_rules! deserialize_literal {
     ($struct_name:ident) => {
         impl Deserialize for $struct_name {
@@ -92,118 +65,82 @@ macro_rules! deserialize_literal {
 
 deserialize_literal!((DolphinString), (Symbol));
 
-macro_rules! deserialize_primitive {
-    ($raw_type:tt, $read_func:ident, $func_name:ident) => {
-        async fn $func_name<R>(&mut self, reader: &mut R) -> Result<()>
-        where
-            R: AsyncBufReadExt + Unpin,
-        {
-            let val = reader.$read_func().await?;
-
-            if val == $raw_type::MIN {
-                self.0 = None;
-            } else {
-                self.0 = Some(val.into());
-            }
-
-            Ok(())
-        }
-    };
-
-    ($(($raw_type:tt, $struct_name:ident, $read_func:ident, $read_func_le:ident)), *) => {
-        $(
-            impl Deserialize for $struct_name {
-                deserialize_primitive!($raw_type, $read_func, deserialize);
-                deserialize_primitive!($raw_type, $read_func_le, deserialize_le);
-            }
-        )*
-    };
+/// Rejects values out of the range of their [`chrono`] type after a bulk read.
+fn check_elapsed<S: RawScalar, T>(slots: &[S], decode: fn(S::Raw) -> Option<T>) -> Result<()> {
+    let valid = |raw: S::Raw| raw == S::NULL || decode(raw).is_some();
+    if as_raw_slice(slots).iter().all(|raw| valid(*raw)) {
+        Ok(())
+    } else {
+        Err(Error::ConstraintsViolated(
+            "time elapsed out of bound".into(),
+        ))
+    }
 }
 
-deserialize_primitive!(
-    (i16, Short, read_i16, read_i16_le),
-    (i32, Int, read_i32, read_i32_le),
-    (i64, Long, read_i64, read_i64_le),
-    (f32, Float, read_f32, read_f32_le),
-    (f64, Double, read_f64, read_f64_le)
-);
-
-macro_rules! deserialize_i32_temporal {
-    ($func_name:ident, $elapsed_type:tt, $offset:expr) => {
-        async fn $func_name<R>(&mut self, reader: &mut R) -> Result<()>
-        where
-            R: AsyncBufReadExt + Unpin,
-        {
-            let mut int = Int::default();
-            int.$func_name(reader).await?;
-
-            if let Some(elapsed) = int.as_ref().map(|v| $elapsed_type::try_from(v).ok()).flatten() {
-                *self = Self::from_raw(elapsed - $offset)
-                        .ok_or(Error::ConstraintsViolated(
-                            "time elapsed out of bound".into(),
-                        ))?;
-            } else {
-                self.0 = None;
+macro_rules! deserialize_raw {
+    ($struct_name:ident $(, $decode:path)?) => {
+        impl Deserialize for $struct_name {
+            async fn deserialize<R>(&mut self, reader: &mut R) -> Result<()>
+            where
+                R: AsyncBufReadExt + Unpin,
+            {
+                Self::deserialize_slice(slice::from_mut(self), reader).await
             }
 
-            Ok(())
-        }
-    };
-
-    ($(($struct_name:ident, $elapsed_type:tt, $offset:expr)), *) => {
-        $(
-            impl Deserialize for $struct_name {
-                deserialize_i32_temporal!(deserialize, $elapsed_type, $offset);
-                deserialize_i32_temporal!(deserialize_le, $elapsed_type, $offset);
+            async fn deserialize_le<R>(&mut self, reader: &mut R) -> Result<()>
+            where
+                R: AsyncBufReadExt + Unpin,
+            {
+                Self::deserialize_slice_le(slice::from_mut(self), reader).await
             }
-        )*
-    };
-}
 
-macro_rules! deserialize_i64_temporal {
-    ($func_name:ident, $elapsed_type:tt) => {
-        async fn $func_name<R>(&mut self, reader: &mut R) -> Result<()>
-        where
-            R: AsyncBufReadExt + Unpin,
-        {
-            let mut long = Long::default();
-            long.$func_name(reader).await?;
-
-            if let Some(elapsed) = long.as_ref().map(|v| $elapsed_type::try_from(v).ok()).flatten() {
-                *self = Self::from_raw(elapsed)
-                        .ok_or(Error::ConstraintsViolated(
-                            "time elapsed out of bound".into(),
-                        ))?;
-            } else {
-                self.0 = None;
+            async fn deserialize_slice<R>(slots: &mut [Self], reader: &mut R) -> Result<()>
+            where
+                R: AsyncBufReadExt + Unpin,
+            {
+                read_raw_slice(slots, reader, true).await?;
+                $(check_elapsed(slots, $decode)?;)?
+                Ok(())
             }
 
-            Ok(())
+            async fn deserialize_slice_le<R>(slots: &mut [Self], reader: &mut R) -> Result<()>
+            where
+                R: AsyncBufReadExt + Unpin,
+            {
+                read_raw_slice(slots, reader, false).await?;
+                $(check_elapsed(slots, $decode)?;)?
+                Ok(())
+            }
         }
     };
 
-    ($(($struct_name:ident, $elapsed_type:tt)), *) => {
+    ($(($struct_name:ident $(, $decode:path)?)), *) => {
         $(
-            impl Deserialize for $struct_name {
-                deserialize_i64_temporal!(deserialize, $elapsed_type);
-                deserialize_i64_temporal!(deserialize_le, $elapsed_type);
-            }
+            deserialize_raw!($struct_name $(, $decode)?);
         )*
     };
 }
 
-deserialize_i32_temporal!(
-    (Date, i64, 0),
-    (Month, i32, 23640),
-    (Time, u32, 0),
-    (Minute, u32, 0),
-    (Second, u32, 0),
-    (DateTime, i32, 0),
-    (DateHour, i64, 0)
+deserialize_raw!(
+    (Bool),
+    (Char),
+    (Short),
+    (Int),
+    (Long),
+    (Float),
+    (Double),
+    (Date, Date::decode),
+    (Month, Month::decode),
+    (Time, Time::decode),
+    (Minute, Minute::decode),
+    (Second, Second::decode),
+    (DateTime, DateTime::decode),
+    (Timestamp, Timestamp::decode),
+    (NanoTime, NanoTime::decode),
+    (NanoTimestamp, NanoTimestamp::decode),
+    (DateHour, DateHour::decode)
 );
 
-deserialize_i64_temporal!((Timestamp, i64), (NanoTime, u64), (NanoTimestamp, i64));
-
 macro_rules! deserialize_decimal {
     ($raw_type:tt, $read_func:ident, $read_scale:ident, $func_name:ident) => {
         async fn $func_name<R>(&mut self, reader: &mut R) -> Result<()>
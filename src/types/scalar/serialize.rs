@@ -6,7 +6,8 @@ use bytes::BufMut;
 
 use byteorder::{WriteBytesExt, BE, LE};
 
-use crate::types::{any::Any, decimal::*, primitive::*, temporal::*};
+use crate::types::{any::Any, decimal::*, primitive::*, raw::write_raw_slice, temporal::*};
+use std::slice;
 
 impl Serialize for Void {
     fn serialize<B>(&self, buffer: &mut B) -> Result<usize>
@@ -19,26 +20,6 @@ impl Serialize for Void {
     }
 }
 
-impl Serialize for Bool {
-    fn serialize<B>(&self, buffer: &mut B) -> Result<usize>
-    where
-        B: bytes::BufMut,
-    {
-        buffer.put_i8(self.as_ref().map(|b| b as i8).unwrap_or(i8::MIN));
-        Ok(0)
-    }
-}
-
-impl Serialize for Char {
-    fn serialize<B>(&self, buffer: &mut B) -> Result<usize>
-    where
-        B: bytes::BufMut,
-    {
-        buffer.put_i8(self.as_ref().unwrap_or(i8::MIN));
-        Ok(0)
-    }
-}
-
 macro_rules! serialize_literal {
     ($struct_name:ident) => {
         impl Serialize for $struct_name {
@@ -62,88 +43,65 @@ macro_rules! serialize_literal {
 
 serialize_literal!(DolphinString, Symbol);
 
-macro_rules! serialize_primitive {
-    ($raw_type:tt, $write_func:ident, $func_name:ident, $endian:tt) => {
-        fn $func_name<B>(&self, buffer: &mut B) -> Result<usize>
-        where
-            B: bytes::BufMut,
-        {
-            let mut writer = buffer.writer();
-            writer
-                .$write_func::<$endian>(self.into_inner().unwrap_or($raw_type::MIN.into()).into())
-                .unwrap();
-            Ok(0)
-        }
-    };
-
-    ($(($raw_type:tt, $struct_name:ident, $write_func:ident)), *) => {
-        $(
-            impl Serialize for $struct_name {
-                serialize_primitive!($raw_type, $write_func, serialize, BE);
-                serialize_primitive!($raw_type, $write_func, serialize_le, LE);
-            }
-        )*
-    };
-}
-
-serialize_primitive!(
-    (i16, Short, write_i16),
-    (i32, Int, write_i32),
-    (i64, Long, write_i64),
-    (f32, Float, write_f32),
-    (f64, Double, write_f64)
-);
-
-macro_rules! serialize_i32_temporal {
-    ($func_name:ident, $offset:expr) => {
-        fn $func_name<B>(&self, buffer: &mut B) -> Result<usize>
-        where
-            B: BufMut,
-        {
-            Int::from(self.elapsed().map(|i| i as i32 + $offset)).$func_name(buffer)
-        }
-    };
-
-    ($(($struct_name:ident, $offset:expr)), *) => {
-        $(
-            impl Serialize for $struct_name {
-                serialize_i32_temporal!(serialize, $offset);
-                serialize_i32_temporal!(serialize_le, $offset);
-            }
-        )*
-    }
-}
-
-macro_rules! serialize_i64_temporal {
-    ($func_name:ident) => {
-        fn $func_name<B>(&self, buffer: &mut B) -> Result<usize>
-        where
-            B: BufMut,
-        {
-            Long::from(self.elapsed().map(|i| i as i64)).$func_name(buffer)
-        }
-    };
-
+macro_rules! serialize_raw {
     ($($struct_name:ident), *) => {
         $(
             impl Serialize for $struct_name {
-                serialize_i64_temporal!(serialize);
-                serialize_i64_temporal!(serialize_le);
+                fn serialize<B>(&self, buffer: &mut B) -> Result<usize>
+                where
+                    B: BufMut,
+                {
+                    write_raw_slice(slice::from_ref(self), buffer, true);
+                    Ok(0)
+                }
+
+                fn serialize_le<B>(&self, buffer: &mut B) -> Result<usize>
+                where
+                    B: BufMut,
+                {
+                    write_raw_slice(slice::from_ref(self), buffer, false);
+                    Ok(0)
+                }
+
+                fn serialize_slice<B>(data: &[Self], buffer: &mut B) -> Result<()>
+                where
+                    B: BufMut,
+                {
+                    write_raw_slice(data, buffer, true);
+                    Ok(())
+                }
+
+                fn serialize_slice_le<B>(data: &[Self], buffer: &mut B) -> Result<()>
+                where
+                    B: BufMut,
+                {
+                    write_raw_slice(data, buffer, false);
+                    Ok(())
+                }
             }
         )*
-    }
+    };
 }
 
-serialize_i32_temporal!(
-    (Date, 0),
-    (Month, 23640),
-    (Time, 0),
-    (Minute, 0),
-    (Second, 0),
-    (DateTime, 0),
-    (DateHour, 0)
+serialize_raw!(
+    Bool,
+    Char,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    Date,
+    Month,
+    Time,
+    Minute,
+    Second,
+    DateTime,
+    Timestamp,
+    NanoTime,
+    NanoTimestamp,
+    DateHour
 );
-serialize_i64_temporal!(Timestamp, NanoTime, NanoTimestamp);
 
 macro_rules! serialize_decimal {
     ($raw_type:tt, $write_func:ident, $func_name:ident, $endian:tt) => {
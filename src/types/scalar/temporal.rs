@@ -1,8 +1,9 @@
 //! Temporal type interface compatible to [`chrono`]
 
 use chrono::{
+    format::{self as chrono_format, Parsed, StrftimeItems},
     naive::{NaiveDate, NaiveDateTime, NaiveTime},
-    Datelike, Days, Months,
+    Datelike, Days, TimeZone,
 };
 
 use super::super::*;
@@ -16,31 +17,31 @@ impl Date {
 
     /// Makes a new [`Date`] from elapsed day since 1st of January 1970.
     pub fn from_raw(elapsed: i64) -> Option<Self> {
-        if elapsed >= 0 {
-            NaiveDate::default()
-                .checked_add_days(Days::new(elapsed as u64))
-                .map(Self::new)
-        } else {
-            NaiveDate::default()
-                .checked_sub_days(Days::new(elapsed.unsigned_abs()))
-                .map(Self::new)
-        }
+        let raw = i32::try_from(elapsed).ok()?;
+        Self::decode(raw).map(|_| Self(raw))
     }
 
     /// Counts of day since 1st of January 1970.
     pub fn elapsed(&self) -> Option<i64> {
-        self.as_ref().map(|d| (d - NaiveDate::default()).num_days())
+        (!self.is_null()).then_some(self.0 as i64)
+    }
+
+    pub(crate) fn encode(date: NaiveDate) -> i32 {
+        (date - NaiveDate::default()).num_days() as i32
     }
 
-    /// DolphinDB internal representation
-    pub(crate) fn ddb_rep(&self) -> i32 {
-        self.elapsed().unwrap() as i32
+    pub(crate) fn decode(raw: i32) -> Option<NaiveDate> {
+        if raw >= 0 {
+            NaiveDate::default().checked_add_days(Days::new(raw as u64))
+        } else {
+            NaiveDate::default().checked_sub_days(Days::new(raw.unsigned_abs() as u64))
+        }
     }
 }
 
 impl Display for Date {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.0 {
+        match self.into_inner() {
             None => write!(f, "null"),
             Some(s) => write!(f, "{}", s.format("%Y.%m.%d")),
         }
@@ -55,38 +56,30 @@ impl Month {
 
     /// Makes a new [`Month`] from elapsed month since January 1970.
     pub fn from_raw(elapsed: i32) -> Option<Self> {
-        if elapsed >= 0 {
-            NaiveDate::default()
-                .checked_add_months(Months::new(elapsed as u32))
-                .map(Self::new)
-        } else {
-            NaiveDate::default()
-                .checked_sub_months(Months::new(elapsed.unsigned_abs()))
-                .map(Self::new)
-        }
+        let raw = elapsed.checked_add(Self::EPOCH)?;
+        Self::decode(raw).map(|_| Self(raw))
     }
 
     /// Counts of month since January 1970.
     pub fn elapsed(&self) -> Option<i32> {
-        self.as_ref().map(|d| {
-            let start = NaiveDate::default();
+        (!self.is_null()).then(|| self.0 - Self::EPOCH)
+    }
 
-            let month = d.month() as i32 - start.month() as i32;
-            let year = d.year() - start.year();
+    /// Months from year 0 to January 1970.
+    const EPOCH: i32 = 1970 * 12;
 
-            year * 12 + month
-        })
+    pub(crate) fn encode(date: NaiveDate) -> i32 {
+        date.year() * 12 + date.month0() as i32
     }
 
-    /// DolphinDB internal representation
-    pub(crate) fn ddb_rep(&self) -> i32 {
-        self.elapsed().unwrap() + 23640
+    pub(crate) fn decode(raw: i32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(raw.div_euclid(12), raw.rem_euclid(12) as u32 + 1, 1)
     }
 }
 
 impl Display for Month {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.0 {
+        match self.into_inner() {
             None => write!(f, "null"),
             Some(s) => write!(f, "{}", s.format("%Y.%mM")),
         }
@@ -101,19 +94,22 @@ impl Time {
 
     /// Makes a new [`Time`] from elapsed milliseconds since 00:00:00.000.
     pub fn from_raw(elapsed: u32) -> Option<Self> {
-        NaiveTime::from_num_seconds_from_midnight_opt(elapsed / 1_000, elapsed % 1_000 * 1_000_000)
-            .map(Self::new)
+        let raw = i32::try_from(elapsed).ok()?;
+        Self::decode(raw).map(|_| Self(raw))
     }
 
     /// Counts of milliseconds since 00:00:00.000.
     pub fn elapsed(&self) -> Option<u32> {
-        self.as_ref()
-            .map(|t| (t - NaiveTime::default()).num_milliseconds() as u32)
+        (!self.is_null()).then_some(self.0 as u32)
     }
 
-    /// DolphinDB internal representation
-    pub(crate) fn ddb_rep(&self) -> i32 {
-        self.elapsed().unwrap() as i32
+    pub(crate) fn encode(time: NaiveTime) -> i32 {
+        (time - NaiveTime::default()).num_milliseconds() as i32
+    }
+
+    pub(crate) fn decode(raw: i32) -> Option<NaiveTime> {
+        let raw = u32::try_from(raw).ok()?;
+        NaiveTime::from_num_seconds_from_midnight_opt(raw / 1_000, raw % 1_000 * 1_000_000)
     }
 }
 
@@ -125,18 +121,22 @@ impl Minute {
 
     /// Makes a new [`Minute`] from elapsed minutes since 00:00.
     pub fn from_raw(elapsed: u32) -> Option<Self> {
-        NaiveTime::from_num_seconds_from_midnight_opt(elapsed * 60, 0).map(Self::new)
+        let raw = i32::try_from(elapsed).ok()?;
+        Self::decode(raw).map(|_| Self(raw))
     }
 
     /// Counts of minutes since 00:00.
     pub fn elapsed(&self) -> Option<u32> {
-        self.as_ref()
-            .map(|t| (t - NaiveTime::default()).num_minutes() as u32)
+        (!self.is_null()).then_some(self.0 as u32)
     }
 
-    /// DolphinDB internal representation
-    pub(crate) fn ddb_rep(&self) -> i32 {
-        self.elapsed().unwrap() as i32
+    pub(crate) fn encode(time: NaiveTime) -> i32 {
+        (time - NaiveTime::default()).num_minutes() as i32
+    }
+
+    pub(crate) fn decode(raw: i32) -> Option<NaiveTime> {
+        let raw = u32::try_from(raw).ok()?;
+        NaiveTime::from_num_seconds_from_midnight_opt(raw.checked_mul(60)?, 0)
     }
 }
 
@@ -148,18 +148,21 @@ impl Second {
 
     /// Makes a new [`Second`] from elapsed seconds since 00:00:00.
     pub fn from_raw(elapsed: u32) -> Option<Self> {
-        NaiveTime::from_num_seconds_from_midnight_opt(elapsed, 0).map(Self::new)
+        let raw = i32::try_from(elapsed).ok()?;
+        Self::decode(raw).map(|_| Self(raw))
     }
 
     /// Counts of seconds since 00:00:00.
     pub fn elapsed(&self) -> Option<u32> {
-        self.as_ref()
-            .map(|t| (t - NaiveTime::default()).num_seconds() as u32)
+        (!self.is_null()).then_some(self.0 as u32)
+    }
+
+    pub(crate) fn encode(time: NaiveTime) -> i32 {
+        (time - NaiveTime::default()).num_seconds() as i32
     }
 
-    /// DolphinDB internal representation
-    pub(crate) fn ddb_rep(&self) -> i32 {
-        self.elapsed().unwrap() as i32
+    pub(crate) fn decode(raw: i32) -> Option<NaiveTime> {
+        NaiveTime::from_num_seconds_from_midnight_opt(u32::try_from(raw).ok()?, 0)
     }
 }
 
@@ -178,26 +181,26 @@ impl DateTime {
 
     /// Makes a new [`DateTime`] from elapsed seconds since 1st of January 1970 at 00:00:00.
     pub fn from_raw(elapsed: i32) -> Option<Self> {
-        chrono::DateTime::from_timestamp(elapsed as i64, 0)
-            .map(|dt| dt.naive_local())
-            .map(Self::new)
+        Self::decode(elapsed).map(|_| Self(elapsed))
     }
 
     /// Counts of seconds since 1st of January 1970 at 00:00:00.
     pub fn elapsed(&self) -> Option<i32> {
-        self.as_ref()
-            .map(|t| (t - NaiveDateTime::default()).num_seconds() as i32)
+        (!self.is_null()).then_some(self.0)
     }
 
-    /// DolphinDB internal representation
-    pub(crate) fn ddb_rep(&self) -> i32 {
-        self.elapsed().unwrap()
+    pub(crate) fn encode(datetime: NaiveDateTime) -> i32 {
+        (datetime - NaiveDateTime::default()).num_seconds() as i32
+    }
+
+    pub(crate) fn decode(raw: i32) -> Option<NaiveDateTime> {
+        chrono::DateTime::from_timestamp(raw as i64, 0).map(|dt| dt.naive_utc())
     }
 }
 
 impl Display for DateTime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.0 {
+        match self.into_inner() {
             None => write!(f, "null"),
             Some(s) => write!(f, "{}", s.format("%Y.%m.%dT%H:%M:%S")),
         }
@@ -219,26 +222,26 @@ impl Timestamp {
 
     /// Makes a new [`Timestamp`] from elapsed milliseconds since 1st of January 1970 at 00:00:00.
     pub fn from_raw(elapsed: i64) -> Option<Self> {
-        chrono::DateTime::from_timestamp_millis(elapsed)
-            .map(|dt| dt.naive_local())
-            .map(Self::new)
+        Self::decode(elapsed).map(|_| Self(elapsed))
     }
 
     /// Counts of milliseconds since 1st of January 1970 at 00:00:00.
     pub fn elapsed(&self) -> Option<i64> {
-        self.as_ref()
-            .map(|t| (t - NaiveDateTime::default()).num_milliseconds())
+        (!self.is_null()).then_some(self.0)
     }
 
-    /// DolphinDB internal representation
-    pub(crate) fn ddb_rep(&self) -> i64 {
-        self.elapsed().unwrap()
+    pub(crate) fn encode(datetime: NaiveDateTime) -> i64 {
+        (datetime - NaiveDateTime::default()).num_milliseconds()
+    }
+
+    pub(crate) fn decode(raw: i64) -> Option<NaiveDateTime> {
+        chrono::DateTime::from_timestamp_millis(raw).map(|dt| dt.naive_utc())
     }
 }
 
 impl Display for Timestamp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.0 {
+        match self.into_inner() {
             None => write!(f, "null"),
             Some(s) => write!(f, "{}", s.format("%Y.%m.%dT%H:%M:%S%.3f")),
         }
@@ -253,32 +256,30 @@ impl NanoTime {
 
     /// Makes a new [`NanoTime`] from elapsed nanoseconds since 00:00:00.
     pub fn from_raw(elapsed: u64) -> Option<Self> {
-        let carry = 1_000_000_000;
-        NaiveTime::from_num_seconds_from_midnight_opt(
-            (elapsed / carry) as u32,
-            (elapsed % carry) as u32,
-        )
-        .map(Self::new)
+        let raw = i64::try_from(elapsed).ok()?;
+        Self::decode(raw).map(|_| Self(raw))
     }
 
     /// Counts of nanoseconds since 00:00:00.
     pub fn elapsed(&self) -> Option<u64> {
-        self.as_ref().and_then(|t| {
-            (t - NaiveTime::default())
-                .num_nanoseconds()
-                .map(|nsecs| nsecs as u64)
-        })
+        (!self.is_null()).then_some(self.0 as u64)
     }
 
-    /// DolphinDB internal representation
-    pub(crate) fn ddb_rep(&self) -> i64 {
-        self.elapsed().unwrap() as i64
+    pub(crate) fn encode(time: NaiveTime) -> i64 {
+        // less than a day, never overflows
+        (time - NaiveTime::default()).num_nanoseconds().unwrap()
+    }
+
+    pub(crate) fn decode(raw: i64) -> Option<NaiveTime> {
+        let carry = 1_000_000_000;
+        let raw = u64::try_from(raw).ok()?;
+        NaiveTime::from_num_seconds_from_midnight_opt((raw / carry) as u32, (raw % carry) as u32)
     }
 }
 
 impl Display for NanoTime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.0 {
+        match self.into_inner() {
             None => write!(f, "null"),
             Some(s) => write!(f, "{}", s.format("%H:%M:%S%.9f")),
         }
@@ -300,26 +301,29 @@ impl NanoTimestamp {
 
     /// Makes a new [`NanoTime`] from elapsed nanoseconds since 1st of January 1970 at 00:00:00.
     pub fn from_raw(elapsed: i64) -> Option<Self> {
-        Some(Self::new(
-            chrono::DateTime::from_timestamp_nanos(elapsed).naive_local(),
-        ))
+        Some(Self(elapsed))
     }
 
     /// Counts of nanoseconds since 1st of January 1970 at 00:00:00.
     pub fn elapsed(&self) -> Option<i64> {
-        self.as_ref()
-            .and_then(|t| (t - NaiveDateTime::default()).num_nanoseconds())
+        (!self.is_null()).then_some(self.0)
     }
 
-    /// DolphinDB internal representation
-    pub(crate) fn ddb_rep(&self) -> i64 {
-        self.elapsed().unwrap()
+    /// Datetimes out of the nanosecond range become null.
+    pub(crate) fn encode(datetime: NaiveDateTime) -> i64 {
+        (datetime - NaiveDateTime::default())
+            .num_nanoseconds()
+            .unwrap_or(i64::MIN)
+    }
+
+    pub(crate) fn decode(raw: i64) -> Option<NaiveDateTime> {
+        Some(chrono::DateTime::from_timestamp_nanos(raw).naive_utc())
     }
 }
 
 impl Display for NanoTimestamp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.0 {
+        match self.into_inner() {
             None => write!(f, "null"),
             Some(s) => write!(f, "{}", s.format("%Y.%m.%dT%H:%M:%S%.9f")),
         }
@@ -340,28 +344,121 @@ impl DateHour {
 
     /// Makes a new [`DateHour`] from elapsed hours since 1st of January 1970 at 00:00:00.
     pub fn from_raw(elapsed: i64) -> Option<Self> {
-        chrono::DateTime::from_timestamp(elapsed * 60 * 60, 0)
-            .map(|dt| dt.naive_local())
-            .map(Self::new)
+        let raw = i32::try_from(elapsed).ok()?;
+        Self::decode(raw).map(|_| Self(raw))
     }
 
     /// Counts of hours since 1st of January 1970 at 00:00:00.
     pub fn elapsed(&self) -> Option<i64> {
-        self.as_ref()
-            .map(|t| (t - NaiveDateTime::default()).num_hours())
+        (!self.is_null()).then_some(self.0 as i64)
+    }
+
+    pub(crate) fn encode(datetime: NaiveDateTime) -> i32 {
+        (datetime - NaiveDateTime::default()).num_hours() as i32
     }
 
-    /// DolphinDB internal representation
-    pub(crate) fn ddb_rep(&self) -> i32 {
-        self.elapsed().unwrap() as i32
+    pub(crate) fn decode(raw: i32) -> Option<NaiveDateTime> {
+        chrono::DateTime::from_timestamp(raw as i64 * 60 * 60, 0).map(|dt| dt.naive_utc())
     }
 }
 
 impl Display for DateHour {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.0 {
+        match self.into_inner() {
             None => write!(f, "null"),
             Some(s) => write!(f, "{}", s.format("%Y.%m.%dT%H")),
         }
     }
 }
+
+macro_rules! zoned_impl {
+    ($($struct_name:ident), *) => {
+        $(
+            impl $struct_name {
+                /// Makes a new value from the wall clock time of `datetime` in `tz`.
+                pub fn from_zoned<Tz: TimeZone, Src: TimeZone>(
+                    datetime: &chrono::DateTime<Src>,
+                    tz: &Tz,
+                ) -> Self {
+                    Self::new(datetime.with_timezone(tz).naive_local())
+                }
+
+                /// Reads the value as a wall clock time in `tz`.
+                ///
+                /// Returns [`None`] if null or the time is skipped in `tz`, ambiguous times take
+                /// the earlier offset.
+                pub fn to_zoned<Tz: TimeZone>(&self, tz: &Tz) -> Option<chrono::DateTime<Tz>> {
+                    tz.from_local_datetime(&self.into_inner()?).earliest()
+                }
+            }
+        )*
+    };
+}
+
+zoned_impl!(DateTime, Timestamp, NanoTimestamp);
+
+fn parse_items(s: &str, fmt: &str) -> Option<Parsed> {
+    let mut parsed = Parsed::new();
+    chrono_format::parse(&mut parsed, s, StrftimeItems::new(fmt)).ok()?;
+    Some(parsed)
+}
+
+// Months may be formatted without days.
+fn parse_date(s: &str, fmt: &str) -> Option<NaiveDate> {
+    let mut parsed = parse_items(s, fmt)?;
+    if parsed.day().is_none() {
+        parsed.set_day(1).ok()?;
+    }
+    parsed.to_naive_date().ok()
+}
+
+fn parse_time(s: &str, fmt: &str) -> Option<NaiveTime> {
+    parse_items(s, fmt)?.to_naive_time().ok()
+}
+
+// Date hours may be formatted without minutes.
+fn parse_datetime(s: &str, fmt: &str) -> Option<NaiveDateTime> {
+    let mut parsed = parse_items(s, fmt)?;
+    if parsed.minute().is_none() {
+        parsed.set_minute(0).ok()?;
+    }
+    parsed.to_naive_datetime_with_offset(0).ok()
+}
+
+macro_rules! format_impl {
+    ($struct_name:ident, $parse:ident, $ddb_format:expr) => {
+        impl $struct_name {
+            /// [`chrono`] format of DolphinDB's display.
+            pub(crate) const DDB_FORMAT: &'static str = $ddb_format;
+
+            /// Formats with a [`chrono`] format string, [`None`] if null.
+            pub(crate) fn format_with(&self, fmt: &str) -> Option<String> {
+                self.into_inner().map(|x| x.format(fmt).to_string())
+            }
+
+            /// Parses with a [`chrono`] format string.
+            pub(crate) fn parse_with(s: &str, fmt: &str) -> Option<Self> {
+                $parse(s, fmt).map(Self::new)
+            }
+        }
+    };
+
+    ($(($struct_name:ident, $parse:ident, $ddb_format:expr)), *) => {
+        $(
+            format_impl!($struct_name, $parse, $ddb_format);
+        )*
+    };
+}
+
+format_impl!(
+    (Date, parse_date, "%Y.%m.%d"),
+    (Month, parse_date, "%Y.%mM"),
+    (Time, parse_time, "%H:%M:%S%.3f"),
+    (Minute, parse_time, "%H:%Mm"),
+    (Second, parse_time, "%H:%M:%S"),
+    (DateTime, parse_datetime, "%Y.%m.%dT%H:%M:%S"),
+    (Timestamp, parse_datetime, "%Y.%m.%dT%H:%M:%S%.3f"),
+    (NanoTime, parse_time, "%H:%M:%S%.9f"),
+    (NanoTimestamp, parse_datetime, "%Y.%m.%dT%H:%M:%S%.9f"),
+    (DateHour, parse_datetime, "%Y.%m.%dT%H")
+);
@@ -1,5 +1,6 @@
 use std::fmt::Display;
 use std::hash::Hash;
+use std::ops::Range;
 use std::{collections::HashSet, io::ErrorKind};
 
 use tokio::io::{AsyncBufReadExt, AsyncReadExt};
@@ -13,8 +14,8 @@ use crate::{
 };
 
 use super::{
-    deserialize_vector, deserialize_vector_le, Constant, DataForm, DataType, Dictionary,
-    DictionaryImpl, VectorImpl,
+    deserialize_vector, deserialize_vector_le, ArrayVectorImpl, Constant, ConstantImpl, DataForm,
+    DataType, Dictionary, DictionaryImpl, FieldOptions, RowField, ScalarImpl, VectorImpl,
 };
 
 #[derive(Debug, Clone, Default)]
@@ -162,8 +163,145 @@ impl Table {
 
         Ok(())
     }
+
+    /// Returns a view of the row at `index`, or None if out of bounds.
+    pub fn row(&self, index: usize) -> Option<Row<'_>> {
+        (index < self.len()).then_some(Row { table: self, index })
+    }
+
+    /// Returns an iterator over row views.
+    pub fn rows(&self) -> Rows<'_> {
+        Rows {
+            table: self,
+            range: 0..self.len(),
+        }
+    }
+
+    /// Returns the element at `row` of `column`, which is a column index or name.
+    ///
+    /// Return None if the cell doesn't exist or belongs to an array vector column.
+    pub fn cell(&self, row: usize, column: impl ColumnIndex) -> Option<ScalarImpl> {
+        self.row(row)?.scalar(column)
+    }
+}
+
+/// Column lookup by index or by name.
+pub trait ColumnIndex {
+    /// Position of the column in `table`, None if it doesn't exist.
+    fn column_index(&self, table: &Table) -> Option<usize>;
 }
 
+impl ColumnIndex for usize {
+    fn column_index(&self, table: &Table) -> Option<usize> {
+        (*self < table.columns.len()).then_some(*self)
+    }
+}
+
+impl ColumnIndex for &str {
+    fn column_index(&self, table: &Table) -> Option<usize> {
+        table.column_names.iter().position(|name| name == self)
+    }
+}
+
+impl ColumnIndex for String {
+    fn column_index(&self, table: &Table) -> Option<usize> {
+        self.as_str().column_index(table)
+    }
+}
+
+impl ColumnIndex for &String {
+    fn column_index(&self, table: &Table) -> Option<usize> {
+        self.as_str().column_index(table)
+    }
+}
+
+/// A row of [`Table`], borrowing the columns without copying them.
+#[derive(Debug, Clone, Copy)]
+pub struct Row<'a> {
+    table: &'a Table,
+    index: usize,
+}
+
+impl<'a> Row<'a> {
+    /// Position of the row in the table.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the number of elements in the row.
+    pub fn len(&self) -> usize {
+        self.table.columns.len()
+    }
+
+    /// Returns [`true`] if the table has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn column_names(&self) -> &'a Vec<String> {
+        &self.table.column_names
+    }
+
+    /// Converts the element of `column` to `T`.
+    ///
+    /// Use [`Option`] for nullable columns, null elements are errors otherwise.
+    pub fn get<T: RowField>(&self, column: impl ColumnIndex) -> Result<T> {
+        T::get_field(self.column(&column)?, self.index, &FieldOptions::default())
+    }
+
+    /// Returns the element of `column` as a scalar, None if the column doesn't exist or is
+    /// an array vector.
+    pub fn scalar(&self, column: impl ColumnIndex) -> Option<ScalarImpl> {
+        let column = column.column_index(self.table)?;
+        match self.table.columns[column].get(self.index)? {
+            ConstantImpl::Scalar(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn column(&self, column: &impl ColumnIndex) -> Result<&'a VectorImpl> {
+        column
+            .column_index(self.table)
+            .map(|i| &self.table.columns[i])
+            .ok_or(Error::ConstraintsViolated(
+                "column not found in table".into(),
+            ))
+    }
+}
+
+/// Iterator over rows of [`Table`], created by [`Table::rows`].
+#[derive(Debug, Clone)]
+pub struct Rows<'a> {
+    table: &'a Table,
+    range: Range<usize>,
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = Row<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|index| Row {
+            table: self.table,
+            index,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Rows<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|index| Row {
+            table: self.table,
+            index,
+        })
+    }
+}
+
+impl ExactSizeIterator for Rows<'_> {}
+
 fn no_duplicates<T>(elements: &[T]) -> bool
 where
     T: Hash + Eq + Clone,
@@ -194,39 +332,237 @@ impl Table {
     }
 }
 
-impl Display for Table {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut table = PrettyTable::new();
+/// Options of [`Table::display_with`] and [`VectorImpl::display_with`].
+#[derive(Debug, Clone)]
+pub struct DisplayOptions {
+    max_rows: Option<usize>,
+    max_columns: Option<usize>,
+    max_width: Option<usize>,
+    null: String,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            max_rows: Some(20),
+            max_columns: Some(20),
+            max_width: Some(40),
+            null: "null".into(),
+        }
+    }
+}
 
-        let name = self
-            .column_names
+impl DisplayOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of rows (or vector elements) shown, the rest is elided between the head
+    /// and the tail. None for no limit, defaults to 20.
+    pub fn with_max_rows(&mut self, max_rows: Option<usize>) -> &mut Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Maximum number of columns shown, the rest is elided between the head and the tail.
+    /// None for no limit, defaults to 20.
+    pub fn with_max_columns(&mut self, max_columns: Option<usize>) -> &mut Self {
+        self.max_columns = max_columns;
+        self
+    }
+
+    /// Maximum number of characters in a cell, longer cells end with `...` unless the width is
+    /// less than 3. None for no limit, defaults to 40.
+    pub fn with_max_width(&mut self, max_width: Option<usize>) -> &mut Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Text shown for null elements, defaults to `null`.
+    pub fn with_null(&mut self, null: impl Into<String>) -> &mut Self {
+        self.null = null.into();
+        self
+    }
+
+    fn truncate(&self, s: String) -> String {
+        match self.max_width {
+            // too narrow for the ellipsis, the cell is only cut
+            Some(width) if width < ELLIPSIS.len() => s.chars().take(width).collect(),
+            Some(width) if s.chars().count() > width => {
+                let mut s = s.chars().take(width - ELLIPSIS.len()).collect::<String>();
+                s.push_str(ELLIPSIS);
+                s
+            }
+            _ => s,
+        }
+    }
+}
+
+const ELLIPSIS: &str = "...";
+
+/// Positions kept under `limit`, None marks the elided part between head and tail.
+fn head_tail(len: usize, limit: Option<usize>) -> Vec<Option<usize>> {
+    match limit {
+        Some(limit) if len > limit => {
+            let head = limit.div_ceil(2);
+            let tail = limit / 2;
+            (0..head)
+                .map(Some)
+                .chain(std::iter::once(None))
+                .chain((len - tail..len).map(Some))
+                .collect()
+        }
+        _ => (0..len).map(Some).collect(),
+    }
+}
+
+macro_rules! format_array_element {
+    ($v:expr, $index:expr, $raw_type:ty, $options:expr) => {{
+        let elements = $v[$index]
             .iter()
-            .map(|s| Cell::new(s.as_str()))
+            .map(|x| {
+                if *x == <$raw_type>::MIN {
+                    $options.null.clone()
+                } else {
+                    x.to_string()
+                }
+            })
             .collect::<Vec<_>>();
+        format!("[{}]", elements.join(", "))
+    }};
+}
+
+fn format_element(column: &VectorImpl, index: usize, options: &DisplayOptions) -> String {
+    match column {
+        VectorImpl::ArrayVector(v) => match v {
+            ArrayVectorImpl::Char(v) => format_array_element!(v, index, i8, options),
+            ArrayVectorImpl::Short(v) => format_array_element!(v, index, i16, options),
+            ArrayVectorImpl::Int(v) => format_array_element!(v, index, i32, options),
+            ArrayVectorImpl::Long(v) => format_array_element!(v, index, i64, options),
+            ArrayVectorImpl::Float(v) => format_array_element!(v, index, f32, options),
+            ArrayVectorImpl::Double(v) => format_array_element!(v, index, f64, options),
+        },
+        _ => column
+            .get(index)
+            .map(|c| format_constant(&c, options))
+            .unwrap_or_default(),
+    }
+}
+
+fn format_constant(constant: &ConstantImpl, options: &DisplayOptions) -> String {
+    match constant {
+        ConstantImpl::Scalar(s) if s.is_null() => options.null.clone(),
+        ConstantImpl::Vector(v) => format_vector(v, options),
+        ConstantImpl::Table(t) => format!("table({} rows x {} columns)", t.len(), t.columns.len()),
+        _ => constant.to_string(),
+    }
+}
+
+fn format_vector(vector: &VectorImpl, options: &DisplayOptions) -> String {
+    let elements = head_tail(vector.len(), options.max_rows)
+        .into_iter()
+        .map(|i| match i {
+            Some(i) => format_element(vector, i, options),
+            None => ELLIPSIS.to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", elements.join(", "))
+}
+
+impl Table {
+    /// Renders the table cell by cell with `options`.
+    pub fn display_with<'a>(&'a self, options: &'a DisplayOptions) -> impl Display + 'a {
+        TableDisplay {
+            table: self,
+            options,
+        }
+    }
+}
+
+impl VectorImpl {
+    /// Renders the vector element by element with `options`, `max_rows` limits the elements.
+    pub fn display_with<'a>(&'a self, options: &'a DisplayOptions) -> impl Display + 'a {
+        VectorDisplay {
+            vector: self,
+            options,
+        }
+    }
+}
+
+struct TableDisplay<'a> {
+    table: &'a Table,
+    options: &'a DisplayOptions,
+}
+
+impl Display for TableDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { table, options } = self;
+        let rows = head_tail(table.len(), options.max_rows);
+        let columns = head_tail(table.columns.len(), options.max_columns);
 
-        table.add_row(name.into());
+        let mut pretty = PrettyTable::new();
 
-        let columns = self
-            .columns
+        let names = columns
             .iter()
-            .map(|v| v.to_string())
-            .map(|s| {
-                s[1..s.len() - 1]
-                    .split(", ")
-                    .map(|s| s.to_string())
-                    .collect::<Vec<_>>()
+            .map(|j| match j {
+                Some(j) => Cell::new(&options.truncate(table.column_names[*j].clone())),
+                None => Cell::new(ELLIPSIS),
             })
             .collect::<Vec<_>>();
-
-        for j in 0..self.len() {
-            let row = (0..self.columns.len())
-                .map(|i| Cell::new(columns[i][j].as_str()))
+        pretty.add_row(names.into());
+
+        for i in rows.iter() {
+            let row = columns
+                .iter()
+                .map(|j| match (i, j) {
+                    (Some(i), Some(j)) => Cell::new(&options.truncate(format_element(
+                        &table.columns[*j],
+                        *i,
+                        options,
+                    ))),
+                    _ => Cell::new(ELLIPSIS),
+                })
                 .collect::<Vec<_>>();
+            pretty.add_row(row.into());
+        }
+
+        pretty.fmt(f)?;
 
-            table.add_row(row.into());
+        if rows.contains(&None) || columns.contains(&None) {
+            writeln!(
+                f,
+                "[{} rows x {} columns]",
+                table.len(),
+                table.columns.len()
+            )?;
         }
 
-        table.fmt(f)
+        Ok(())
+    }
+}
+
+struct VectorDisplay<'a> {
+    vector: &'a VectorImpl,
+    options: &'a DisplayOptions,
+}
+
+impl Display for VectorDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { vector, options } = self;
+        let elements = head_tail(vector.len(), options.max_rows)
+            .into_iter()
+            .map(|i| match i {
+                Some(i) => options.truncate(format_element(vector, i, options)),
+                None => ELLIPSIS.to_string(),
+            })
+            .collect::<Vec<_>>();
+        write!(f, "[{}]", elements.join(", "))
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.display_with(&DisplayOptions::default()).fmt(f)
     }
 }
 
@@ -0,0 +1,557 @@
+//! [Apache Arrow](arrow) interoperability.
+//!
+//! | DolphinDB               | Arrow                                 |
+//! |-------------------------|---------------------------------------|
+//! | `VOID`                  | `Null`                                |
+//! | `BOOL`                  | `Boolean`                             |
+//! | `CHAR` ~ `LONG`         | `Int8` ~ `Int64`                      |
+//! | `FLOAT`, `DOUBLE`       | `Float32`, `Float64`                  |
+//! | `SYMBOL`                | `Dictionary(Int32, Utf8)`             |
+//! | `STRING`, `BLOB`        | `Utf8`, `Binary`                      |
+//! | `DATE`, `MONTH`         | `Date32`                              |
+//! | `TIME`                  | `Time32(Millisecond)`                 |
+//! | `MINUTE`, `SECOND`      | `Time32(Second)`                      |
+//! | `NANOTIME`              | `Time64(Nanosecond)`                  |
+//! | `DATETIME`, `DATEHOUR`  | `Timestamp(Second)`                   |
+//! | `TIMESTAMP`             | `Timestamp(Millisecond)`              |
+//! | `NANOTIMESTAMP`         | `Timestamp(Nanosecond)`               |
+//! | `DECIMAL32` ~ `128`     | `Decimal128(9 / 18 / 38, scale)`      |
+//! | array vectors           | `List`                                |
+//!
+//! Null values are mapped to validity bitmaps. Types sharing an Arrow type are told apart by
+//! the `dolphindb:type` field metadata when converting [`RecordBatch`]es.
+//!
+//! Converting from Arrow also accepts unsigned integers, large and view strings, `Date64`,
+//! `Time64(Microsecond)` and `Timestamp(Microsecond)`, which are widened to the types above.
+
+use std::{collections::HashMap, sync::Arc};
+
+use arrow::{
+    array::{
+        Array, ArrayRef, AsArray, BinaryArray, BooleanArray, Date32Array, Decimal128Array,
+        DictionaryArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+        ListArray, NullArray, StringArray, Time32MillisecondArray, Time32SecondArray,
+        Time64NanosecondArray, TimestampMillisecondArray, TimestampNanosecondArray,
+        TimestampSecondArray,
+    },
+    compute::cast,
+    datatypes::{
+        DataType as ArrowDataType, Date32Type, Decimal128Type, Field, Float32Type, Float64Type,
+        Int16Type, Int32Type, Int64Type, Int8Type, Schema, Time32MillisecondType, Time32SecondType,
+        Time64NanosecondType, TimeUnit, TimestampMillisecondType, TimestampNanosecondType,
+        TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+    },
+    record_batch::{RecordBatch, RecordBatchOptions},
+};
+use chrono::{Datelike, NaiveDate};
+
+use crate::error::{Error, Result};
+
+use super::*;
+
+/// Field metadata key holding the DolphinDB type of a column.
+pub const ARROW_TYPE_KEY: &str = "dolphindb:type";
+
+/// Schema metadata key holding the DolphinDB table name.
+pub const ARROW_TABLE_NAME_KEY: &str = "dolphindb:table";
+
+fn unsupported(data_type: impl ToString) -> Error {
+    Error::Unsupported {
+        data_form: DataForm::Vector.to_string(),
+        data_type: data_type.to_string(),
+    }
+}
+
+fn out_of_range(value: impl ToString, data_type: DataType) -> Error {
+    Error::InvalidConvert {
+        from: value.to_string(),
+        to: data_type.to_string(),
+    }
+}
+
+fn decimal_array<S>(v: &Vector<S>, precision: u8) -> Result<ArrayRef>
+where
+    S: DecimalInterface + AsRef<Option<rust_decimal::Decimal>>,
+{
+    let scale = v.max_scale();
+    let array = v
+        .iter()
+        .map(|d| {
+            d.as_ref().map(|mut d| {
+                d.rescale(scale);
+                d.mantissa()
+            })
+        })
+        .collect::<Decimal128Array>()
+        .with_precision_and_scale(precision, scale as i8)?;
+
+    Ok(Arc::new(array))
+}
+
+macro_rules! list_array {
+    ($v:expr, $arrow_type:ty, $raw_type:ty) => {
+        Arc::new(ListArray::from_iter_primitive::<$arrow_type, _, _>(
+            (0..$v.len()).map(|i| {
+                Some(
+                    $v[i]
+                        .iter()
+                        .map(|x| (*x != <$raw_type>::MIN).then_some(*x))
+                        .collect::<Vec<_>>(),
+                )
+            }),
+        ))
+    };
+}
+
+impl TryFrom<&VectorImpl> for ArrayRef {
+    type Error = Error;
+
+    fn try_from(value: &VectorImpl) -> Result<Self> {
+        let array: ArrayRef = match value {
+            VectorImpl::Void(v) => Arc::new(NullArray::new(v.len())),
+            VectorImpl::Bool(v) => {
+                Arc::new(v.iter().map(|x| x.into_inner()).collect::<BooleanArray>())
+            }
+            VectorImpl::Char(v) => {
+                Arc::new(v.iter().map(|x| x.into_inner()).collect::<Int8Array>())
+            }
+            VectorImpl::Short(v) => {
+                Arc::new(v.iter().map(|x| x.into_inner()).collect::<Int16Array>())
+            }
+            VectorImpl::Int(v) => {
+                Arc::new(v.iter().map(|x| x.into_inner()).collect::<Int32Array>())
+            }
+            VectorImpl::Long(v) => {
+                Arc::new(v.iter().map(|x| x.into_inner()).collect::<Int64Array>())
+            }
+            VectorImpl::Float(v) => {
+                Arc::new(v.iter().map(|x| x.into_inner()).collect::<Float32Array>())
+            }
+            VectorImpl::Double(v) => {
+                Arc::new(v.iter().map(|x| x.into_inner()).collect::<Float64Array>())
+            }
+            VectorImpl::Symbol(v) => Arc::new(
+                v.iter()
+                    .map(|x| x.0.as_deref())
+                    .collect::<DictionaryArray<Int32Type>>(),
+            ),
+            VectorImpl::String(v) => {
+                Arc::new(v.iter().map(|x| x.0.as_deref()).collect::<StringArray>())
+            }
+            VectorImpl::Blob(v) => {
+                Arc::new(v.iter().map(|x| x.0.as_deref()).collect::<BinaryArray>())
+            }
+            VectorImpl::Date(v) => Arc::new(
+                v.iter()
+                    .map(|x| x.elapsed().map(|e| e as i32))
+                    .collect::<Date32Array>(),
+            ),
+            VectorImpl::Month(v) => Arc::new(
+                v.iter()
+                    .map(|x| {
+                        x.into_inner()
+                            .map(|d| (d - NaiveDate::default()).num_days() as i32)
+                    })
+                    .collect::<Date32Array>(),
+            ),
+            VectorImpl::Time(v) => Arc::new(
+                v.iter()
+                    .map(|x| x.elapsed().map(|e| e as i32))
+                    .collect::<Time32MillisecondArray>(),
+            ),
+            VectorImpl::Minute(v) => Arc::new(
+                v.iter()
+                    .map(|x| x.elapsed().map(|e| e as i32 * 60))
+                    .collect::<Time32SecondArray>(),
+            ),
+            VectorImpl::Second(v) => Arc::new(
+                v.iter()
+                    .map(|x| x.elapsed().map(|e| e as i32))
+                    .collect::<Time32SecondArray>(),
+            ),
+            VectorImpl::NanoTime(v) => Arc::new(
+                v.iter()
+                    .map(|x| x.elapsed().map(|e| e as i64))
+                    .collect::<Time64NanosecondArray>(),
+            ),
+            VectorImpl::DateTime(v) => Arc::new(
+                v.iter()
+                    .map(|x| x.elapsed().map(|e| e as i64))
+                    .collect::<TimestampSecondArray>(),
+            ),
+            VectorImpl::DateHour(v) => Arc::new(
+                v.iter()
+                    .map(|x| x.elapsed().map(|e| e * 3600))
+                    .collect::<TimestampSecondArray>(),
+            ),
+            VectorImpl::Timestamp(v) => Arc::new(
+                v.iter()
+                    .map(|x| x.elapsed())
+                    .collect::<TimestampMillisecondArray>(),
+            ),
+            VectorImpl::NanoTimestamp(v) => Arc::new(
+                v.iter()
+                    .map(|x| x.elapsed())
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            VectorImpl::Decimal32(v) => decimal_array(v, 9)?,
+            VectorImpl::Decimal64(v) => decimal_array(v, 18)?,
+            VectorImpl::Decimal128(v) => decimal_array(v, 38)?,
+            VectorImpl::ArrayVector(v) => match v {
+                ArrayVectorImpl::Char(v) => list_array!(v, Int8Type, i8),
+                ArrayVectorImpl::Short(v) => list_array!(v, Int16Type, i16),
+                ArrayVectorImpl::Int(v) => list_array!(v, Int32Type, i32),
+                ArrayVectorImpl::Long(v) => list_array!(v, Int64Type, i64),
+                ArrayVectorImpl::Float(v) => list_array!(v, Float32Type, f32),
+                ArrayVectorImpl::Double(v) => list_array!(v, Float64Type, f64),
+            },
+            VectorImpl::Any(_) => return Err(unsupported(value.data_type())),
+        };
+
+        Ok(array)
+    }
+}
+
+// Builds a temporal vector from raw elapsed values, failing on values out of range.
+macro_rules! temporal_vector {
+    ($iter:expr, $struct_name:ident, $convert:expr) => {
+        $iter
+            .map(|x| match x {
+                Some(x) => $convert(x).ok_or(out_of_range(x, $struct_name::data_type())),
+                None => Ok($struct_name::default()),
+            })
+            .collect::<Result<Vector<$struct_name>>>()?
+            .into()
+    };
+}
+
+macro_rules! decimal_vector {
+    ($array:expr, $struct_name:ident, $raw_type:ty, $scale:expr) => {
+        $array
+            .iter()
+            .map(|x| match x {
+                Some(x) => <$raw_type>::try_from(x)
+                    .ok()
+                    .and_then(|m| $struct_name::from_raw(m, $scale))
+                    .ok_or(out_of_range(x, $struct_name::data_type())),
+                None => Ok($struct_name::default()),
+            })
+            .collect::<Result<Vector<$struct_name>>>()?
+            .into()
+    };
+}
+
+macro_rules! array_vector {
+    ($list:expr, $arrow_type:ty, $raw_type:ty) => {{
+        let mut v = ArrayVector::<$raw_type>::new();
+        for row in $list.iter() {
+            v.push(match row {
+                Some(row) => row
+                    .as_primitive::<$arrow_type>()
+                    .iter()
+                    .map(|x| x.unwrap_or(<$raw_type>::MIN))
+                    .collect(),
+                None => vec![],
+            });
+        }
+        v.into()
+    }};
+}
+
+fn list_vector<O>(array: &dyn Array, child: &ArrowDataType) -> Result<VectorImpl>
+where
+    O: arrow::array::OffsetSizeTrait,
+{
+    let list = array.as_list::<O>();
+    let v: VectorImpl = match child {
+        ArrowDataType::Int8 => array_vector!(list, Int8Type, i8),
+        ArrowDataType::Int16 => array_vector!(list, Int16Type, i16),
+        ArrowDataType::Int32 => array_vector!(list, Int32Type, i32),
+        ArrowDataType::Int64 => array_vector!(list, Int64Type, i64),
+        ArrowDataType::Float32 => array_vector!(list, Float32Type, f32),
+        ArrowDataType::Float64 => array_vector!(list, Float64Type, f64),
+        _ => return Err(unsupported(array.data_type())),
+    };
+
+    Ok(v)
+}
+
+/// Converts an Arrow array to a [`VectorImpl`].
+///
+/// `hint` selects the DolphinDB type among those sharing one Arrow type, e.g. `MONTH` for
+/// `Date32` arrays. It is ignored if it does not apply.
+pub fn vector_from_arrow(array: &dyn Array, hint: Option<DataType>) -> Result<VectorImpl> {
+    let v: VectorImpl = match array.data_type() {
+        ArrowDataType::Null => (0..array.len())
+            .map(|_| Void::default())
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::Boolean => array
+            .as_boolean()
+            .iter()
+            .map(Bool::from)
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::Int8 => array
+            .as_primitive::<Int8Type>()
+            .iter()
+            .map(Char::from)
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::Int16 => array
+            .as_primitive::<Int16Type>()
+            .iter()
+            .map(Short::from)
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::Int32 => array
+            .as_primitive::<Int32Type>()
+            .iter()
+            .map(Int::from)
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::Int64 => array
+            .as_primitive::<Int64Type>()
+            .iter()
+            .map(Long::from)
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::UInt8 => array
+            .as_primitive::<UInt8Type>()
+            .iter()
+            .map(|x| Short::from(x.map(i16::from)))
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::UInt16 => array
+            .as_primitive::<UInt16Type>()
+            .iter()
+            .map(|x| Int::from(x.map(i32::from)))
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::UInt32 => array
+            .as_primitive::<UInt32Type>()
+            .iter()
+            .map(|x| Long::from(x.map(i64::from)))
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::UInt64 => array
+            .as_primitive::<UInt64Type>()
+            .iter()
+            .map(|x| match x {
+                Some(x) => i64::try_from(x)
+                    .map(Long::new)
+                    .map_err(|_| out_of_range(x, DataType::Long)),
+                None => Ok(Long::default()),
+            })
+            .collect::<Result<Vector<_>>>()?
+            .into(),
+        ArrowDataType::Float32 => array
+            .as_primitive::<Float32Type>()
+            .iter()
+            .map(Float::from)
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::Float64 => array
+            .as_primitive::<Float64Type>()
+            .iter()
+            .map(Double::from)
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::Utf8 => {
+            let strings = array
+                .as_string::<i32>()
+                .iter()
+                .map(|x| x.map(str::to_string));
+            if hint == Some(DataType::Symbol) {
+                strings.map(Symbol::from).collect::<Vector<_>>().into()
+            } else {
+                strings
+                    .map(DolphinString::from)
+                    .collect::<Vector<_>>()
+                    .into()
+            }
+        }
+        ArrowDataType::LargeUtf8 | ArrowDataType::Utf8View => {
+            return vector_from_arrow(cast(array, &ArrowDataType::Utf8)?.as_ref(), hint)
+        }
+        ArrowDataType::Dictionary(_, values)
+            if matches!(
+                values.as_ref(),
+                ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 | ArrowDataType::Utf8View
+            ) =>
+        {
+            return vector_from_arrow(
+                cast(array, &ArrowDataType::Utf8)?.as_ref(),
+                Some(DataType::Symbol),
+            )
+        }
+        ArrowDataType::Binary => array
+            .as_binary::<i32>()
+            .iter()
+            .map(|x| Blob::from(x.map(<[u8]>::to_vec)))
+            .collect::<Vector<_>>()
+            .into(),
+        ArrowDataType::LargeBinary | ArrowDataType::BinaryView => {
+            return vector_from_arrow(cast(array, &ArrowDataType::Binary)?.as_ref(), hint)
+        }
+        ArrowDataType::Date32 => {
+            let days = array.as_primitive::<Date32Type>().iter();
+            if hint == Some(DataType::Month) {
+                temporal_vector!(days, Month, |x: i32| Date::from_raw(x as i64)
+                    .and_then(|d| d.into_inner())
+                    .and_then(|d| Month::from_ym(d.year(), d.month())))
+            } else {
+                temporal_vector!(days, Date, |x: i32| Date::from_raw(x as i64))
+            }
+        }
+        ArrowDataType::Date64 => {
+            return vector_from_arrow(cast(array, &ArrowDataType::Date32)?.as_ref(), hint)
+        }
+        ArrowDataType::Time32(TimeUnit::Second) => {
+            let seconds = array.as_primitive::<Time32SecondType>().iter();
+            if hint == Some(DataType::Minute) {
+                temporal_vector!(seconds, Minute, |x: i32| u32::try_from(x / 60)
+                    .ok()
+                    .and_then(Minute::from_raw))
+            } else {
+                temporal_vector!(seconds, Second, |x: i32| u32::try_from(x)
+                    .ok()
+                    .and_then(Second::from_raw))
+            }
+        }
+        ArrowDataType::Time32(TimeUnit::Millisecond) => temporal_vector!(
+            array.as_primitive::<Time32MillisecondType>().iter(),
+            Time,
+            |x: i32| u32::try_from(x).ok().and_then(Time::from_raw)
+        ),
+        ArrowDataType::Time64(TimeUnit::Nanosecond) => temporal_vector!(
+            array.as_primitive::<Time64NanosecondType>().iter(),
+            NanoTime,
+            |x: i64| u64::try_from(x).ok().and_then(NanoTime::from_raw)
+        ),
+        ArrowDataType::Time64(TimeUnit::Microsecond) => {
+            return vector_from_arrow(
+                cast(array, &ArrowDataType::Time64(TimeUnit::Nanosecond))?.as_ref(),
+                hint,
+            )
+        }
+        ArrowDataType::Timestamp(TimeUnit::Second, _) => {
+            let seconds = array.as_primitive::<TimestampSecondType>().iter();
+            if hint == Some(DataType::DateHour) {
+                temporal_vector!(seconds, DateHour, |x: i64| DateHour::from_raw(
+                    x.div_euclid(3600)
+                ))
+            } else {
+                temporal_vector!(seconds, DateTime, |x: i64| i32::try_from(x)
+                    .ok()
+                    .and_then(DateTime::from_raw))
+            }
+        }
+        ArrowDataType::Timestamp(TimeUnit::Millisecond, _) => temporal_vector!(
+            array.as_primitive::<TimestampMillisecondType>().iter(),
+            Timestamp,
+            Timestamp::from_raw
+        ),
+        ArrowDataType::Timestamp(TimeUnit::Nanosecond, _) => temporal_vector!(
+            array.as_primitive::<TimestampNanosecondType>().iter(),
+            NanoTimestamp,
+            NanoTimestamp::from_raw
+        ),
+        ArrowDataType::Timestamp(TimeUnit::Microsecond, tz) => {
+            return vector_from_arrow(
+                cast(
+                    array,
+                    &ArrowDataType::Timestamp(TimeUnit::Nanosecond, tz.clone()),
+                )?
+                .as_ref(),
+                hint,
+            )
+        }
+        ArrowDataType::Decimal128(precision, scale) => {
+            let scale = u32::try_from(*scale).map_err(|_| unsupported(array.data_type()))?;
+            let decimals = array.as_primitive::<Decimal128Type>();
+            match precision {
+                0..=9 => decimal_vector!(decimals, Decimal32, i32, scale),
+                10..=18 => decimal_vector!(decimals, Decimal64, i64, scale),
+                _ => decimal_vector!(decimals, Decimal128, i128, scale),
+            }
+        }
+        ArrowDataType::List(field) => return list_vector::<i32>(array, field.data_type()),
+        ArrowDataType::LargeList(field) => return list_vector::<i64>(array, field.data_type()),
+        t => return Err(unsupported(t)),
+    };
+
+    Ok(v)
+}
+
+impl TryFrom<&dyn Array> for VectorImpl {
+    type Error = Error;
+
+    fn try_from(value: &dyn Array) -> Result<Self> {
+        vector_from_arrow(value, None)
+    }
+}
+
+impl TryFrom<&Table> for RecordBatch {
+    type Error = Error;
+
+    fn try_from(value: &Table) -> Result<Self> {
+        let mut fields = Vec::with_capacity(value.columns().len());
+        let mut arrays = Vec::with_capacity(value.columns().len());
+
+        for (name, column) in value.column_names().iter().zip(value.columns()) {
+            let array = ArrayRef::try_from(column)?;
+            let metadata =
+                HashMap::from([(ARROW_TYPE_KEY.to_string(), column.data_type().to_string())]);
+            fields.push(Field::new(name, array.data_type().clone(), true).with_metadata(metadata));
+            arrays.push(array);
+        }
+
+        let metadata = HashMap::from([(ARROW_TABLE_NAME_KEY.to_string(), value.name().clone())]);
+        let schema = Schema::new(fields).with_metadata(metadata);
+        let options = RecordBatchOptions::new().with_row_count(Some(value.len()));
+
+        Ok(RecordBatch::try_new_with_options(
+            Arc::new(schema),
+            arrays,
+            &options,
+        )?)
+    }
+}
+
+impl TryFrom<&RecordBatch> for Table {
+    type Error = Error;
+
+    fn try_from(value: &RecordBatch) -> Result<Self> {
+        let schema = value.schema();
+        let mut columns = Vec::with_capacity(value.num_columns());
+        let mut column_names = Vec::with_capacity(value.num_columns());
+
+        for (field, array) in schema.fields().iter().zip(value.columns()) {
+            let hint = field
+                .metadata()
+                .get(ARROW_TYPE_KEY)
+                .and_then(|t| data_type_from_name(t));
+            columns.push(vector_from_arrow(array.as_ref(), hint)?);
+            column_names.push(field.name().clone());
+        }
+
+        let mut builder = TableBuilder::new();
+        if let Some(name) = schema.metadata().get(ARROW_TABLE_NAME_KEY) {
+            builder.with_name(name.clone());
+        }
+        builder.with_contents(columns, column_names);
+        builder.build()
+    }
+}
+
+fn data_type_from_name(name: &str) -> Option<DataType> {
+    [
+        DataType::Symbol,
+        DataType::Month,
+        DataType::Minute,
+        DataType::DateHour,
+    ]
+    .into_iter()
+    .find(|t| t.to_string() == name)
+}
@@ -0,0 +1,593 @@
+//! CSV import and export of [`Table`]s.
+//!
+//! Cells are written the way DolphinDB displays them, e.g. `2024.03.15` for `DATE` and
+//! `09:30m` for `MINUTE`. Temporal formats are [`chrono`] format strings and can be changed
+//! with [`CsvOptions::with_temporal_format`]. Elements of array vector cells are joined by
+//! [`CsvOptions::with_array_delimiter`].
+
+use std::{
+    borrow::Cow,
+    io::{BufWriter, Read, Write},
+    str::FromStr,
+};
+
+use csv::ReaderBuilder;
+use rust_decimal::Decimal;
+
+use crate::error::{Error, Result};
+
+use super::*;
+
+/// Options of [`Table::to_csv`] and [`Table::from_csv`].
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    delimiter: u8,
+    array_delimiter: char,
+    header: bool,
+    null: String,
+    temporal_formats: Vec<(DataType, String)>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        let temporal_formats = [
+            (DataType::Date, Date::DDB_FORMAT),
+            (DataType::Month, Month::DDB_FORMAT),
+            (DataType::Time, Time::DDB_FORMAT),
+            (DataType::Minute, Minute::DDB_FORMAT),
+            (DataType::Second, Second::DDB_FORMAT),
+            (DataType::DateTime, DateTime::DDB_FORMAT),
+            (DataType::Timestamp, Timestamp::DDB_FORMAT),
+            (DataType::NanoTime, NanoTime::DDB_FORMAT),
+            (DataType::NanoTimestamp, NanoTimestamp::DDB_FORMAT),
+            (DataType::DateHour, DateHour::DDB_FORMAT),
+        ];
+
+        Self {
+            delimiter: b',',
+            array_delimiter: ',',
+            header: true,
+            null: String::new(),
+            temporal_formats: temporal_formats
+                .into_iter()
+                .map(|(t, f)| (t, f.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl CsvOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Field delimiter, `,` by default.
+    pub fn with_delimiter(&mut self, delimiter: u8) -> &mut Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Delimiter between elements of array vector cells, `,` by default.
+    pub fn with_array_delimiter(&mut self, array_delimiter: char) -> &mut Self {
+        self.array_delimiter = array_delimiter;
+        self
+    }
+
+    /// Whether the first record holds column names, [`true`] by default.
+    ///
+    /// Columns read without a header are named `col0`, `col1` and so on.
+    pub fn with_header(&mut self, header: bool) -> &mut Self {
+        self.header = header;
+        self
+    }
+
+    /// Representation of null cells, empty by default.
+    ///
+    /// Unquoted cells equal to it are read as null, quoted ones as text, so `""` is an empty
+    /// string by default. Empty cells are also null in columns other than `STRING`, `SYMBOL`
+    /// and `BLOB`.
+    pub fn with_null(&mut self, null: impl Into<String>) -> &mut Self {
+        self.null = null.into();
+        self
+    }
+
+    /// Sets the [`chrono`] format of a temporal type.
+    ///
+    /// Months may be formatted without days and date hours without minutes.
+    pub fn with_temporal_format(
+        &mut self,
+        data_type: DataType,
+        format: impl Into<String>,
+    ) -> &mut Self {
+        let format = format.into();
+        match self
+            .temporal_formats
+            .iter_mut()
+            .find(|(t, _)| *t == data_type)
+        {
+            Some((_, f)) => *f = format,
+            None => self.temporal_formats.push((data_type, format)),
+        }
+        self
+    }
+
+    fn temporal_format(&self, data_type: DataType) -> &str {
+        self.temporal_formats
+            .iter()
+            .find(|(t, _)| *t == data_type)
+            .map(|(_, f)| f.as_str())
+            .unwrap_or_default()
+    }
+
+    fn is_null(&self, cell: &str) -> bool {
+        cell.is_empty() || cell == self.null
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CsvColumn {
+    name: String,
+    data_type: DataType,
+    scale: Option<u32>,
+}
+
+/// Column types of [`Table::from_csv`], columns not listed are inferred.
+#[derive(Debug, Clone, Default)]
+pub struct CsvSchema {
+    columns: Vec<CsvColumn>,
+}
+
+impl CsvSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the type of column `name`.
+    ///
+    /// Decimals keep the largest scale found in the column.
+    pub fn with_column(&mut self, name: impl Into<String>, data_type: DataType) -> &mut Self {
+        self.push(name.into(), data_type, None)
+    }
+
+    /// Sets column `name` to a decimal type rescaled to `scale`.
+    pub fn with_decimal(
+        &mut self,
+        name: impl Into<String>,
+        data_type: DataType,
+        scale: u32,
+    ) -> &mut Self {
+        self.push(name.into(), data_type, Some(scale))
+    }
+
+    fn push(&mut self, name: String, data_type: DataType, scale: Option<u32>) -> &mut Self {
+        self.columns.retain(|c| c.name != name);
+        self.columns.push(CsvColumn {
+            name,
+            data_type,
+            scale,
+        });
+        self
+    }
+}
+
+fn invalid_cell(cell: &str, data_type: DataType) -> Error {
+    Error::InvalidConvert {
+        from: cell.to_string(),
+        to: data_type.to_string(),
+    }
+}
+
+fn unsupported(data_type: DataType) -> Error {
+    Error::Unsupported {
+        data_form: "csv".into(),
+        data_type: data_type.to_string(),
+    }
+}
+
+fn parse_bool(cell: &str) -> Option<bool> {
+    match cell {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_decimal(cell: &str, scale: Option<u32>) -> Option<Decimal> {
+    let mut d = Decimal::from_str(cell)
+        .or_else(|_| Decimal::from_scientific(cell))
+        .ok()?;
+    if let Some(scale) = scale {
+        d.rescale(scale);
+    }
+    Some(d)
+}
+
+// Parses non-null cells with `$parse`, which returns an `Option<$struct_name>`.
+macro_rules! parse_vector {
+    ($cells:expr, $struct_name:ident, $parse:expr) => {
+        $cells
+            .iter()
+            .map(|cell| match cell {
+                Some(s) => $parse(*s).ok_or_else(|| invalid_cell(s, $struct_name::data_type())),
+                None => Ok($struct_name::default()),
+            })
+            .collect::<Result<Vector<$struct_name>>>()?
+    };
+}
+
+macro_rules! parse_decimal_vector {
+    ($cells:expr, $struct_name:ident, $raw_type:ty, $scale:expr) => {{
+        let mut v = parse_vector!($cells, $struct_name, |s| parse_decimal(s, $scale).and_then(
+            |d| <$raw_type>::try_from(d.mantissa())
+                .ok()
+                .and_then(|m| $struct_name::from_raw(m, d.scale()))
+        ));
+        v.scale_to_same();
+        v.into()
+    }};
+}
+
+macro_rules! parse_array_vector {
+    ($cells:expr, $raw_type:ty, $data_type:expr, $options:expr) => {{
+        let mut v = ArrayVector::<$raw_type>::new();
+        for cell in $cells {
+            let row = match cell {
+                Some(s) => s
+                    .split($options.array_delimiter)
+                    .map(|x| {
+                        let x = x.trim();
+                        if $options.is_null(x) {
+                            Ok(<$raw_type>::MIN)
+                        } else {
+                            x.parse::<$raw_type>()
+                                .map_err(|_| invalid_cell(x, $data_type))
+                        }
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+                None => vec![],
+            };
+            v.push(row);
+        }
+        v.into()
+    }};
+}
+
+fn parse_column(
+    cells: &[Option<&str>],
+    data_type: DataType,
+    scale: Option<u32>,
+    options: &CsvOptions,
+) -> Result<VectorImpl> {
+    let fmt = options.temporal_format(data_type);
+    // only text holds empty values
+    let cells = match data_type {
+        DataType::Symbol | DataType::String | DataType::Blob => cells.to_vec(),
+        _ => cells
+            .iter()
+            .map(|cell| cell.filter(|s| !s.is_empty()))
+            .collect(),
+    };
+
+    let v: VectorImpl = match data_type {
+        DataType::Void => cells
+            .iter()
+            .map(|_| Void::default())
+            .collect::<Vector<_>>()
+            .into(),
+        DataType::Bool => parse_vector!(cells, Bool, |s| parse_bool(s).map(Bool::new)).into(),
+        DataType::Char => {
+            parse_vector!(cells, Char, |s: &str| s.parse::<i8>().ok().map(Char::new)).into()
+        }
+        DataType::Short => parse_vector!(cells, Short, |s: &str| s
+            .parse::<i16>()
+            .ok()
+            .map(Short::new))
+        .into(),
+        DataType::Int => {
+            parse_vector!(cells, Int, |s: &str| s.parse::<i32>().ok().map(Int::new)).into()
+        }
+        DataType::Long => {
+            parse_vector!(cells, Long, |s: &str| s.parse::<i64>().ok().map(Long::new)).into()
+        }
+        DataType::Float => parse_vector!(cells, Float, |s: &str| s
+            .parse::<f32>()
+            .ok()
+            .map(Float::new))
+        .into(),
+        DataType::Double => parse_vector!(cells, Double, |s: &str| s
+            .parse::<f64>()
+            .ok()
+            .map(Double::new))
+        .into(),
+        DataType::Date => parse_vector!(cells, Date, |s| Date::parse_with(s, fmt)).into(),
+        DataType::Month => parse_vector!(cells, Month, |s| Month::parse_with(s, fmt)).into(),
+        DataType::Time => parse_vector!(cells, Time, |s| Time::parse_with(s, fmt)).into(),
+        DataType::Minute => parse_vector!(cells, Minute, |s| Minute::parse_with(s, fmt)).into(),
+        DataType::Second => parse_vector!(cells, Second, |s| Second::parse_with(s, fmt)).into(),
+        DataType::NanoTime => {
+            parse_vector!(cells, NanoTime, |s| NanoTime::parse_with(s, fmt)).into()
+        }
+        DataType::DateTime => {
+            parse_vector!(cells, DateTime, |s| DateTime::parse_with(s, fmt)).into()
+        }
+        DataType::Timestamp => {
+            parse_vector!(cells, Timestamp, |s| Timestamp::parse_with(s, fmt)).into()
+        }
+        DataType::NanoTimestamp => {
+            parse_vector!(cells, NanoTimestamp, |s| NanoTimestamp::parse_with(s, fmt)).into()
+        }
+        DataType::DateHour => {
+            parse_vector!(cells, DateHour, |s| DateHour::parse_with(s, fmt)).into()
+        }
+        DataType::Symbol => {
+            parse_vector!(cells, Symbol, |s: &str| Some(Symbol::new(s.to_string()))).into()
+        }
+        DataType::String => parse_vector!(cells, DolphinString, |s: &str| Some(
+            DolphinString::new(s.to_string())
+        ))
+        .into(),
+        DataType::Blob => parse_vector!(cells, Blob, |s: &str| Some(Blob::new(
+            s.as_bytes().to_vec()
+        )))
+        .into(),
+        DataType::Decimal32 => parse_decimal_vector!(cells, Decimal32, i32, scale),
+        DataType::Decimal64 => parse_decimal_vector!(cells, Decimal64, i64, scale),
+        DataType::Decimal128 => parse_decimal_vector!(cells, Decimal128, i128, scale),
+        DataType::CharArray => parse_array_vector!(cells, i8, data_type, options),
+        DataType::ShortArray => parse_array_vector!(cells, i16, data_type, options),
+        DataType::IntArray => parse_array_vector!(cells, i32, data_type, options),
+        DataType::LongArray => parse_array_vector!(cells, i64, data_type, options),
+        DataType::FloatArray => parse_array_vector!(cells, f32, data_type, options),
+        DataType::DoubleArray => parse_array_vector!(cells, f64, data_type, options),
+        _ => return Err(unsupported(data_type)),
+    };
+
+    Ok(v)
+}
+
+fn infer_column(cells: &[Option<&str>], options: &CsvOptions) -> Result<VectorImpl> {
+    const CANDIDATES: [DataType; 14] = [
+        DataType::Int,
+        DataType::Long,
+        DataType::Double,
+        DataType::Bool,
+        DataType::Date,
+        DataType::Month,
+        DataType::Minute,
+        DataType::Second,
+        DataType::Time,
+        DataType::NanoTime,
+        DataType::DateHour,
+        DataType::DateTime,
+        DataType::Timestamp,
+        DataType::NanoTimestamp,
+    ];
+
+    if cells.iter().flatten().any(|cell| !cell.is_empty()) {
+        for data_type in CANDIDATES {
+            if let Ok(v) = parse_column(cells, data_type, None, options) {
+                return Ok(v);
+            }
+        }
+    }
+
+    parse_column(cells, DataType::String, None, options)
+}
+
+macro_rules! format_array_cell {
+    ($v:expr, $index:expr, $raw_type:ty, $options:expr) => {
+        $v[$index]
+            .iter()
+            .map(|x| {
+                if *x == <$raw_type>::MIN {
+                    $options.null.clone()
+                } else {
+                    x.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&$options.array_delimiter.to_string())
+    };
+}
+
+fn format_cell(column: &VectorImpl, index: usize, options: &CsvOptions) -> Option<String> {
+    let fmt = options.temporal_format(column.data_type());
+
+    match column {
+        VectorImpl::Void(_) | VectorImpl::Any(_) => None,
+        VectorImpl::Bool(v) => v[index].into_inner().map(|x| x.to_string()),
+        VectorImpl::Char(v) => v[index].into_inner().map(|x| x.to_string()),
+        VectorImpl::Short(v) => v[index].into_inner().map(|x| x.to_string()),
+        VectorImpl::Int(v) => v[index].into_inner().map(|x| x.to_string()),
+        VectorImpl::Long(v) => v[index].into_inner().map(|x| x.to_string()),
+        VectorImpl::Float(v) => v[index].into_inner().map(|x| x.to_string()),
+        VectorImpl::Double(v) => v[index].into_inner().map(|x| x.to_string()),
+        VectorImpl::Date(v) => v[index].format_with(fmt),
+        VectorImpl::Month(v) => v[index].format_with(fmt),
+        VectorImpl::Time(v) => v[index].format_with(fmt),
+        VectorImpl::Minute(v) => v[index].format_with(fmt),
+        VectorImpl::Second(v) => v[index].format_with(fmt),
+        VectorImpl::NanoTime(v) => v[index].format_with(fmt),
+        VectorImpl::DateTime(v) => v[index].format_with(fmt),
+        VectorImpl::Timestamp(v) => v[index].format_with(fmt),
+        VectorImpl::NanoTimestamp(v) => v[index].format_with(fmt),
+        VectorImpl::DateHour(v) => v[index].format_with(fmt),
+        VectorImpl::Symbol(v) => v[index].0.clone(),
+        VectorImpl::String(v) => v[index].0.clone(),
+        VectorImpl::Blob(v) => v[index]
+            .0
+            .as_ref()
+            .map(|x| String::from_utf8_lossy(x).into_owned()),
+        VectorImpl::Decimal32(v) => v[index].into_inner().map(|x| x.to_string()),
+        VectorImpl::Decimal64(v) => v[index].into_inner().map(|x| x.to_string()),
+        VectorImpl::Decimal128(v) => v[index].into_inner().map(|x| x.to_string()),
+        VectorImpl::ArrayVector(v) => Some(match v {
+            ArrayVectorImpl::Char(v) => format_array_cell!(v, index, i8, options),
+            ArrayVectorImpl::Short(v) => format_array_cell!(v, index, i16, options),
+            ArrayVectorImpl::Int(v) => format_array_cell!(v, index, i32, options),
+            ArrayVectorImpl::Long(v) => format_array_cell!(v, index, i64, options),
+            ArrayVectorImpl::Float(v) => format_array_cell!(v, index, f32, options),
+            ArrayVectorImpl::Double(v) => format_array_cell!(v, index, f64, options),
+        }),
+    }
+}
+
+/// Writes `cells` as a record, null cells as [`CsvOptions::with_null`].
+///
+/// Text cells equal to the null representation are quoted to be read back as text.
+fn write_record<W: Write>(
+    writer: &mut W,
+    cells: impl Iterator<Item = (Option<String>, bool)>,
+    options: &CsvOptions,
+) -> Result<()> {
+    let mut record = Vec::new();
+    for (i, (cell, text)) in cells.enumerate() {
+        if i > 0 {
+            record.push(options.delimiter);
+        }
+        let cell: Cow<'_, str> = match &cell {
+            None => Cow::Borrowed(&options.null),
+            Some(cell) => {
+                let special = |b: u8| matches!(b, b'"' | b'\n' | b'\r') || b == options.delimiter;
+                if (text && *cell == options.null) || cell.bytes().any(special) {
+                    Cow::Owned(format!("\"{}\"", cell.replace('"', "\"\"")))
+                } else {
+                    Cow::Borrowed(cell)
+                }
+            }
+        };
+        record.extend_from_slice(cell.as_bytes());
+    }
+    record.push(b'\n');
+    writer.write_all(&record)?;
+    Ok(())
+}
+
+/// Whether each field of the raw `record` is quoted.
+fn quoted_fields(record: &[u8], delimiter: u8) -> Vec<bool> {
+    let mut quoted = vec![record.first() == Some(&b'"')];
+    let mut in_quotes = false;
+    for (i, &b) in record.iter().enumerate() {
+        if b == b'"' {
+            in_quotes = !in_quotes;
+        } else if b == delimiter && !in_quotes {
+            quoted.push(record.get(i + 1) == Some(&b'"'));
+        }
+    }
+    quoted
+}
+
+impl Table {
+    /// Writes the table as CSV to `writer`.
+    ///
+    /// Tables with `ANY` columns are not supported.
+    pub fn to_csv<W: Write>(&self, writer: W, options: &CsvOptions) -> Result<()> {
+        if let Some(column) = self
+            .columns()
+            .iter()
+            .find(|c| c.data_type() == DataType::Any)
+        {
+            return Err(unsupported(column.data_type()));
+        }
+
+        let mut writer = BufWriter::new(writer);
+
+        if options.header {
+            write_record(
+                &mut writer,
+                self.column_names()
+                    .iter()
+                    .map(|name| (Some(name.clone()), false)),
+                options,
+            )?;
+        }
+
+        for i in 0..self.len() {
+            write_record(
+                &mut writer,
+                self.columns().iter().map(|c| {
+                    let text = matches!(
+                        c.data_type(),
+                        DataType::Symbol | DataType::String | DataType::Blob
+                    );
+                    (format_cell(c, i, options), text)
+                }),
+                options,
+            )?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a table from CSV.
+    ///
+    /// Column types are taken from `schema` or inferred from the cells, trying `INT`, `LONG`,
+    /// `DOUBLE`, `BOOL` and the temporal types in turn before falling back to `STRING`.
+    /// `SYMBOL`, `DECIMAL` and array vector columns are only read through a schema.
+    pub fn from_csv<R: Read>(reader: R, schema: &CsvSchema, options: &CsvOptions) -> Result<Table> {
+        // quoting is not reported by the reader, it is found in the input
+        let mut input = Vec::new();
+        let mut reader = reader;
+        reader.read_to_end(&mut input)?;
+        let mut reader = ReaderBuilder::new()
+            .delimiter(options.delimiter)
+            .has_headers(options.header)
+            .from_reader(input.as_slice());
+
+        let mut column_names = if options.header {
+            reader.headers()?.iter().map(str::to_string).collect()
+        } else {
+            Vec::new()
+        };
+        let mut cells: Vec<Vec<Option<String>>> = vec![Vec::new(); column_names.len()];
+
+        let records = reader
+            .records()
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        for (i, record) in records.iter().enumerate() {
+            if cells.is_empty() {
+                cells.resize(record.len(), Vec::new());
+            }
+            let start = record.position().map_or(0, |p| p.byte() as usize);
+            let end = records
+                .get(i + 1)
+                .and_then(|r| r.position())
+                .map_or(input.len(), |p| p.byte() as usize);
+            let quoted = quoted_fields(&input[start..end], options.delimiter);
+            for (j, (column, cell)) in cells.iter_mut().zip(record.iter()).enumerate() {
+                let null = cell == options.null && !quoted.get(j).copied().unwrap_or(false);
+                column.push((!null).then(|| cell.to_string()));
+            }
+        }
+
+        if !options.header {
+            column_names = (0..cells.len()).map(|i| format!("col{}", i)).collect();
+        }
+
+        if let Some(c) = schema
+            .columns
+            .iter()
+            .find(|c| !column_names.contains(&c.name))
+        {
+            return Err(Error::ConstraintsViolated(format!(
+                "column {} not found in csv",
+                c.name
+            )));
+        }
+
+        let mut columns = Vec::with_capacity(cells.len());
+        for (name, column) in column_names.iter().zip(&cells) {
+            let column = column.iter().map(Option::as_deref).collect::<Vec<_>>();
+
+            columns.push(match schema.columns.iter().find(|c| &c.name == name) {
+                Some(c) => parse_column(&column, c.data_type, c.scale, options)?,
+                None => infer_column(&column, options)?,
+            });
+        }
+
+        let mut builder = TableBuilder::new();
+        builder.with_contents(columns, column_names);
+        builder.build()
+    }
+}
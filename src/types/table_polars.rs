@@ -0,0 +1,432 @@
+//! [Polars](polars) interoperability.
+//!
+//! | DolphinDB                          | Polars                        |
+//! |------------------------------------|-------------------------------|
+//! | `VOID`                             | `Null`                        |
+//! | `BOOL`                             | `Boolean`                     |
+//! | `CHAR` ~ `LONG`                    | `Int8` ~ `Int64`              |
+//! | `FLOAT`, `DOUBLE`                  | `Float32`, `Float64`          |
+//! | `SYMBOL`                           | `Categorical`                 |
+//! | `STRING`, `BLOB`                   | `String`, `Binary`            |
+//! | `DATE`, `MONTH`                    | `Date`                        |
+//! | `TIME`, `MINUTE`, `SECOND`, `NANOTIME` | `Time`                    |
+//! | `DATETIME`, `DATEHOUR`, `TIMESTAMP`| `Datetime(Milliseconds)`      |
+//! | `NANOTIMESTAMP`                    | `Datetime(Nanoseconds)`       |
+//! | `DECIMAL32` ~ `128`                | `Decimal(9 / 18 / 38, scale)` |
+//! | array vectors                      | `List`                        |
+//!
+//! Converting from Polars also accepts unsigned integers, `Enum` and `Datetime(Microseconds)`,
+//! which are widened to the types above.
+//!
+//! Polars fields carry no metadata to keep the DolphinDB type in, so `MONTH`, `TIME`, `MINUTE`,
+//! `SECOND`, `DATETIME` and `DATEHOUR` columns come back as `DATE`, `NANOTIME` or `TIMESTAMP`
+//! unless their types are passed to [`data_frame_to_table`].
+
+use chrono::NaiveDate;
+use polars::prelude::{
+    BinaryChunked, Categories, Column, DataFrame, DataType as PolarsDataType, Int128Chunked,
+    IntoColumn, IntoSeries, NamedFrom, NewChunkedArray, PlSmallStr, Series, TimeUnit,
+};
+
+use crate::error::{Error, Result};
+
+use super::*;
+
+fn unsupported(data_type: impl ToString) -> Error {
+    Error::Unsupported {
+        data_form: DataForm::Vector.to_string(),
+        data_type: data_type.to_string(),
+    }
+}
+
+fn out_of_range(value: impl ToString, data_type: DataType) -> Error {
+    Error::InvalidConvert {
+        from: value.to_string(),
+        to: data_type.to_string(),
+    }
+}
+
+fn decimal_series<S>(name: PlSmallStr, v: &Vector<S>, precision: usize) -> Result<Series>
+where
+    S: DecimalInterface + AsRef<Option<rust_decimal::Decimal>>,
+{
+    let scale = v.max_scale();
+    Int128Chunked::from_iter_options(
+        name,
+        v.iter().map(|d| {
+            d.as_ref().map(|mut d| {
+                d.rescale(scale);
+                d.mantissa()
+            })
+        }),
+    )
+    .into_decimal(Some(precision), scale as usize)
+    .map(IntoSeries::into_series)
+    .map_err(Error::from)
+}
+
+macro_rules! list_series {
+    ($name:expr, $v:expr, $raw_type:ty) => {
+        Series::new(
+            $name,
+            (0..$v.len())
+                .map(|i| {
+                    Series::new(
+                        PlSmallStr::EMPTY,
+                        $v[i]
+                            .iter()
+                            .map(|x| (*x != <$raw_type>::MIN).then_some(*x))
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+    };
+}
+
+/// Converts a [`VectorImpl`] to a Polars [`Series`] named `name`.
+pub fn vector_to_series(name: &str, vector: &VectorImpl) -> Result<Series> {
+    let name = PlSmallStr::from(name);
+
+    fn elapsed<T>(v: &[T], f: impl Fn(&T) -> Option<i64>) -> Vec<Option<i64>> {
+        v.iter().map(f).collect()
+    }
+
+    let s = match vector {
+        VectorImpl::Void(v) => Series::full_null(name, v.len(), &PolarsDataType::Null),
+        VectorImpl::Bool(v) => {
+            Series::new(name, v.iter().map(|x| x.into_inner()).collect::<Vec<_>>())
+        }
+        VectorImpl::Char(v) => {
+            Series::new(name, v.iter().map(|x| x.into_inner()).collect::<Vec<_>>())
+        }
+        VectorImpl::Short(v) => {
+            Series::new(name, v.iter().map(|x| x.into_inner()).collect::<Vec<_>>())
+        }
+        VectorImpl::Int(v) => {
+            Series::new(name, v.iter().map(|x| x.into_inner()).collect::<Vec<_>>())
+        }
+        VectorImpl::Long(v) => {
+            Series::new(name, v.iter().map(|x| x.into_inner()).collect::<Vec<_>>())
+        }
+        VectorImpl::Float(v) => {
+            Series::new(name, v.iter().map(|x| x.into_inner()).collect::<Vec<_>>())
+        }
+        VectorImpl::Double(v) => {
+            Series::new(name, v.iter().map(|x| x.into_inner()).collect::<Vec<_>>())
+        }
+        VectorImpl::String(v) => {
+            Series::new(name, v.iter().map(|x| x.0.as_deref()).collect::<Vec<_>>())
+        }
+        VectorImpl::Symbol(v) => {
+            Series::new(name, v.iter().map(|x| x.0.as_deref()).collect::<Vec<_>>())
+                .cast(&PolarsDataType::from_categories(Categories::global()))?
+        }
+        VectorImpl::Blob(v) => {
+            BinaryChunked::from_iter_options(name, v.iter().map(|x| x.0.as_deref())).into_series()
+        }
+        VectorImpl::Date(v) => Series::new(name, elapsed(v, |x| x.elapsed()))
+            .cast(&PolarsDataType::Int32)?
+            .cast(&PolarsDataType::Date)?,
+        VectorImpl::Month(v) => Series::new(
+            name,
+            elapsed(v, |x| {
+                x.into_inner()
+                    .map(|d| (d - NaiveDate::default()).num_days())
+            }),
+        )
+        .cast(&PolarsDataType::Int32)?
+        .cast(&PolarsDataType::Date)?,
+        VectorImpl::Time(v) => Series::new(
+            name,
+            elapsed(v, |x| x.elapsed().map(|e| e as i64 * 1_000_000)),
+        )
+        .cast(&PolarsDataType::Time)?,
+        VectorImpl::Minute(v) => Series::new(
+            name,
+            elapsed(v, |x| x.elapsed().map(|e| e as i64 * 60_000_000_000)),
+        )
+        .cast(&PolarsDataType::Time)?,
+        VectorImpl::Second(v) => Series::new(
+            name,
+            elapsed(v, |x| x.elapsed().map(|e| e as i64 * 1_000_000_000)),
+        )
+        .cast(&PolarsDataType::Time)?,
+        VectorImpl::NanoTime(v) => Series::new(name, elapsed(v, |x| x.elapsed().map(|e| e as i64)))
+            .cast(&PolarsDataType::Time)?,
+        VectorImpl::DateTime(v) => {
+            Series::new(name, elapsed(v, |x| x.elapsed().map(|e| e as i64 * 1000)))
+                .cast(&PolarsDataType::Datetime(TimeUnit::Milliseconds, None))?
+        }
+        VectorImpl::DateHour(v) => {
+            Series::new(name, elapsed(v, |x| x.elapsed().map(|e| e * 3_600_000)))
+                .cast(&PolarsDataType::Datetime(TimeUnit::Milliseconds, None))?
+        }
+        VectorImpl::Timestamp(v) => Series::new(name, elapsed(v, |x| x.elapsed()))
+            .cast(&PolarsDataType::Datetime(TimeUnit::Milliseconds, None))?,
+        VectorImpl::NanoTimestamp(v) => Series::new(name, elapsed(v, |x| x.elapsed()))
+            .cast(&PolarsDataType::Datetime(TimeUnit::Nanoseconds, None))?,
+        VectorImpl::Decimal32(v) => decimal_series(name, v, 9)?,
+        VectorImpl::Decimal64(v) => decimal_series(name, v, 18)?,
+        VectorImpl::Decimal128(v) => decimal_series(name, v, 38)?,
+        VectorImpl::ArrayVector(v) => match v {
+            ArrayVectorImpl::Char(v) => list_series!(name, v, i8),
+            ArrayVectorImpl::Short(v) => list_series!(name, v, i16),
+            ArrayVectorImpl::Int(v) => list_series!(name, v, i32),
+            ArrayVectorImpl::Long(v) => list_series!(name, v, i64),
+            ArrayVectorImpl::Float(v) => list_series!(name, v, f32),
+            ArrayVectorImpl::Double(v) => list_series!(name, v, f64),
+        },
+        VectorImpl::Any(_) => return Err(unsupported(vector.data_type())),
+    };
+
+    Ok(s)
+}
+
+macro_rules! temporal_vector {
+    ($iter:expr, $struct_name:ident, $convert:expr) => {
+        $iter
+            .map(|x| match x {
+                Some(x) => $convert(x).ok_or(out_of_range(x, $struct_name::data_type())),
+                None => Ok($struct_name::default()),
+            })
+            .collect::<Result<Vector<$struct_name>>>()?
+            .into()
+    };
+}
+
+macro_rules! decimal_vector {
+    ($iter:expr, $struct_name:ident, $raw_type:ty, $scale:expr) => {
+        $iter
+            .map(|x| match x {
+                Some(x) => <$raw_type>::try_from(x)
+                    .ok()
+                    .and_then(|m| $struct_name::from_raw(m, $scale))
+                    .ok_or(out_of_range(x, $struct_name::data_type())),
+                None => Ok($struct_name::default()),
+            })
+            .collect::<Result<Vector<$struct_name>>>()?
+            .into()
+    };
+}
+
+macro_rules! array_vector {
+    ($list:expr, $func:ident, $raw_type:ty) => {{
+        let mut v = ArrayVector::<$raw_type>::new();
+        for row in $list.into_iter() {
+            v.push(match row {
+                Some(row) => row
+                    .$func()?
+                    .into_iter()
+                    .map(|x| x.unwrap_or(<$raw_type>::MIN))
+                    .collect(),
+                None => vec![],
+            });
+        }
+        v.into()
+    }};
+}
+
+/// Converts a Polars [`Series`] to a [`VectorImpl`].
+pub fn series_to_vector(series: &Series) -> Result<VectorImpl> {
+    let v: VectorImpl = match series.dtype() {
+        PolarsDataType::Null => (0..series.len())
+            .map(|_| Void::default())
+            .collect::<Vector<_>>()
+            .into(),
+        PolarsDataType::Boolean => series
+            .bool()?
+            .into_iter()
+            .map(Bool::from)
+            .collect::<Vector<_>>()
+            .into(),
+        PolarsDataType::Int8 => series
+            .i8()?
+            .into_iter()
+            .map(Char::from)
+            .collect::<Vector<_>>()
+            .into(),
+        PolarsDataType::Int16 => series
+            .i16()?
+            .into_iter()
+            .map(Short::from)
+            .collect::<Vector<_>>()
+            .into(),
+        PolarsDataType::Int32 => series
+            .i32()?
+            .into_iter()
+            .map(Int::from)
+            .collect::<Vector<_>>()
+            .into(),
+        PolarsDataType::Int64 => series
+            .i64()?
+            .into_iter()
+            .map(Long::from)
+            .collect::<Vector<_>>()
+            .into(),
+        PolarsDataType::UInt8 => return series_to_vector(&series.cast(&PolarsDataType::Int16)?),
+        PolarsDataType::UInt16 => return series_to_vector(&series.cast(&PolarsDataType::Int32)?),
+        PolarsDataType::UInt32 | PolarsDataType::UInt64 => {
+            return series_to_vector(&series.strict_cast(&PolarsDataType::Int64)?)
+        }
+        PolarsDataType::Float32 => series
+            .f32()?
+            .into_iter()
+            .map(Float::from)
+            .collect::<Vector<_>>()
+            .into(),
+        PolarsDataType::Float64 => series
+            .f64()?
+            .into_iter()
+            .map(Double::from)
+            .collect::<Vector<_>>()
+            .into(),
+        PolarsDataType::String => series
+            .str()?
+            .into_iter()
+            .map(|x| DolphinString::from(x.map(str::to_string)))
+            .collect::<Vector<_>>()
+            .into(),
+        PolarsDataType::Categorical(_, _) | PolarsDataType::Enum(_, _) => series
+            .cast(&PolarsDataType::String)?
+            .str()?
+            .into_iter()
+            .map(|x| Symbol::from(x.map(str::to_string)))
+            .collect::<Vector<_>>()
+            .into(),
+        PolarsDataType::Binary => series
+            .binary()?
+            .into_iter()
+            .map(|x| Blob::from(x.map(<[u8]>::to_vec)))
+            .collect::<Vector<_>>()
+            .into(),
+        PolarsDataType::Date => temporal_vector!(
+            series.cast(&PolarsDataType::Int32)?.i32()?.into_iter(),
+            Date,
+            |x: i32| Date::from_raw(x as i64)
+        ),
+        PolarsDataType::Time => temporal_vector!(
+            series.cast(&PolarsDataType::Int64)?.i64()?.into_iter(),
+            NanoTime,
+            |x: i64| u64::try_from(x).ok().and_then(NanoTime::from_raw)
+        ),
+        PolarsDataType::Datetime(unit, _) => {
+            let elapsed = series.cast(&PolarsDataType::Int64)?;
+            let elapsed = elapsed.i64()?.into_iter();
+            match unit {
+                TimeUnit::Milliseconds => temporal_vector!(elapsed, Timestamp, Timestamp::from_raw),
+                TimeUnit::Microseconds => temporal_vector!(elapsed, NanoTimestamp, |x: i64| x
+                    .checked_mul(1000)
+                    .and_then(NanoTimestamp::from_raw)),
+                TimeUnit::Nanoseconds => {
+                    temporal_vector!(elapsed, NanoTimestamp, NanoTimestamp::from_raw)
+                }
+            }
+        }
+        PolarsDataType::Decimal(precision, scale) => {
+            let scale = scale.unwrap_or(0) as u32;
+            let decimals = series.decimal()?.physical().into_iter();
+            match precision.unwrap_or(38) {
+                0..=9 => decimal_vector!(decimals, Decimal32, i32, scale),
+                10..=18 => decimal_vector!(decimals, Decimal64, i64, scale),
+                _ => decimal_vector!(decimals, Decimal128, i128, scale),
+            }
+        }
+        PolarsDataType::List(inner) => {
+            let list = series.list()?;
+            match inner.as_ref() {
+                PolarsDataType::Int8 => array_vector!(list, i8, i8),
+                PolarsDataType::Int16 => array_vector!(list, i16, i16),
+                PolarsDataType::Int32 => array_vector!(list, i32, i32),
+                PolarsDataType::Int64 => array_vector!(list, i64, i64),
+                PolarsDataType::Float32 => array_vector!(list, f32, f32),
+                PolarsDataType::Float64 => array_vector!(list, f64, f64),
+                _ => return Err(unsupported(series.dtype())),
+            }
+        }
+        t => return Err(unsupported(t)),
+    };
+
+    Ok(v)
+}
+
+/// Converts a Polars [`Series`] to a [`VectorImpl`] of `data_type`.
+///
+/// Temporal columns are converted to units of `data_type`, decimals keep the scale of `series`.
+pub fn series_to_vector_as(series: &Series, data_type: DataType) -> Result<VectorImpl> {
+    let vector = series_to_vector(series)?;
+    if vector.data_type() == data_type {
+        return Ok(vector);
+    }
+
+    match (series.dtype(), data_type) {
+        (
+            PolarsDataType::Decimal(_, scale),
+            DataType::Decimal32 | DataType::Decimal64 | DataType::Decimal128,
+        ) => vector.cast_decimal(data_type, scale.unwrap_or(0) as u32),
+        _ => vector.cast(data_type),
+    }
+}
+
+/// Converts a Polars [`DataFrame`] to a [`Table`], reading the columns named in `column_types`
+/// as those types and inferring the others.
+pub fn data_frame_to_table(df: &DataFrame, column_types: &[(&str, DataType)]) -> Result<Table> {
+    let mut columns = Vec::with_capacity(df.width());
+    let mut column_names = Vec::with_capacity(df.width());
+
+    for column in df.get_columns() {
+        let series = column.as_materialized_series();
+        let data_type = column_types
+            .iter()
+            .find(|(name, _)| *name == column.name().as_str())
+            .map(|(_, data_type)| *data_type);
+        columns.push(match data_type {
+            Some(data_type) => series_to_vector_as(series, data_type)?,
+            None => series_to_vector(series)?,
+        });
+        column_names.push(column.name().to_string());
+    }
+
+    let mut builder = TableBuilder::new();
+    builder.with_contents(columns, column_names);
+    builder.build()
+}
+
+impl TryFrom<&Table> for DataFrame {
+    type Error = Error;
+
+    fn try_from(value: &Table) -> Result<Self> {
+        let columns = value
+            .column_names()
+            .iter()
+            .zip(value.columns())
+            .map(|(name, column)| vector_to_series(name, column).map(IntoColumn::into_column))
+            .collect::<Result<Vec<Column>>>()?;
+
+        Ok(DataFrame::new(columns)?)
+    }
+}
+
+impl TryFrom<Table> for DataFrame {
+    type Error = Error;
+
+    fn try_from(value: Table) -> Result<Self> {
+        DataFrame::try_from(&value)
+    }
+}
+
+impl TryFrom<&DataFrame> for Table {
+    type Error = Error;
+
+    fn try_from(value: &DataFrame) -> Result<Self> {
+        data_frame_to_table(value, &[])
+    }
+}
+
+impl TryFrom<DataFrame> for Table {
+    type Error = Error;
+
+    fn try_from(value: DataFrame) -> Result<Self> {
+        Table::try_from(&value)
+    }
+}
@@ -0,0 +1,993 @@
+//! [`serde`] integration for [`Table`] rows.
+//!
+//! Rows are mapped to structs by column name. Null values map to [`Option`], temporal values
+//! map to [`chrono`] types and decimal values map to [`rust_decimal::Decimal`].
+//!
+//! ```
+//! use dolphindb::types::{from_table, to_table};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Trade {
+//!     sym: String,
+//!     price: f64,
+//!     qty: Option<i32>,
+//! }
+//!
+//! let trades = vec![
+//!     Trade { sym: "AAPL".into(), price: 10.5, qty: Some(100) },
+//!     Trade { sym: "MSFT".into(), price: 20.5, qty: None },
+//! ];
+//!
+//! let table = to_table(&trades).unwrap();
+//! assert_eq!(from_table::<Trade>(&table).unwrap(), trades);
+//! ```
+
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::{prelude::FromPrimitive, Decimal};
+use serde::{
+    de::{self, value::SeqDeserializer, DeserializeOwned, IntoDeserializer, MapAccess, Visitor},
+    ser::{self, Impossible, Serialize, SerializeMap, SerializeStruct},
+    Deserializer,
+};
+
+use crate::error::{Error, Result};
+
+use super::{
+    ArrayVectorImpl, Blob, Bool, Char, Constant, ConstantImpl, DataType, Date, DateHour, DateTime,
+    Decimal128, Decimal32, Decimal64, DictionaryImpl, DolphinString, Double, Float, Int, Long,
+    Minute, Month, NanoTime, NanoTimestamp, ScalarImpl, Second, Short, Symbol, Table, TableBuilder,
+    Time, Timestamp, Vector, VectorImpl,
+};
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Serde(msg.to_string())
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Serde(msg.to_string())
+    }
+}
+
+/// Deserializes every row of `table` into `T`, mapping column names to field names.
+pub fn from_table<T>(table: &Table) -> Result<Vec<T>>
+where
+    T: DeserializeOwned,
+{
+    (0..table.len())
+        .map(|index| T::deserialize(RowDeserializer::new(table, index)))
+        .collect()
+}
+
+/// Serializes `rows` into a [`Table`], mapping field names to column names.
+///
+/// See [`TableSerializer`] to specify column types explicitly.
+pub fn to_table<T>(rows: &[T]) -> Result<Table>
+where
+    T: Serialize,
+{
+    TableSerializer::new().to_table(rows)
+}
+
+/// A [`Deserializer`] over one row of a [`Table`].
+///
+/// The row is presented as a map from column name to cell value.
+#[derive(Debug, Clone, Copy)]
+pub struct RowDeserializer<'a> {
+    table: &'a Table,
+    index: usize,
+}
+
+impl<'a> RowDeserializer<'a> {
+    /// Creates a deserializer over the `index`th row of `table`.
+    ///
+    /// Deserialization fails if `index` is out of bounds.
+    pub fn new(table: &'a Table, index: usize) -> Self {
+        Self { table, index }
+    }
+}
+
+impl<'de> Deserializer<'de> for RowDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(RowAccess {
+            row: self,
+            column: 0,
+        })
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct RowAccess<'a> {
+    row: RowDeserializer<'a>,
+    column: usize,
+}
+
+impl<'de> MapAccess<'de> for RowAccess<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.row.table.column_names().get(self.column) {
+            Some(name) => seed
+                .deserialize(name.as_str().into_deserializer())
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let column = self.row.table.get_columns_by_index(self.column);
+        self.column += 1;
+
+        seed.deserialize(ConstantDeserializer(cell(column, self.row.index)?))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.table.columns().len() - self.column)
+    }
+}
+
+// Array vector cells are returned as vectors.
+fn cell(column: &VectorImpl, index: usize) -> Result<ConstantImpl> {
+    if index >= column.len() {
+        return Err(Error::Serde(format!(
+            "row {} out of bounds of {} rows",
+            index,
+            column.len()
+        )));
+    }
+
+    macro_rules! array_cell {
+        ($(($enum_name:ident, $struct_name:ident, $raw_type:ty)), *) => {
+            match column {
+                $(
+                    VectorImpl::ArrayVector(ArrayVectorImpl::$enum_name(v)) => v[index]
+                        .iter()
+                        .map(|raw| {
+                            if *raw == <$raw_type>::MIN {
+                                $struct_name::default()
+                            } else {
+                                $struct_name::new(*raw)
+                            }
+                        })
+                        .collect::<Vector<$struct_name>>()
+                        .into(),
+                )*
+                _ => column.get(index).ok_or(Error::Serde(format!(
+                    "cannot read row {} of {} column",
+                    index,
+                    column.data_type()
+                )))?,
+            }
+        };
+    }
+
+    Ok(array_cell!(
+        (Char, Char, i8),
+        (Short, Short, i16),
+        (Int, Int, i32),
+        (Long, Long, i64),
+        (Float, Float, f32),
+        (Double, Double, f64)
+    ))
+}
+
+struct ConstantDeserializer(ConstantImpl);
+
+impl<'de> IntoDeserializer<'de, Error> for ConstantDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl ConstantDeserializer {
+    fn elements(v: VectorImpl) -> Result<Vec<ConstantDeserializer>> {
+        (0..v.len())
+            .map(|i| cell(&v, i).map(ConstantDeserializer))
+            .collect()
+    }
+}
+
+impl<'de> Deserializer<'de> for ConstantDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let s = match self.0 {
+            ConstantImpl::Scalar(s) => s,
+            ConstantImpl::Vector(v) => {
+                return visitor.visit_seq(SeqDeserializer::new(Self::elements(v)?.into_iter()));
+            }
+            ConstantImpl::Pair(p) => {
+                return visitor
+                    .visit_seq(SeqDeserializer::new(Self::elements(p.into())?.into_iter()));
+            }
+            c => {
+                return Err(Error::Unsupported {
+                    data_form: c.data_form().to_string(),
+                    data_type: c.data_type().to_string(),
+                })
+            }
+        };
+
+        if s.is_null() {
+            return visitor.visit_unit();
+        }
+
+        match s {
+            ScalarImpl::Bool(v) => visitor.visit_bool(v.into_inner().unwrap()),
+            ScalarImpl::Char(v) => visitor.visit_i8(v.into_inner().unwrap()),
+            ScalarImpl::Short(v) => visitor.visit_i16(v.into_inner().unwrap()),
+            ScalarImpl::Int(v) => visitor.visit_i32(v.into_inner().unwrap()),
+            ScalarImpl::Long(v) => visitor.visit_i64(v.into_inner().unwrap()),
+            ScalarImpl::Float(v) => visitor.visit_f32(v.into_inner().unwrap()),
+            ScalarImpl::Double(v) => visitor.visit_f64(v.into_inner().unwrap()),
+            ScalarImpl::Symbol(v) => visitor.visit_string(v.into_inner().unwrap()),
+            ScalarImpl::String(v) => visitor.visit_string(v.into_inner().unwrap()),
+            ScalarImpl::Blob(v) => visitor.visit_byte_buf(v.into_inner().unwrap()),
+            ScalarImpl::Date(v) => visit_chrono(visitor, v.into_inner()),
+            ScalarImpl::Month(v) => visit_chrono(visitor, v.into_inner()),
+            ScalarImpl::Time(v) => visit_chrono(visitor, v.into_inner()),
+            ScalarImpl::Minute(v) => visit_chrono(visitor, v.into_inner()),
+            ScalarImpl::Second(v) => visit_chrono(visitor, v.into_inner()),
+            ScalarImpl::NanoTime(v) => visit_chrono(visitor, v.into_inner()),
+            ScalarImpl::DateTime(v) => visit_chrono(visitor, v.into_inner()),
+            ScalarImpl::Timestamp(v) => visit_chrono(visitor, v.into_inner()),
+            ScalarImpl::NanoTimestamp(v) => visit_chrono(visitor, v.into_inner()),
+            ScalarImpl::DateHour(v) => visit_chrono(visitor, v.into_inner()),
+            ScalarImpl::Decimal32(v) => visitor.visit_string(v.into_inner().unwrap().to_string()),
+            ScalarImpl::Decimal64(v) => visitor.visit_string(v.into_inner().unwrap().to_string()),
+            ScalarImpl::Decimal128(v) => visitor.visit_string(v.into_inner().unwrap().to_string()),
+            ScalarImpl::Void(_) => visitor.visit_unit(),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.is_null() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    // decimals are deserialized as strings by default, allow reading them into floats.
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let decimal = match &self.0 {
+            ConstantImpl::Scalar(ScalarImpl::Decimal32(v)) => v.0,
+            ConstantImpl::Scalar(ScalarImpl::Decimal64(v)) => v.0,
+            ConstantImpl::Scalar(ScalarImpl::Decimal128(v)) => v.0,
+            _ => return self.deserialize_any(visitor),
+        };
+
+        match decimal.and_then(|d| rust_decimal::prelude::ToPrimitive::to_f64(&d)) {
+            Some(f) => visitor.visit_f64(f),
+            None => visitor.visit_unit(),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+// Passes a temporal value in the form chrono serializes it, which its Deserialize impls parse.
+fn visit_chrono<'de, V, T>(visitor: V, value: Option<T>) -> Result<V::Value>
+where
+    V: Visitor<'de>,
+    T: Serialize,
+{
+    match value.map(|v| v.serialize(ValueSerializer)).transpose()? {
+        Some(Value::Str(s)) => visitor.visit_string(s),
+        Some(v) => Err(Error::Serde(format!("unexpected temporal value {:?}", v))),
+        None => visitor.visit_unit(),
+    }
+}
+
+/// Builds a [`Table`] or a [`DictionaryImpl`] from a slice of structs or maps.
+///
+/// Column types are inferred from the first non-null value of each column unless specified by
+/// [`with_column_type`](TableSerializer::with_column_type). Temporal and decimal columns must be
+/// specified explicitly since [`chrono`] and [`rust_decimal`] values are serialized as strings.
+#[derive(Debug, Clone, Default)]
+pub struct TableSerializer {
+    name: String,
+    column_types: HashMap<String, (DataType, Option<u32>)>,
+}
+
+impl TableSerializer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_name(&mut self, name: String) -> &mut Self {
+        self.name = name;
+        self
+    }
+
+    /// Specifies the [`DataType`] of a column instead of inferring it.
+    pub fn with_column_type(&mut self, column_name: &str, data_type: DataType) -> &mut Self {
+        self.column_types
+            .insert(column_name.to_string(), (data_type, None));
+        self
+    }
+
+    /// Specifies the decimal [`DataType`] of a column, rescaling its values to `scale`.
+    pub fn with_decimal_column_type(
+        &mut self,
+        column_name: &str,
+        data_type: DataType,
+        scale: u32,
+    ) -> &mut Self {
+        self.column_types
+            .insert(column_name.to_string(), (data_type, Some(scale)));
+        self
+    }
+
+    /// Serializes `rows` into a [`Table`].
+    pub fn to_table<T>(&self, rows: &[T]) -> Result<Table>
+    where
+        T: Serialize,
+    {
+        let mut column_names: Vec<String> = Vec::new();
+        let mut values: Vec<Vec<Value>> = Vec::new();
+
+        for (i, row) in rows.iter().enumerate() {
+            let fields = row.serialize(RowSerializer::default())?;
+
+            if i == 0 {
+                for (name, value) in fields {
+                    column_names.push(name);
+                    values.push(vec![value]);
+                }
+                continue;
+            }
+
+            if fields.len() != column_names.len() {
+                return Err(Error::ConstraintsViolated(format!(
+                    "row {} has {} fields, but {} columns expected",
+                    i,
+                    fields.len(),
+                    column_names.len()
+                )));
+            }
+
+            for (j, (name, value)) in fields.into_iter().enumerate() {
+                let index = if column_names[j] == name {
+                    j
+                } else {
+                    column_names.iter().position(|n| *n == name).ok_or(
+                        Error::ConstraintsViolated(format!(
+                            "unknown column `{}` in row {}",
+                            name, i
+                        )),
+                    )?
+                };
+                values[index].push(value);
+            }
+        }
+
+        let mut columns = Vec::with_capacity(values.len());
+        for (name, values) in column_names.iter().zip(values) {
+            let (data_type, scale) = match self.column_types.get(name) {
+                Some(column_type) => *column_type,
+                None => (
+                    values
+                        .iter()
+                        .find_map(Value::data_type)
+                        .ok_or(Error::ConstraintsViolated(format!(
+                            "cannot infer type of column `{}` with only null values",
+                            name
+                        )))?,
+                    None,
+                ),
+            };
+
+            columns.push(build_column(name, data_type, scale, values)?);
+        }
+
+        let mut builder = TableBuilder::new();
+        builder
+            .with_name(self.name.clone())
+            .with_contents(columns, column_names);
+        builder.build()
+    }
+
+    /// Serializes `rows` into a [`DictionaryImpl`] from column name to column.
+    pub fn to_dictionary<T>(&self, rows: &[T]) -> Result<DictionaryImpl>
+    where
+        T: Serialize,
+    {
+        self.to_table(rows).map(Into::into)
+    }
+}
+
+fn build_column(
+    name: &str,
+    data_type: DataType,
+    scale: Option<u32>,
+    values: Vec<Value>,
+) -> Result<VectorImpl> {
+    let mut column = VectorImpl::from_type(data_type).ok_or(Error::Unsupported {
+        data_form: "Vector".into(),
+        data_type: data_type.to_string(),
+    })?;
+
+    for value in values {
+        let s = value.into_scalar(data_type, scale).map_err(|e| {
+            Error::ConstraintsViolated(format!("failed to serialize column `{}`: {}", name, e))
+        })?;
+        column.push_scalar(s);
+    }
+
+    Ok(column)
+}
+
+// serialized field value before its column type is resolved.
+#[derive(Debug, Clone)]
+enum Value {
+    Null,
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    Str(String),
+    Bytes(Vec<u8>),
+}
+
+impl Value {
+    fn data_type(&self) -> Option<DataType> {
+        match self {
+            Value::Null => None,
+            Value::Bool(_) => Some(DataType::Bool),
+            Value::I8(_) => Some(DataType::Char),
+            Value::I16(_) => Some(DataType::Short),
+            Value::I32(_) => Some(DataType::Int),
+            Value::I64(_) | Value::U64(_) => Some(DataType::Long),
+            Value::F32(_) => Some(DataType::Float),
+            Value::F64(_) => Some(DataType::Double),
+            Value::Str(_) => Some(DataType::String),
+            Value::Bytes(_) => Some(DataType::Blob),
+        }
+    }
+
+    fn integer(&self) -> Option<i128> {
+        match *self {
+            Value::I8(v) => Some(v as i128),
+            Value::I16(v) => Some(v as i128),
+            Value::I32(v) => Some(v as i128),
+            Value::I64(v) => Some(v as i128),
+            Value::U64(v) => Some(v as i128),
+            _ => None,
+        }
+    }
+
+    fn float(&self) -> Option<f64> {
+        match *self {
+            Value::F32(v) => Some(v as f64),
+            Value::F64(v) => Some(v),
+            _ => self.integer().map(|i| i as f64),
+        }
+    }
+
+    fn parse<T: FromStr>(&self) -> Option<T> {
+        match self {
+            Value::Str(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn decimal(&self, scale: Option<u32>) -> Option<Decimal> {
+        let mut d = match *self {
+            Value::F32(v) => Decimal::from_f32(v),
+            Value::F64(v) => Decimal::from_f64(v),
+            Value::Str(_) => self.parse(),
+            _ => self.integer().and_then(Decimal::from_i128),
+        }?;
+        if let Some(scale) = scale {
+            d.rescale(scale);
+        }
+        Some(d)
+    }
+
+    fn into_scalar(self, data_type: DataType, scale: Option<u32>) -> Result<ScalarImpl> {
+        if matches!(self, Value::Null) {
+            return ScalarImpl::from_type(data_type).ok_or(Error::Unsupported {
+                data_form: "Scalar".into(),
+                data_type: data_type.to_string(),
+            });
+        }
+
+        let from = format!("{:?}", self);
+
+        let s: Option<ScalarImpl> = match data_type {
+            DataType::Bool => match self {
+                Value::Bool(b) => Some(Bool::new(b).into()),
+                _ => None,
+            },
+            DataType::Char => self
+                .integer()
+                .and_then(|i| i8::try_from(i).ok())
+                .map(|i| Char::new(i).into()),
+            DataType::Short => self
+                .integer()
+                .and_then(|i| i16::try_from(i).ok())
+                .map(|i| Short::new(i).into()),
+            DataType::Int => self
+                .integer()
+                .and_then(|i| i32::try_from(i).ok())
+                .map(|i| Int::new(i).into()),
+            DataType::Long => self
+                .integer()
+                .and_then(|i| i64::try_from(i).ok())
+                .map(|i| Long::new(i).into()),
+            DataType::Float => self.float().map(|f| Float::new(f as f32).into()),
+            DataType::Double => self.float().map(|f| Double::new(f).into()),
+            DataType::Symbol => match self {
+                Value::Str(s) => Some(Symbol::new(s).into()),
+                _ => None,
+            },
+            DataType::String => match self {
+                Value::Str(s) => Some(DolphinString::new(s).into()),
+                _ => None,
+            },
+            DataType::Blob => match self {
+                Value::Bytes(b) => Some(Blob::new(b).into()),
+                Value::Str(s) => Some(Blob::new(s.into_bytes()).into()),
+                _ => None,
+            },
+            DataType::Date => self.parse::<NaiveDate>().map(|d| Date::from(d).into()),
+            DataType::Month => self
+                .parse::<NaiveDate>()
+                .and_then(|d| Month::from_ym(d.year(), d.month()))
+                .map(Into::into),
+            DataType::Time => self.parse::<NaiveTime>().map(|t| Time::from(t).into()),
+            DataType::Minute => self.parse::<NaiveTime>().map(|t| Minute::from(t).into()),
+            DataType::Second => self.parse::<NaiveTime>().map(|t| Second::from(t).into()),
+            DataType::NanoTime => self.parse::<NaiveTime>().map(|t| NanoTime::from(t).into()),
+            DataType::DateTime => self
+                .parse::<NaiveDateTime>()
+                .map(|dt| DateTime::from(dt).into()),
+            DataType::Timestamp => self
+                .parse::<NaiveDateTime>()
+                .map(|dt| Timestamp::from(dt).into()),
+            DataType::NanoTimestamp => self
+                .parse::<NaiveDateTime>()
+                .map(|dt| NanoTimestamp::from(dt).into()),
+            DataType::DateHour => self
+                .parse::<NaiveDateTime>()
+                .map(|dt| DateHour::from(dt).into()),
+            DataType::Decimal32 => self.decimal(scale).and_then(|d| {
+                i32::try_from(d.mantissa())
+                    .ok()
+                    .and_then(|m| Decimal32::from_raw(m, d.scale()))
+                    .map(Into::into)
+            }),
+            DataType::Decimal64 => self.decimal(scale).and_then(|d| {
+                i64::try_from(d.mantissa())
+                    .ok()
+                    .and_then(|m| Decimal64::from_raw(m, d.scale()))
+                    .map(Into::into)
+            }),
+            DataType::Decimal128 => self
+                .decimal(scale)
+                .and_then(|d| Decimal128::from_raw(d.mantissa(), d.scale()))
+                .map(Into::into),
+            _ => {
+                return Err(Error::Unsupported {
+                    data_form: "Scalar".into(),
+                    data_type: data_type.to_string(),
+                })
+            }
+        };
+
+        s.ok_or(Error::InvalidConvert {
+            from,
+            to: data_type.to_string(),
+        })
+    }
+}
+
+fn unsupported_row() -> Error {
+    Error::Serde("table rows must be serialized as structs or maps".into())
+}
+
+fn unsupported_value() -> Error {
+    Error::Serde("nested values are not supported in table cells".into())
+}
+
+// Serializes a struct or map into (column name, value) pairs.
+#[derive(Default)]
+struct RowSerializer {
+    fields: Vec<(String, Value)>,
+    key: Option<String>,
+}
+
+macro_rules! unsupported_row_methods {
+    ($($func_name:ident($($arg:ty),*)),*) => {
+        $(
+            fn $func_name(self, $(_: $arg),*) -> Result<Self::Ok> {
+                Err(unsupported_row())
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for RowSerializer {
+    type Ok = Vec<(String, Value)>;
+    type Error = Error;
+
+    type SerializeSeq = Impossible<Self::Ok, Error>;
+    type SerializeTuple = Impossible<Self::Ok, Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Error>;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<Self::Ok, Error>;
+
+    unsupported_row_methods!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_none(),
+        serialize_unit(),
+        serialize_unit_struct(&'static str),
+        serialize_unit_variant(&'static str, u32, &'static str)
+    );
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(unsupported_row())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(unsupported_row())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(unsupported_row())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(unsupported_row())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(unsupported_row())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(unsupported_row())
+    }
+}
+
+impl SerializeStruct for RowSerializer {
+    type Ok = Vec<(String, Value)>;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.fields
+            .push((key.to_string(), value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(self.fields)
+    }
+}
+
+impl SerializeMap for RowSerializer {
+    type Ok = Vec<(String, Value)>;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        match key.serialize(ValueSerializer)? {
+            Value::Str(s) => {
+                self.key = Some(s);
+                Ok(())
+            }
+            _ => Err(Error::Serde("map keys must be strings".into())),
+        }
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or(Error::Serde("map value without key".into()))?;
+        self.fields.push((key, value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(self.fields)
+    }
+}
+
+// Serializes a single cell.
+struct ValueSerializer;
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = Impossible<Value, Error>;
+    type SerializeTuple = Impossible<Value, Error>;
+    type SerializeTupleStruct = Impossible<Value, Error>;
+    type SerializeTupleVariant = Impossible<Value, Error>;
+    type SerializeMap = Impossible<Value, Error>;
+    type SerializeStruct = Impossible<Value, Error>;
+    type SerializeStructVariant = Impossible<Value, Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<Value> {
+        Ok(Value::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value> {
+        Ok(Value::I8(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value> {
+        Ok(Value::I16(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value> {
+        Ok(Value::I32(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value> {
+        Ok(Value::I64(v))
+    }
+
+    // unsigned integers are widened since DolphinDB has no unsigned types.
+    fn serialize_u8(self, v: u8) -> Result<Value> {
+        Ok(Value::I16(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value> {
+        Ok(Value::I32(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value> {
+        Ok(Value::I64(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value> {
+        Ok(Value::U64(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value> {
+        Ok(Value::F32(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value> {
+        Ok(Value::F64(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value> {
+        Ok(Value::Str(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value> {
+        Ok(Value::Str(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value> {
+        Ok(Value::Bytes(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    // unit variants are stored as their names, which fits Symbol columns.
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value> {
+        Ok(Value::Str(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(unsupported_value())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(unsupported_value())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(unsupported_value())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(unsupported_value())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(unsupported_value())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(unsupported_value())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Err(unsupported_value())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(unsupported_value())
+    }
+}
@@ -1,19 +1,54 @@
-use super::DataType;
+use super::{DataType, OptionRef, RawScalar};
 use crate::error::Error;
 
 use chrono::naive::{NaiveDate, NaiveDateTime, NaiveTime};
-use std::any::type_name;
+use std::{
+    any::type_name,
+    fmt::{self, Debug},
+};
 
 macro_rules! temporal_impl {
-    ($raw_type:tt, $struct_name:ident, $enum_name:ident) => {
-        #[derive(Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-        pub struct $struct_name(pub(crate) Option<$raw_type>);
+    ($raw_type:tt, $wire_type:tt, $struct_name:ident, $enum_name:ident) => {
+        #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+        #[repr(transparent)]
+        pub struct $struct_name(pub(crate) $wire_type);
+
+        impl Default for $struct_name {
+            fn default() -> Self {
+                Self($wire_type::MIN)
+            }
+        }
+
+        impl Debug for $struct_name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($struct_name))
+                    .field(&self.into_inner())
+                    .finish()
+            }
+        }
+
+        // SAFETY: the struct is repr(transparent) over the wire type.
+        unsafe impl RawScalar for $struct_name {
+            type Raw = $wire_type;
+
+            const NULL: Self::Raw = $wire_type::MIN;
+
+            #[inline]
+            fn from_raw_value(raw: Self::Raw) -> Self {
+                Self(raw)
+            }
+
+            #[inline]
+            fn raw_value(&self) -> Self::Raw {
+                self.0
+            }
+        }
 
         impl $struct_name {
             pub const DATA_BYTE: DataType = DataType::$enum_name;
 
             pub(crate) fn new(val: $raw_type) -> Self {
-                Self(Some(val))
+                Self(Self::encode(val))
             }
 
             pub const fn data_type() -> DataType {
@@ -21,34 +56,28 @@ macro_rules! temporal_impl {
             }
 
             pub const fn is_null(&self) -> bool {
-                self.0.is_none()
+                self.0 == $wire_type::MIN
             }
 
+            /// Converts to [`chrono`] type, [`None`] if null or out of range.
             pub fn into_inner(self) -> Option<$raw_type> {
-                self.0
+                if self.is_null() {
+                    None
+                } else {
+                    Self::decode(self.0)
+                }
             }
-        }
-    };
 
-    ($(($raw_type:tt, $struct_name:ident, $enum_name:ident)), *) => {
-        $(
-            temporal_impl!($raw_type, $struct_name, $enum_name);
-        )*
-    };
-}
-
-macro_rules! as_ref_impl {
-    ($raw_type:tt, $struct_name:ident, $enum_name:ident) => {
-        impl AsRef<Option<$raw_type>> for $struct_name {
-            fn as_ref(&self) -> &Option<$raw_type> {
-                &self.0
+            /// Views the value as an [`Option`], [`None`] if null or out of range.
+            pub fn as_ref(&self) -> OptionRef<'_, $raw_type> {
+                OptionRef::new(self.into_inner())
             }
         }
     };
 
-    ($(($raw_type:tt, $struct_name:ident, $enum_name:ident)), *) => {
+    ($(($raw_type:tt, $wire_type:tt, $struct_name:ident, $enum_name:ident)), *) => {
         $(
-            as_ref_impl!($raw_type, $struct_name, $enum_name);
+            temporal_impl!($raw_type, $wire_type, $struct_name, $enum_name);
         )*
     };
 }
@@ -70,12 +99,12 @@ macro_rules! from_raw_impl {
         }
         impl From<$raw_type> for $struct_name {
             fn from(value: $raw_type) -> Self {
-                Self(Some(value))
+                Self::new(value)
             }
         }
     };
 
-    ($(($raw_type:tt, $struct_name:ident, $enum_name:ident)), *) => {
+    ($(($raw_type:tt, $wire_type:tt, $struct_name:ident, $enum_name:ident)), *) => {
         $(
             from_raw_impl!($raw_type, $struct_name);
         )*
@@ -85,22 +114,20 @@ macro_rules! from_raw_impl {
 macro_rules! for_all_types {
     ($macro:tt) => {
         $macro!(
-            (NaiveDate, Date, Date),
-            (NaiveDate, Month, Month),
-            (NaiveTime, Time, Time),
-            (NaiveTime, Minute, Minute),
-            (NaiveTime, Second, Second),
-            (NaiveDateTime, DateTime, DateTime),
-            (NaiveDateTime, Timestamp, Timestamp),
-            (NaiveTime, NanoTime, NanoTime),
-            (NaiveDateTime, NanoTimestamp, NanoTimestamp),
-            (NaiveDateTime, DateHour, DateHour)
+            (NaiveDate, i32, Date, Date),
+            (NaiveDate, i32, Month, Month),
+            (NaiveTime, i32, Time, Time),
+            (NaiveTime, i32, Minute, Minute),
+            (NaiveTime, i32, Second, Second),
+            (NaiveDateTime, i32, DateTime, DateTime),
+            (NaiveDateTime, i64, Timestamp, Timestamp),
+            (NaiveTime, i64, NanoTime, NanoTime),
+            (NaiveDateTime, i64, NanoTimestamp, NanoTimestamp),
+            (NaiveDateTime, i32, DateHour, DateHour)
         );
     };
 }
 
 for_all_types!(temporal_impl);
 
-for_all_types!(as_ref_impl);
-
 for_all_types!(from_raw_impl);
@@ -1,6 +1,14 @@
 use super::{
-    any::Any, array_vector::*, decimal::*, for_all_types, primitive::*, temporal::*, Constant,
-    ConstantImpl, DataForm, DataType, DecimalInterface, NotDecimal, ScalarImpl,
+    any::Any,
+    array_vector::*,
+    cast::{from_temporal_raw, to_decimal},
+    decimal::*,
+    for_all_types,
+    primitive::*,
+    raw::{as_raw_slice, as_raw_slice_mut},
+    temporal::*,
+    Constant, ConstantImpl, DataForm, DataType, DecimalInterface, NotDecimal, RawScalar,
+    ScalarImpl,
 };
 use crate::{
     error::{Error, Result},
@@ -8,11 +16,13 @@ use crate::{
 };
 use byteorder::{WriteBytesExt, BE, LE};
 use bytes::BufMut;
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use rust_decimal::Decimal;
 use std::{
     any::type_name,
     collections::HashMap,
     fmt::{self, Display},
+    mem::ManuallyDrop,
     ops::{Deref, DerefMut, Index, IndexMut},
     slice::{Iter, IterMut, SliceIndex},
     vec::IntoIter,
@@ -142,6 +152,12 @@ impl<T> FromIterator<T> for Vector<T> {
     }
 }
 
+impl<S> Extend<S> for Vector<S> {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        self.data.extend(iter)
+    }
+}
+
 impl<S> IntoIterator for Vector<S> {
     type Item = S;
 
@@ -287,6 +303,40 @@ impl<S: Primitive> Vector<S> {
     }
 }
 
+impl<S: RawScalar> Vector<S> {
+    /// Returns the raw values as they are transferred, nulls included as sentinels.
+    pub fn as_slice(&self) -> &[S::Raw] {
+        as_raw_slice(&self.data)
+    }
+
+    /// Returns the raw values as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [S::Raw] {
+        as_raw_slice_mut(&mut self.data)
+    }
+
+    /// Constructs a new [`Vector`] from raw values without copying them.
+    pub fn from_raw_vec(raw: Vec<S::Raw>) -> Self {
+        let mut raw = ManuallyDrop::new(raw);
+        // SAFETY: S is repr(transparent) over S::Raw, so the allocation layout is identical.
+        let data =
+            unsafe { Vec::from_raw_parts(raw.as_mut_ptr() as *mut S, raw.len(), raw.capacity()) };
+        Self { data }
+    }
+
+    /// Converts the vector into raw values without copying them.
+    pub fn into_raw_vec(self) -> Vec<S::Raw> {
+        let mut data = ManuallyDrop::new(self.data);
+        // SAFETY: S is repr(transparent) over S::Raw, so the allocation layout is identical.
+        unsafe {
+            Vec::from_raw_parts(
+                data.as_mut_ptr() as *mut S::Raw,
+                data.len(),
+                data.capacity(),
+            )
+        }
+    }
+}
+
 impl Vector<Any> {
     pub fn data_type(&self) -> DataType {
         Any::DATA_BYTE
@@ -344,9 +394,7 @@ where
     where
         B: bytes::BufMut,
     {
-        for v in self.data.iter() {
-            v.serialize(buffer)?;
-        }
+        S::serialize_slice(&self.data, buffer)?;
         Ok(0)
     }
 
@@ -354,9 +402,7 @@ where
     where
         B: bytes::BufMut,
     {
-        for v in self.data.iter() {
-            v.serialize_le(buffer)?;
-        }
+        S::serialize_slice_le(&self.data, buffer)?;
         Ok(0)
     }
 }
@@ -406,20 +452,14 @@ where
     where
         R: AsyncBufReadExt + Unpin,
     {
-        for slot in self.iter_mut() {
-            slot.deserialize(reader).await?;
-        }
-        Ok(())
+        S::deserialize_slice(&mut self.data, reader).await
     }
 
     async fn deserialize_le<R>(&mut self, reader: &mut R) -> Result<()>
     where
         R: AsyncBufReadExt + Unpin,
     {
-        for slot in self.iter_mut() {
-            slot.deserialize_le(reader).await?;
-        }
-        Ok(())
+        S::deserialize_slice_le(&mut self.data, reader).await
     }
 }
 
@@ -640,7 +680,7 @@ macro_rules! dispatch_reflect {
                     DataType::LongArray => Some(Self::ArrayVector(ArrayVectorImpl::Long(LongArrayVector::new()))),
                     DataType::FloatArray => Some(Self::ArrayVector(ArrayVectorImpl::Float(FloatArrayVector::new()))),
                     DataType::DoubleArray => Some(Self::ArrayVector(ArrayVectorImpl::Double(DoubleArrayVector::new()))),
-                    _ => panic!("Unsupported data type"),
+                    _ => None,
                 }
             }
         }
@@ -694,6 +734,8 @@ macro_rules! for_all_vectors {
     };
 }
 
+pub(crate) use for_all_vectors;
+
 for_all_vectors!(dispatch_data_type);
 
 for_all_vectors!(dispatch_len);
@@ -894,6 +936,15 @@ pub enum PrimitiveType {
     NaiveDateTime(NaiveDateTime),
     NaiveDate(NaiveDate),
     NaiveTime(NaiveTime),
+    /// Datetime with an offset, converted to the client's timezone on writing.
+    ZonedDateTime(chrono::DateTime<FixedOffset>),
+    /// Decimal keeping its own scale.
+    Decimal(Decimal),
+    Blob(Vec<u8>),
+    /// String written to a Symbol column.
+    Symbol(String),
+    /// Scalar of the exact column type.
+    Scalar(ScalarImpl),
 }
 
 impl Display for PrimitiveType {
@@ -915,7 +966,12 @@ impl Display for PrimitiveType {
             PrimitiveType::VecF64(_) => "Vec<f64>(Double[])",
             PrimitiveType::NaiveDateTime(_) => "NaiveDateTime",
             PrimitiveType::NaiveDate(_) => "NaiveDate",
-            PrimitiveType::NaiveTime(_) => "NaiveDate",
+            PrimitiveType::NaiveTime(_) => "NaiveTime",
+            PrimitiveType::ZonedDateTime(_) => "DateTime<Tz>",
+            PrimitiveType::Decimal(_) => "Decimal",
+            PrimitiveType::Blob(_) => "Vec<u8>(Blob)",
+            PrimitiveType::Symbol(_) => "String(Symbol)",
+            PrimitiveType::Scalar(s) => return write!(f, "{}", s.data_type()),
             PrimitiveType::None => "None",
         };
         write!(f, "{type_name}")
@@ -994,9 +1050,128 @@ macro_rules! from_for_array_type {
     };
 }
 
+impl<Tz: TimeZone> From<chrono::DateTime<Tz>> for PrimitiveType {
+    fn from(value: chrono::DateTime<Tz>) -> Self {
+        Self::ZonedDateTime(value.fixed_offset())
+    }
+}
+
+impl From<&str> for PrimitiveType {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<Vec<u8>> for PrimitiveType {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Blob(value)
+    }
+}
+
+impl From<Decimal> for PrimitiveType {
+    fn from(value: Decimal) -> Self {
+        Self::Decimal(value)
+    }
+}
+
+impl From<ScalarImpl> for PrimitiveType {
+    fn from(value: ScalarImpl) -> Self {
+        Self::Scalar(value)
+    }
+}
+
 for_primitive_types!(from_for_primitive_type);
 for_array_types!(from_for_array_type);
 
+/// Coerces a datetime to the precision of the temporal type `data_type`.
+fn datetime_scalar(datetime: NaiveDateTime, data_type: DataType) -> Option<ScalarImpl> {
+    Some(match data_type {
+        DataType::Date => Date::new(datetime.date()).into(),
+        DataType::Month => Month::new(datetime.date()).into(),
+        DataType::Time => Time::new(datetime.time()).into(),
+        DataType::Minute => Minute::new(datetime.time()).into(),
+        DataType::Second => Second::new(datetime.time()).into(),
+        DataType::NanoTime => NanoTime::new(datetime.time()).into(),
+        DataType::DateTime => DateTime::new(datetime).into(),
+        DataType::Timestamp => Timestamp::new(datetime).into(),
+        DataType::NanoTimestamp => NanoTimestamp::new(datetime).into(),
+        DataType::DateHour => DateHour::new(datetime).into(),
+        _ => return None,
+    })
+}
+
+impl PrimitiveType {
+    /// Converts to a scalar of `data_type`.
+    ///
+    /// Chrono values are truncated to the precision of the column, dates alone are midnight
+    /// and zoned datetimes are taken in UTC. Integers are accepted as the internal
+    /// representation of temporals of the same width.
+    pub(crate) fn into_scalar(self, data_type: DataType) -> Result<ScalarImpl> {
+        let invalid = |value: &PrimitiveType| Error::InvalidConvert {
+            from: value.to_string(),
+            to: data_type.to_string(),
+        };
+
+        let scalar = match (self, data_type) {
+            (PrimitiveType::None, t) => ScalarImpl::from_type(t),
+            (PrimitiveType::Bool(v), DataType::Bool) => Some(Bool::new(v).into()),
+            (PrimitiveType::I8(v), DataType::Char) => Some(Char::new(v).into()),
+            (PrimitiveType::I16(v), DataType::Short) => Some(Short::new(v).into()),
+            (PrimitiveType::I32(v), DataType::Int) => Some(Int::new(v).into()),
+            (PrimitiveType::I64(v), DataType::Long) => Some(Long::new(v).into()),
+            (PrimitiveType::F32(v), DataType::Float) => Some(Float::new(v).into()),
+            (PrimitiveType::F64(v), DataType::Double) => Some(Double::new(v).into()),
+            // internal representation of temporals
+            (
+                PrimitiveType::I32(v),
+                t @ (DataType::Date
+                | DataType::Month
+                | DataType::Time
+                | DataType::Minute
+                | DataType::Second
+                | DataType::DateTime
+                | DataType::DateHour),
+            ) => Some(from_temporal_raw(v as i128, t)?),
+            (
+                PrimitiveType::I64(v),
+                t @ (DataType::Timestamp | DataType::NanoTime | DataType::NanoTimestamp),
+            ) => Some(from_temporal_raw(v as i128, t)?),
+            (PrimitiveType::String(v) | PrimitiveType::Symbol(v), DataType::String) => {
+                Some(DolphinString::new(v).into())
+            }
+            (PrimitiveType::String(v) | PrimitiveType::Symbol(v), DataType::Symbol) => {
+                Some(Symbol::new(v).into())
+            }
+            (PrimitiveType::String(v), DataType::Blob) => Some(Blob::new(v.into_bytes()).into()),
+            (PrimitiveType::Blob(v), DataType::Blob) => Some(Blob::new(v).into()),
+            (
+                PrimitiveType::Decimal(v),
+                DataType::Decimal32 | DataType::Decimal64 | DataType::Decimal128,
+            ) => Some(to_decimal(v, data_type, None)?),
+            (PrimitiveType::NaiveDateTime(v), t) => datetime_scalar(v, t),
+            (PrimitiveType::ZonedDateTime(v), t) => datetime_scalar(v.naive_utc(), t),
+            (
+                PrimitiveType::NaiveDate(v),
+                t @ (DataType::Date
+                | DataType::Month
+                | DataType::DateTime
+                | DataType::Timestamp
+                | DataType::NanoTimestamp
+                | DataType::DateHour),
+            ) => datetime_scalar(v.and_time(NaiveTime::MIN), t),
+            (
+                PrimitiveType::NaiveTime(v),
+                t @ (DataType::Time | DataType::Minute | DataType::Second | DataType::NanoTime),
+            ) => datetime_scalar(NaiveDate::default().and_time(v), t),
+            (PrimitiveType::Scalar(v), t) if v.data_type() == t => Some(v),
+            (value, _) => return Err(invalid(&value)),
+        };
+
+        // only None of a non-scalar type remains
+        scalar.ok_or_else(|| invalid(&PrimitiveType::None))
+    }
+}
+
 impl VectorImpl {
     pub fn push(&mut self, value: ConstantImpl) -> Result<(), String> {
         if self.data_type() == Any::data_type() {
@@ -1021,59 +1196,47 @@ impl VectorImpl {
         Ok(())
     }
 
+    /// Pushes a Rust value, which is coerced to the column type.
+    ///
+    /// Temporal columns accept chrono values of any precision, decimal columns accept
+    /// [`Decimal`] and `None` pushes a null, or an empty array into array vectors.
     pub fn push_primitive_type(&mut self, value: PrimitiveType) -> Result<(), Error> {
-        macro_rules! push_simple_type {
-            ($ddb_type:ident, $enum_name:ident) => {
-                if let VectorImpl::$ddb_type(v) = self {
-                    if let PrimitiveType::$enum_name(tmp) = value {
-                        v.push(tmp.into());
-                        return Ok(());
-                    } else if let PrimitiveType::None = value {
-                        v.push($ddb_type::default().into());
-                        return Ok(());
-                    }
-                }
-            };
-            ($(($rust_type:tt, $ddb_type:ident, $enum_name:ident)), *) => {
-                $(
-                    push_simple_type!($ddb_type, $enum_name);
-                )*
-            };
-        }
-
         macro_rules! push_array_type {
-            ($ddb_type:ident, $enum_name:ident, $array_enum_name:ident) => {
-                if let VectorImpl::$ddb_type(v) = self {
-                    if let PrimitiveType::$enum_name(tmp) = value {
-                        v.push(tmp.into());
-                        return Ok(());
-                    } else if let PrimitiveType::None = value {
-                        v.push($ddb_type::default().into());
-                        return Ok(());
-                    }
-                }
+            ($ddb_type:ident, $array_enum_name:ident) => {
                 if let VectorImpl::ArrayVector(ArrayVectorImpl::$ddb_type(a)) = self {
-                    if let PrimitiveType::$array_enum_name(tmp) = value {
-                        a.push(tmp);
-                        return Ok(());
+                    match value {
+                        PrimitiveType::$array_enum_name(tmp) => {
+                            a.push(tmp);
+                            return Ok(());
+                        }
+                        PrimitiveType::None => {
+                            a.push(Vec::new());
+                            return Ok(());
+                        }
+                        _ => {}
                     }
                 }
             };
-            // rust_type is ignored
+            // rust_type and scalar enum are ignored
             ($(($rust_type:tt, $ddb_type:tt, $enum_name:ident, $array_enum_name:ident)), *) => {
                 $(
-                    push_array_type!($ddb_type, $enum_name, $array_enum_name);
+                    push_array_type!($ddb_type, $array_enum_name);
                 )*
             };
         }
 
-        for_primitive_types!(push_simple_type);
         for_array_types!(push_array_type);
 
-        return Err(Error::InvalidConvert {
-            from: value.to_string(),
-            to: self.data_type().to_string(),
-        });
+        if let VectorImpl::ArrayVector(_) | VectorImpl::Any(_) = self {
+            return Err(Error::InvalidConvert {
+                from: value.to_string(),
+                to: self.data_type().to_string(),
+            });
+        }
+
+        let scalar = value.into_scalar(self.data_type())?;
+        self.push_scalar(scalar);
+        Ok(())
     }
 
     pub fn push_unchecked(&mut self, value: ConstantImpl) {
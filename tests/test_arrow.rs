@@ -0,0 +1,182 @@
+#![cfg(feature = "arrow")]
+
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, AsArray, Decimal128Array, Int32Array, ListArray, StringArray,
+        TimestampMicrosecondArray, UInt64Array,
+    },
+    datatypes::{DataType as ArrowDataType, Int32Type, TimeUnit},
+    record_batch::RecordBatch,
+};
+use chrono::{NaiveDate, NaiveTime};
+use dolphindb::types::*;
+
+fn table() -> Table {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let time = date.and_hms_milli_opt(9, 30, 15, 250).unwrap();
+
+    let mut int = IntVector::new();
+    int.push(Int::new(1));
+    int.push(Int::default());
+    let mut sym = SymbolVector::new();
+    sym.push(Symbol::new("a".into()));
+    sym.push(Symbol::new("b".into()));
+    let mut string = StringVector::new();
+    string.push(DolphinString::new("x".into()));
+    string.push(DolphinString::default());
+    let mut month = MonthVector::new();
+    month.push(Month::from_ym(2024, 3).unwrap());
+    month.push(Month::default());
+    let mut minute = MinuteVector::new();
+    minute.push(Minute::from(NaiveTime::from_hms_opt(9, 30, 0).unwrap()));
+    minute.push(Minute::default());
+    let mut date_hour = DateHourVector::new();
+    date_hour.push(DateHour::from(date.and_hms_opt(9, 0, 0).unwrap()));
+    date_hour.push(DateHour::default());
+    let mut timestamp = TimestampVector::new();
+    timestamp.push(Timestamp::from(time));
+    timestamp.push(Timestamp::default());
+    let mut nano_time = NanoTimeVector::new();
+    nano_time.push(NanoTime::from(time.time()));
+    nano_time.push(NanoTime::default());
+    let mut decimal = Decimal64Vector::new();
+    decimal.push(Decimal64::from_raw(12345, 3).unwrap());
+    decimal.push(Decimal64::default());
+    let mut array = IntArrayVector::new();
+    array.push(vec![1, i32::MIN, 3]);
+    array.push(vec![]);
+
+    let mut builder = TableBuilder::new();
+    builder.with_name("t".into()).with_contents(
+        vec![
+            int.into(),
+            sym.into(),
+            string.into(),
+            month.into(),
+            minute.into(),
+            date_hour.into(),
+            timestamp.into(),
+            nano_time.into(),
+            decimal.into(),
+            array.into(),
+        ],
+        vec![
+            "int".into(),
+            "sym".into(),
+            "string".into(),
+            "month".into(),
+            "minute".into(),
+            "date_hour".into(),
+            "timestamp".into(),
+            "nano_time".into(),
+            "decimal".into(),
+            "array".into(),
+        ],
+    );
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_arrow_to_record_batch() {
+    let batch = RecordBatch::try_from(&table()).unwrap();
+    assert_eq!(batch.num_rows(), 2);
+    let types = batch
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.data_type().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        vec![
+            ArrowDataType::Int32,
+            ArrowDataType::Dictionary(
+                Box::new(ArrowDataType::Int32),
+                Box::new(ArrowDataType::Utf8)
+            ),
+            ArrowDataType::Utf8,
+            ArrowDataType::Date32,
+            ArrowDataType::Time32(TimeUnit::Second),
+            ArrowDataType::Timestamp(TimeUnit::Second, None),
+            ArrowDataType::Timestamp(TimeUnit::Millisecond, None),
+            ArrowDataType::Time64(TimeUnit::Nanosecond),
+            ArrowDataType::Decimal128(18, 3),
+            batch.column(9).data_type().clone(),
+        ]
+    );
+    assert!(matches!(
+        batch.column(9).data_type(),
+        ArrowDataType::List(_)
+    ));
+
+    // nulls become validity bitmaps
+    assert_eq!(batch.column(0).null_count(), 1);
+    assert!(batch.column(0).is_null(1));
+    assert_eq!(batch.column(8).null_count(), 1);
+
+    let decimal = batch
+        .column(8)
+        .as_primitive::<arrow::datatypes::Decimal128Type>();
+    assert_eq!(decimal.value(0), 12345);
+
+    let list = batch.column(9).as_list::<i32>();
+    let first = list.value(0);
+    let first = first.as_primitive::<Int32Type>();
+    assert_eq!(
+        first.iter().collect::<Vec<_>>(),
+        vec![Some(1), None, Some(3)]
+    );
+}
+
+#[test]
+fn test_arrow_round_trip() {
+    let table = table();
+    let batch = RecordBatch::try_from(&table).unwrap();
+    assert_eq!(Table::try_from(&batch).unwrap(), table);
+}
+
+#[test]
+fn test_arrow_from_array() {
+    let array: ArrayRef = Arc::new(StringArray::from(vec![Some("a"), None]));
+    let vector = VectorImpl::try_from(array.as_ref()).unwrap();
+    assert_eq!(vector.data_type(), DataType::String);
+    assert_eq!(vector.get(1).unwrap(), DolphinString::default().into());
+
+    let array = TimestampMicrosecondArray::from(vec![Some(1_000_001), None]);
+    let vector = VectorImpl::try_from(&array as &dyn Array).unwrap();
+    assert_eq!(vector.data_type(), DataType::NanoTimestamp);
+    assert_eq!(
+        vector.get(0).unwrap(),
+        NanoTimestamp::from_raw(1_000_001_000).unwrap().into()
+    );
+
+    let array = Decimal128Array::from(vec![Some(-12345), None])
+        .with_precision_and_scale(30, 2)
+        .unwrap();
+    let vector = VectorImpl::try_from(&array as &dyn Array).unwrap();
+    assert_eq!(vector.data_type(), DataType::Decimal128);
+    assert_eq!(
+        vector.get(0).unwrap(),
+        Decimal128::from_raw(-12345, 2).unwrap().into()
+    );
+
+    let array =
+        ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![Some(1), None]), None]);
+    let vector = VectorImpl::try_from(&array as &dyn Array).unwrap();
+    assert_eq!(vector.data_type(), DataType::IntArray);
+
+    let array = Int32Array::from(vec![1, 2]);
+    assert_eq!(VectorImpl::try_from(&array as &dyn Array).unwrap().len(), 2);
+}
+
+#[test]
+fn test_arrow_error() {
+    let array = UInt64Array::from(vec![u64::MAX]);
+    assert!(VectorImpl::try_from(&array as &dyn Array).is_err());
+
+    let mut any = Vector::<Any>::new();
+    any.push(Any::new(Int::new(1).into()));
+    assert!(ArrayRef::try_from(&VectorImpl::from(any)).is_err());
+}
@@ -0,0 +1,108 @@
+mod setup;
+
+use dolphindb::client::{AutoFitTableAppender, ClientBuilder};
+use dolphindb::types::*;
+use setup::settings::Config;
+
+fn table(columns: Vec<VectorImpl>, names: &[&str]) -> Table {
+    let mut builder = TableBuilder::new();
+    builder.with_contents(columns, names.iter().map(|n| n.to_string()).collect());
+    builder.build().unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_auto_fit_table_appender_convert() {
+    const TABLE: &str = "test_auto_fit_table_appender_convert";
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client = builder.connect().await.unwrap();
+    client
+        .run_script(
+            format!(
+                "share table(10:0, `ts`price`sym`qty, [TIMESTAMP, DECIMAL64(2), SYMBOL, LONG]) as {TABLE}"
+            )
+            .as_str(),
+        )
+        .await
+        .unwrap();
+
+    let mut appender = AutoFitTableAppender::new(&mut client, "", TABLE)
+        .await
+        .unwrap();
+    assert_eq!(
+        appender.column_types(),
+        [
+            DataType::Timestamp,
+            DataType::Decimal64,
+            DataType::Symbol,
+            DataType::Long
+        ]
+    );
+
+    let ts: NanoTimestampVector =
+        vec![NanoTimestamp::from_raw(1_710_495_015_250_999_999).unwrap()].into();
+    let price: DoubleVector = vec![Double::new(1.375)].into();
+    let sym: StringVector = vec![DolphinString::new("a".into())].into();
+    let qty: IntVector = vec![Int::new(7)].into();
+    let data = table(
+        vec![ts.into(), price.into(), sym.into(), qty.into()],
+        &["ts", "price", "sym", "qty"],
+    );
+
+    let converted = appender.convert(&data).unwrap();
+    assert_eq!(
+        converted.get_columns_by_index(0),
+        &VectorImpl::from(TimestampVector::from(vec![Timestamp::from_raw(
+            1_710_495_015_250
+        )
+        .unwrap()]))
+    );
+    assert_eq!(
+        converted.get_columns_by_index(1),
+        &VectorImpl::from(Decimal64Vector::from(vec![
+            Decimal64::from_raw(138, 2).unwrap()
+        ]))
+    );
+    assert_eq!(
+        converted.get_columns_by_index(3),
+        &VectorImpl::from(LongVector::from(vec![Long::new(7)]))
+    );
+
+    let res = appender.append(&data).await.unwrap();
+    assert_eq!(res.unwrap(), Int::new(1).into());
+    drop(appender);
+    client
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_auto_fit_table_appender_errors() {
+    const TABLE: &str = "test_auto_fit_table_appender_errors";
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client = builder.connect().await.unwrap();
+    client
+        .run_script(format!("share table(10:0, `data, [SHORT]) as {TABLE}").as_str())
+        .await
+        .unwrap();
+
+    let appender = AutoFitTableAppender::new(&mut client, "", TABLE)
+        .await
+        .unwrap();
+    let overflow: IntVector = vec![Int::new(40_000)].into();
+    assert!(appender
+        .convert(&table(vec![overflow.into()], &["data"]))
+        .is_err());
+    let blob: BlobVector = vec![Blob::new(vec![1])].into();
+    assert!(appender
+        .convert(&table(vec![blob.into()], &["data"]))
+        .is_err());
+    let ints: IntVector = vec![Int::new(1)].into();
+    assert!(appender
+        .convert(&table(vec![ints.clone().into(), ints.into()], &["a", "b"]))
+        .is_err());
+}
@@ -0,0 +1,221 @@
+use dolphindb::types::*;
+
+fn scalar(s: impl Into<ScalarImpl>) -> ScalarImpl {
+    s.into()
+}
+
+#[test]
+fn test_cast_numeric() {
+    assert_eq!(
+        scalar(Int::new(3)).cast(DataType::Double).unwrap(),
+        scalar(Double::new(3.0))
+    );
+    assert_eq!(
+        scalar(Double::new(2.5)).cast(DataType::Int).unwrap(),
+        scalar(Int::new(3))
+    );
+    assert_eq!(
+        scalar(Double::new(-2.5)).cast(DataType::Long).unwrap(),
+        scalar(Long::new(-3))
+    );
+    assert_eq!(
+        scalar(Int::new(0)).cast(DataType::Bool).unwrap(),
+        scalar(Bool::new(false))
+    );
+    assert_eq!(
+        scalar(Bool::new(true)).cast(DataType::Short).unwrap(),
+        scalar(Short::new(1))
+    );
+
+    // nulls are preserved, overflows are errors
+    assert_eq!(
+        scalar(Int::default()).cast(DataType::Double).unwrap(),
+        scalar(Double::default())
+    );
+    assert!(scalar(Int::new(300)).cast(DataType::Char).is_err());
+    assert!(scalar(Long::new(i32::MIN as i64))
+        .cast(DataType::Int)
+        .is_err());
+    assert!(scalar(Double::new(f64::INFINITY))
+        .cast(DataType::Long)
+        .is_err());
+    assert!(scalar(Double::new(1e300)).cast(DataType::Float).is_err());
+}
+
+#[test]
+fn test_cast_temporal() {
+    let timestamp = scalar(Timestamp::from_raw(1_710_495_015_250).unwrap());
+    assert_eq!(
+        timestamp.cast(DataType::Date).unwrap(),
+        scalar(Date::from_ymd(2024, 3, 15).unwrap())
+    );
+    assert_eq!(
+        timestamp.cast(DataType::Month).unwrap(),
+        scalar(Month::from_ym(2024, 3).unwrap())
+    );
+    assert_eq!(
+        timestamp.cast(DataType::Second).unwrap(),
+        scalar(Second::from_hms(9, 30, 15).unwrap())
+    );
+    assert_eq!(
+        timestamp.cast(DataType::DateHour).unwrap(),
+        scalar(DateHour::from_ymd_h(2024, 3, 15, 9).unwrap())
+    );
+
+    // truncates towards the past before the epoch
+    let before_epoch = scalar(Timestamp::from_raw(-1).unwrap());
+    assert_eq!(
+        before_epoch.cast(DataType::Date).unwrap(),
+        scalar(Date::from_ymd(1969, 12, 31).unwrap())
+    );
+    assert_eq!(
+        before_epoch.cast(DataType::Time).unwrap(),
+        scalar(Time::from_hms_milli(23, 59, 59, 999).unwrap())
+    );
+
+    let month = scalar(Month::from_ym(2024, 3).unwrap());
+    assert_eq!(
+        month.cast(DataType::Timestamp).unwrap(),
+        scalar(Timestamp::from_raw(1_709_251_200_000).unwrap())
+    );
+
+    // integers convert by the internal representation
+    assert_eq!(
+        scalar(Int::new(1)).cast(DataType::Date).unwrap(),
+        scalar(Date::from_ymd(1970, 1, 2).unwrap())
+    );
+    assert_eq!(month.cast(DataType::Int).unwrap(), scalar(Int::new(24290)));
+    assert!(scalar(Int::new(86_400)).cast(DataType::Second).is_err());
+
+    assert!(scalar(Date::from_ymd(2024, 3, 15).unwrap())
+        .cast(DataType::Time)
+        .is_err());
+    assert!(scalar(Second::from_hms(9, 30, 15).unwrap())
+        .cast(DataType::Date)
+        .is_err());
+    assert!(scalar(Timestamp::from_raw(4_102_444_800_000).unwrap())
+        .cast(DataType::DateTime)
+        .is_err());
+}
+
+#[test]
+fn test_cast_text() {
+    let symbol = scalar(Symbol::new("a, b".into()));
+    assert_eq!(
+        symbol.cast(DataType::String).unwrap(),
+        scalar(DolphinString::new("a, b".into()))
+    );
+    assert_eq!(
+        scalar(DolphinString::new(" 42 ".into()))
+            .cast(DataType::Int)
+            .unwrap(),
+        scalar(Int::new(42))
+    );
+    assert_eq!(
+        scalar(DolphinString::new("x".into()))
+            .cast(DataType::Int)
+            .unwrap(),
+        scalar(Int::default())
+    );
+    assert_eq!(
+        scalar(DolphinString::new("2024.03.15".into()))
+            .cast(DataType::Date)
+            .unwrap(),
+        scalar(Date::from_ymd(2024, 3, 15).unwrap())
+    );
+    assert_eq!(
+        scalar(Date::from_ymd(2024, 3, 15).unwrap())
+            .cast(DataType::Symbol)
+            .unwrap(),
+        scalar(Symbol::new("2024.03.15".into()))
+    );
+    assert_eq!(
+        scalar(Double::new(1.5)).cast(DataType::String).unwrap(),
+        scalar(DolphinString::new("1.5".into()))
+    );
+    assert_eq!(
+        scalar(Symbol::default()).cast(DataType::String).unwrap(),
+        scalar(DolphinString::default())
+    );
+    assert!(scalar(Blob::new(vec![0xff]))
+        .cast(DataType::String)
+        .is_err());
+    assert!(scalar(Blob::new(vec![1])).cast(DataType::Int).is_err());
+}
+
+#[test]
+fn test_cast_decimal() {
+    let decimal = scalar(Decimal64::from_raw(150, 2).unwrap());
+    assert_eq!(
+        decimal.cast_decimal(DataType::Decimal64, 4).unwrap(),
+        scalar(Decimal64::from_raw(15000, 4).unwrap())
+    );
+    assert_eq!(
+        decimal.cast_decimal(DataType::Decimal32, 0).unwrap(),
+        scalar(Decimal32::from_raw(2, 0).unwrap())
+    );
+    assert_eq!(decimal.cast(DataType::Int).unwrap(), scalar(Int::new(2)));
+    assert_eq!(
+        decimal.cast(DataType::Double).unwrap(),
+        scalar(Double::new(1.5))
+    );
+    assert_eq!(
+        scalar(Double::new(-1.25))
+            .cast_decimal(DataType::Decimal32, 1)
+            .unwrap(),
+        scalar(Decimal32::from_raw(-13, 1).unwrap())
+    );
+    assert_eq!(
+        scalar(DolphinString::new("3.14159".into()))
+            .cast_decimal(DataType::Decimal64, 2)
+            .unwrap(),
+        scalar(Decimal64::from_raw(314, 2).unwrap())
+    );
+
+    assert!(scalar(Decimal64::from_raw(1500, 2).unwrap())
+        .cast_decimal(DataType::Decimal32, 9)
+        .is_err());
+    assert!(decimal.cast_decimal(DataType::Decimal32, 10).is_err());
+    assert!(decimal.cast_decimal(DataType::Int, 2).is_err());
+}
+
+#[test]
+fn test_cast_vector() {
+    let mut ints = IntVector::new();
+    ints.push(Int::new(1));
+    ints.push(Int::default());
+    ints.push(Int::new(3));
+    let ints: VectorImpl = ints.into();
+
+    let mut doubles = DoubleVector::new();
+    doubles.push(Double::new(1.0));
+    doubles.push(Double::default());
+    doubles.push(Double::new(3.0));
+    assert_eq!(ints.cast(DataType::Double).unwrap(), doubles.into());
+
+    let mut symbols = SymbolVector::new();
+    symbols.push(Symbol::new("1".into()));
+    symbols.push(Symbol::default());
+    symbols.push(Symbol::new("3".into()));
+    let symbols: VectorImpl = symbols.into();
+    assert_eq!(ints.cast(DataType::Symbol).unwrap(), symbols);
+    assert_eq!(symbols.cast(DataType::Int).unwrap(), ints);
+
+    let any = ints.cast(DataType::Any).unwrap();
+    assert_eq!(any.data_type(), DataType::Any);
+    assert_eq!(any.cast(DataType::Int).unwrap(), ints);
+
+    let mut decimals = Decimal32Vector::new();
+    decimals.push(Decimal32::from_raw(100, 2).unwrap());
+    decimals.push(Decimal32::default());
+    decimals.push(Decimal32::from_raw(300, 2).unwrap());
+    assert_eq!(
+        ints.cast_decimal(DataType::Decimal32, 2).unwrap(),
+        decimals.into()
+    );
+
+    // the types are checked even without elements
+    assert!(VectorImpl::from(IntVector::new())
+        .cast(DataType::Blob)
+        .is_err());
+}
@@ -0,0 +1,182 @@
+use dolphindb::types::*;
+
+fn ints(values: &[Option<i32>]) -> VectorImpl {
+    let mut v = IntVector::new();
+    for x in values {
+        v.push(x.map(Int::new).unwrap_or_default());
+    }
+    v.into()
+}
+
+fn doubles(values: &[Option<f64>]) -> VectorImpl {
+    let mut v = DoubleVector::new();
+    for x in values {
+        v.push(x.map(Double::new).unwrap_or_default());
+    }
+    v.into()
+}
+
+#[test]
+fn test_compute_aggregate() {
+    let v = ints(&[Some(3), None, Some(1), Some(4)]);
+
+    assert_eq!(v.count(), 3);
+    assert_eq!(v.sum().unwrap(), Long::new(8).into());
+    assert_eq!(v.min().unwrap(), Int::new(1).into());
+    assert_eq!(v.max().unwrap(), Int::new(4).into());
+    assert_eq!(v.first().unwrap(), Int::new(3).into());
+    assert_eq!(v.last().unwrap(), Int::new(4).into());
+
+    let avg = v.avg().unwrap();
+    assert_eq!(avg, Double::new(8.0 / 3.0).into());
+    let std = v.std().unwrap();
+    let std = std.as_double().unwrap().into_inner().unwrap();
+    assert!((std - 1.527525231651947).abs() < 1e-12);
+
+    // all null
+    let nulls = ints(&[None, None]);
+    assert_eq!(nulls.count(), 0);
+    assert_eq!(nulls.sum().unwrap(), Long::default().into());
+    assert_eq!(nulls.avg().unwrap(), Double::default().into());
+    assert_eq!(nulls.min().unwrap(), Int::default().into());
+    assert_eq!(nulls.first().unwrap(), Int::default().into());
+    assert_eq!(ints(&[Some(1)]).std().unwrap(), Double::default().into());
+}
+
+#[test]
+fn test_compute_aggregate_types() {
+    let mut decimals = Decimal64Vector::new();
+    decimals.push(Decimal64::from_raw(150, 2).unwrap());
+    decimals.push(Decimal64::default());
+    decimals.push(Decimal64::from_raw(25, 1).unwrap());
+    let decimals: VectorImpl = decimals.into();
+    assert_eq!(
+        decimals.sum().unwrap(),
+        Decimal64::from_raw(400, 2).unwrap().into()
+    );
+    assert_eq!(decimals.avg().unwrap(), Double::new(2.0).into());
+    assert_eq!(
+        decimals.max().unwrap(),
+        Decimal64::from_raw(25, 1).unwrap().into()
+    );
+
+    let mut dates = DateVector::new();
+    dates.push(Date::from_ymd(2024, 3, 15).unwrap());
+    dates.push(Date::default());
+    dates.push(Date::from_ymd(2023, 1, 1).unwrap());
+    let dates: VectorImpl = dates.into();
+    assert_eq!(
+        dates.min().unwrap(),
+        Date::from_ymd(2023, 1, 1).unwrap().into()
+    );
+    assert_eq!(
+        dates.last().unwrap(),
+        Date::from_ymd(2023, 1, 1).unwrap().into()
+    );
+    assert!(dates.sum().is_err());
+    assert!(dates.avg().is_err());
+
+    let mut symbols = SymbolVector::new();
+    symbols.push(Symbol::new("b".into()));
+    symbols.push(Symbol::new("a".into()));
+    let symbols: VectorImpl = symbols.into();
+    assert_eq!(symbols.min().unwrap(), Symbol::new("a".into()).into());
+    assert!(symbols.sum().is_err());
+}
+
+#[test]
+fn test_compute_arithmetic() {
+    let lhs = ints(&[Some(7), Some(-7), None, Some(1)]);
+    let rhs = ints(&[Some(2), Some(2), Some(1), Some(0)]);
+
+    assert_eq!(
+        lhs.add(&rhs).unwrap(),
+        ints(&[Some(9), Some(-5), None, Some(1)])
+    );
+    assert_eq!(
+        lhs.sub(&rhs).unwrap(),
+        ints(&[Some(5), Some(-9), None, Some(1)])
+    );
+    assert_eq!(
+        lhs.mul(&rhs).unwrap(),
+        ints(&[Some(14), Some(-14), None, Some(0)])
+    );
+    // integer division rounds down, division by zero is null
+    assert_eq!(
+        lhs.div(&rhs).unwrap(),
+        ints(&[Some(3), Some(-4), None, None])
+    );
+
+    assert_eq!(
+        lhs.mul_scalar(&Double::new(0.5).into()).unwrap(),
+        doubles(&[Some(3.5), Some(-3.5), None, Some(0.5)])
+    );
+    assert!(lhs
+        .add_scalar(&Int::new(i32::MAX).into())
+        .unwrap_err()
+        .to_string()
+        .contains("overflows"));
+    assert!(lhs.add(&ints(&[Some(1)])).is_err());
+
+    let mut symbols = SymbolVector::new();
+    symbols.push(Symbol::new("a".into()));
+    assert!(VectorImpl::from(symbols)
+        .add_scalar(&Int::new(1).into())
+        .is_err());
+}
+
+#[test]
+fn test_compute_arithmetic_types() {
+    let mut decimals = Decimal32Vector::new();
+    decimals.push(Decimal32::from_raw(150, 2).unwrap());
+    decimals.push(Decimal32::default());
+    let decimals: VectorImpl = decimals.into();
+
+    let mut expected = Decimal32Vector::new();
+    expected.push(Decimal32::from_raw(250, 2).unwrap());
+    expected.push(Decimal32::default());
+    assert_eq!(
+        decimals.add_scalar(&Int::new(1).into()).unwrap(),
+        expected.into()
+    );
+
+    let mut expected = Decimal32Vector::new();
+    expected.push(Decimal32::from_raw(75, 2).unwrap());
+    expected.push(Decimal32::default());
+    assert_eq!(
+        decimals.div_scalar(&Int::new(2).into()).unwrap(),
+        expected.into()
+    );
+
+    let mut dates = DateVector::new();
+    dates.push(Date::from_ymd(2024, 2, 28).unwrap());
+    dates.push(Date::default());
+    let dates: VectorImpl = dates.into();
+
+    let mut expected = DateVector::new();
+    expected.push(Date::from_ymd(2024, 3, 1).unwrap());
+    expected.push(Date::default());
+    let expected: VectorImpl = expected.into();
+    assert_eq!(dates.add_scalar(&Int::new(2).into()).unwrap(), expected);
+    assert_eq!(expected.sub(&dates).unwrap(), ints(&[Some(2), None]));
+    assert!(dates.mul_scalar(&Int::new(2).into()).is_err());
+}
+
+#[test]
+fn test_compute_decimal_product_scale() {
+    let mut decimals = Decimal32Vector::new();
+    decimals.push(Decimal32::from_raw(150_000, 5).unwrap());
+    let decimals: VectorImpl = decimals.into();
+
+    let product = decimals.mul(&decimals).unwrap();
+    assert_eq!(product.data_type(), DataType::Decimal64);
+    assert_eq!(
+        product.get(0).unwrap(),
+        Decimal64::from_raw(22_500_000_000, 10).unwrap().into()
+    );
+
+    let product = decimals
+        .mul_scalar(&Decimal32::from_raw(2, 0).unwrap().into())
+        .unwrap();
+    assert_eq!(product.data_type(), DataType::Decimal32);
+}
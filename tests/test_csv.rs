@@ -0,0 +1,205 @@
+#![cfg(feature = "csv")]
+
+use chrono::{NaiveDate, NaiveTime};
+use dolphindb::types::*;
+
+fn table() -> Table {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let time = date.and_hms_milli_opt(9, 30, 15, 250).unwrap();
+
+    let mut int = IntVector::new();
+    int.push(Int::new(1));
+    int.push(Int::default());
+    let mut sym = SymbolVector::new();
+    sym.push(Symbol::new("a".into()));
+    sym.push(Symbol::new("b,c".into()));
+    let mut string = StringVector::new();
+    string.push(DolphinString::new("x".into()));
+    string.push(DolphinString::default());
+    let mut dates = DateVector::new();
+    dates.push(Date::from(date));
+    dates.push(Date::default());
+    let mut month = MonthVector::new();
+    month.push(Month::from_ym(2024, 3).unwrap());
+    month.push(Month::default());
+    let mut minute = MinuteVector::new();
+    minute.push(Minute::from(NaiveTime::from_hms_opt(9, 30, 0).unwrap()));
+    minute.push(Minute::default());
+    let mut timestamp = TimestampVector::new();
+    timestamp.push(Timestamp::from(time));
+    timestamp.push(Timestamp::default());
+    let mut decimal = Decimal32Vector::new();
+    decimal.push(Decimal32::from_raw(150, 2).unwrap());
+    decimal.push(Decimal32::default());
+    let mut array = IntArrayVector::new();
+    array.push(vec![1, i32::MIN, 3]);
+    array.push(vec![]);
+
+    let mut builder = TableBuilder::new();
+    builder.with_contents(
+        vec![
+            int.into(),
+            sym.into(),
+            string.into(),
+            dates.into(),
+            month.into(),
+            minute.into(),
+            timestamp.into(),
+            decimal.into(),
+            array.into(),
+        ],
+        vec![
+            "int".into(),
+            "sym".into(),
+            "string".into(),
+            "date".into(),
+            "month".into(),
+            "minute".into(),
+            "timestamp".into(),
+            "decimal".into(),
+            "array".into(),
+        ],
+    );
+    builder.build().unwrap()
+}
+
+fn schema() -> CsvSchema {
+    let mut schema = CsvSchema::new();
+    schema
+        .with_column("sym", DataType::Symbol)
+        .with_decimal("decimal", DataType::Decimal32, 2)
+        .with_column("array", DataType::IntArray);
+    schema
+}
+
+#[test]
+fn test_csv_to_csv() {
+    let mut buffer = Vec::new();
+    table().to_csv(&mut buffer, &CsvOptions::new()).unwrap();
+
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "int,sym,string,date,month,minute,timestamp,decimal,array\n\
+         1,a,x,2024.03.15,2024.03M,09:30m,2024.03.15T09:30:15.250,1.50,\"1,,3\"\n\
+         ,\"b,c\",,,,,,,\n"
+    );
+}
+
+#[test]
+fn test_csv_round_trip() {
+    let table = table();
+    let mut buffer = Vec::new();
+    table.to_csv(&mut buffer, &CsvOptions::new()).unwrap();
+
+    let read = Table::from_csv(buffer.as_slice(), &schema(), &CsvOptions::new()).unwrap();
+    assert_eq!(read, table);
+}
+
+#[test]
+fn test_csv_options() {
+    let mut options = CsvOptions::new();
+    options
+        .with_delimiter(b'\t')
+        .with_array_delimiter('|')
+        .with_header(false)
+        .with_null("NULL")
+        .with_temporal_format(DataType::Date, "%Y-%m-%d");
+
+    let mut buffer = Vec::new();
+    table()
+        .select(["int", "date", "array"])
+        .unwrap()
+        .to_csv(&mut buffer, &options)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(buffer.clone()).unwrap(),
+        "1\t2024-03-15\t1|NULL|3\nNULL\tNULL\t\n"
+    );
+
+    let mut schema = CsvSchema::new();
+    schema.with_column("col2", DataType::IntArray);
+    let read = Table::from_csv(buffer.as_slice(), &schema, &options).unwrap();
+    assert_eq!(read.column_names(), &vec!["col0", "col1", "col2"]);
+    assert_eq!(
+        read.cell(0, "col1"),
+        Some(Date::from_ymd(2024, 3, 15).unwrap().into())
+    );
+    assert_eq!(read.cell(1, 0), Some(Int::default().into()));
+}
+
+#[test]
+fn test_csv_infer() {
+    let csv = "a,b,c,d,e,f,g,h\n\
+               1,3000000000,1.5,true,2024.03.15,09:30:15,2024.03.15T09:30:15.250,x\n\
+               ,,,,,,,\n";
+    let table = Table::from_csv(csv.as_bytes(), &CsvSchema::new(), &CsvOptions::new()).unwrap();
+
+    let types = table
+        .columns()
+        .iter()
+        .map(|c| c.data_type())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        vec![
+            DataType::Int,
+            DataType::Long,
+            DataType::Double,
+            DataType::Bool,
+            DataType::Date,
+            DataType::Second,
+            DataType::Timestamp,
+            DataType::String,
+        ]
+    );
+    assert_eq!(table.cell(1, "a"), Some(Int::default().into()));
+}
+
+#[test]
+fn test_csv_errors() {
+    let csv = "a\nx\n";
+    let mut schema = CsvSchema::new();
+    schema.with_column("a", DataType::Int);
+    assert!(Table::from_csv(csv.as_bytes(), &schema, &CsvOptions::new()).is_err());
+
+    let mut schema = CsvSchema::new();
+    schema.with_column("b", DataType::Int);
+    assert!(Table::from_csv(csv.as_bytes(), &schema, &CsvOptions::new()).is_err());
+
+    let csv = "a,b\n1\n";
+    assert!(Table::from_csv(csv.as_bytes(), &CsvSchema::new(), &CsvOptions::new()).is_err());
+}
+
+#[test]
+fn test_csv_null_token() {
+    // quoted cells are text, unquoted empty cells are null
+    let csv = "s,i\n\"\",1\n,\n";
+    let table = Table::from_csv(csv.as_bytes(), &CsvSchema::new(), &CsvOptions::new()).unwrap();
+    assert_eq!(
+        table.cell(0, "s"),
+        Some(DolphinString::new(String::new()).into())
+    );
+    assert_eq!(table.cell(1, "s"), Some(DolphinString::default().into()));
+    assert_eq!(table.cell(1, "i"), Some(Int::default().into()));
+
+    let mut buffer = Vec::new();
+    table.to_csv(&mut buffer, &CsvOptions::new()).unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), csv);
+
+    // with another null token, empty text cells are empty strings
+    let mut options = CsvOptions::new();
+    options.with_null("NA");
+    let csv = "s,i\nNA,NA\n\"NA\",\n,2\n";
+    let table = Table::from_csv(csv.as_bytes(), &CsvSchema::new(), &options).unwrap();
+    assert_eq!(table.cell(0, "s"), Some(DolphinString::default().into()));
+    assert_eq!(
+        table.cell(1, "s"),
+        Some(DolphinString::new("NA".into()).into())
+    );
+    assert_eq!(
+        table.cell(2, "s"),
+        Some(DolphinString::new(String::new()).into())
+    );
+    assert_eq!(table.cell(1, "i"), Some(Int::default().into()));
+    assert_eq!(table.cell(2, "i"), Some(Int::new(2).into()));
+}
@@ -0,0 +1,183 @@
+#![cfg(feature = "derive")]
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use dolphindb::types::{
+    DataType, Decimal32, Decimal64, DolphinRow, Int, NanoTimestamp, Symbol, Table, TableBuilder,
+    Vector,
+};
+use rust_decimal::Decimal;
+
+#[derive(DolphinRow, Debug, Clone, PartialEq)]
+struct Trade {
+    #[dolphindb(symbol)]
+    sym: String,
+    #[dolphindb(rename = "ts", precision = "nano")]
+    time: NaiveDateTime,
+    date: NaiveDate,
+    #[dolphindb(precision = "second")]
+    second: NaiveTime,
+    #[dolphindb(scale = 4)]
+    price: Decimal,
+    qty: Option<i32>,
+    flags: Vec<i16>,
+    note: String,
+}
+
+fn trades() -> Vec<Trade> {
+    let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+    vec![
+        Trade {
+            sym: "AAPL".into(),
+            time: date.and_hms_nano_opt(9, 30, 0, 123_456_789).unwrap(),
+            date,
+            second: NaiveTime::from_hms_opt(9, 30, 1).unwrap(),
+            price: Decimal::new(105, 1),
+            qty: Some(100),
+            flags: vec![1, 2],
+            note: "first".into(),
+        },
+        Trade {
+            sym: "MSFT".into(),
+            time: date.and_hms_opt(15, 0, 0).unwrap(),
+            date,
+            second: NaiveTime::from_hms_opt(15, 0, 0).unwrap(),
+            price: Decimal::new(-20125, 3),
+            qty: None,
+            flags: vec![],
+            note: String::new(),
+        },
+    ]
+}
+
+#[test]
+fn test_derive_schema() {
+    assert_eq!(
+        Trade::column_names(),
+        vec!["sym", "ts", "date", "second", "price", "qty", "flags", "note"]
+    );
+    assert_eq!(
+        Trade::column_types(),
+        vec![
+            DataType::Symbol,
+            DataType::NanoTimestamp,
+            DataType::Date,
+            DataType::Second,
+            DataType::Decimal64,
+            DataType::Int,
+            DataType::ShortArray,
+            DataType::String
+        ]
+    );
+}
+
+#[test]
+fn test_derive_to_table() {
+    let trades = trades();
+    let table = Trade::to_table(&trades).unwrap();
+    assert_eq!(table.len(), 2);
+    assert_eq!(
+        table.get_columns_by_index(0).get(0).unwrap(),
+        Symbol::new("AAPL".to_string()).into()
+    );
+    assert_eq!(
+        table.get_columns_by_index(1).get(0).unwrap(),
+        NanoTimestamp::from(trades[0].time).into()
+    );
+    assert_eq!(
+        table.get_columns_by_index(4).get(0).unwrap(),
+        Decimal64::from_raw(105000, 4).unwrap().into()
+    );
+    assert_eq!(
+        table.get_columns_by_index(5).get(1).unwrap(),
+        Int::default().into()
+    );
+}
+
+#[test]
+fn test_derive_round_trip() {
+    let table = Trade::to_table(&trades()).unwrap();
+    assert_eq!(Trade::from_table(&table).unwrap(), trades());
+}
+
+#[test]
+fn test_derive_from_table_by_name() {
+    #[derive(DolphinRow, Debug, PartialEq)]
+    struct Partial {
+        qty: Option<i32>,
+        #[dolphindb(rename = "sym")]
+        symbol: String,
+    }
+
+    let table = Trade::to_table(&trades()).unwrap();
+    assert_eq!(
+        Partial::from_table(&table).unwrap(),
+        vec![
+            Partial {
+                qty: Some(100),
+                symbol: "AAPL".into()
+            },
+            Partial {
+                qty: None,
+                symbol: "MSFT".into()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_derive_error() {
+    #[derive(DolphinRow, Debug)]
+    #[allow(dead_code)]
+    struct Row {
+        id: i32,
+    }
+
+    let mut id = Vector::<Int>::new();
+    id.push(Int::new(1));
+    id.push(Int::default());
+    let mut builder = TableBuilder::new();
+    builder.with_contents(vec![id.into()], vec!["id".into()]);
+    let table: Table = builder.build().unwrap();
+    // null into non-nullable field
+    assert!(Row::from_table(&table).is_err());
+
+    let mut builder = TableBuilder::new();
+    builder.with_contents(vec![Vector::<Int>::new().into()], vec!["other".into()]);
+    // missing column
+    assert!(Row::from_table(&builder.build().unwrap()).is_err());
+}
+
+#[test]
+fn test_derive_decimal_precision() {
+    #[derive(DolphinRow, Debug, PartialEq)]
+    struct Prices {
+        #[dolphindb(precision = 9, scale = 2)]
+        small: Decimal,
+        #[dolphindb(precision = 38)]
+        large: Option<Decimal>,
+    }
+
+    assert_eq!(
+        Prices::column_types(),
+        vec![DataType::Decimal32, DataType::Decimal128]
+    );
+    let prices = vec![Prices {
+        small: Decimal::new(105, 1),
+        large: Some(Decimal::new(i64::MAX, 0) * Decimal::new(100, 0)),
+    }];
+    let table = Prices::to_table(&prices).unwrap();
+    assert_eq!(
+        table.get_columns_by_index(0).get(0).unwrap(),
+        Decimal32::from_raw(1050, 2).unwrap().into()
+    );
+    assert_eq!(Prices::from_table(&table).unwrap(), prices);
+
+    // a column of another width does not fit the precision
+    #[derive(DolphinRow, Debug)]
+    #[allow(dead_code)]
+    struct Wide {
+        #[dolphindb(rename = "small", precision = 18)]
+        price: Decimal,
+    }
+    assert!(Wide::from_table(&table).is_err());
+}
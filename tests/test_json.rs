@@ -0,0 +1,195 @@
+#![cfg(feature = "json")]
+
+use chrono::NaiveDate;
+use dolphindb::types::*;
+use serde_json::json;
+
+fn table() -> Table {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+
+    let mut int = IntVector::new();
+    int.push(Int::new(1));
+    int.push(Int::default());
+    let mut sym = SymbolVector::new();
+    sym.push(Symbol::new("a".into()));
+    sym.push(Symbol::new("b".into()));
+    let mut timestamp = TimestampVector::new();
+    timestamp.push(Timestamp::from(
+        date.and_hms_milli_opt(9, 30, 15, 250).unwrap(),
+    ));
+    timestamp.push(Timestamp::default());
+    let mut decimal = Decimal64Vector::new();
+    decimal.push(Decimal64::from_raw(150, 2).unwrap());
+    decimal.push(Decimal64::default());
+    let mut array = IntArrayVector::new();
+    array.push(vec![1, i32::MIN]);
+    array.push(vec![]);
+
+    let mut builder = TableBuilder::new();
+    builder.with_contents(
+        vec![
+            int.into(),
+            sym.into(),
+            timestamp.into(),
+            decimal.into(),
+            array.into(),
+        ],
+        vec![
+            "int".into(),
+            "sym".into(),
+            "timestamp".into(),
+            "decimal".into(),
+            "array".into(),
+        ],
+    );
+    builder.build().unwrap()
+}
+
+fn table_type() -> JsonType {
+    JsonType::Table(vec![
+        ("int".into(), DataType::Int),
+        ("sym".into(), DataType::Symbol),
+        ("timestamp".into(), DataType::Timestamp),
+        ("decimal".into(), DataType::Decimal64),
+        ("array".into(), DataType::IntArray),
+    ])
+}
+
+#[test]
+fn test_json_scalar() {
+    let date: ConstantImpl = Date::from_ymd(2024, 3, 15).unwrap().into();
+    assert_eq!(date.to_json().unwrap(), json!("2024-03-15"));
+
+    let mut options = JsonOptions::new();
+    options.with_temporal_format(JsonTemporalFormat::DolphinDB);
+    assert_eq!(date.to_json_with(&options).unwrap(), json!("2024.03.15"));
+    assert_eq!(
+        ConstantImpl::from_json_with(
+            &json!("2024.03.15"),
+            &JsonType::Scalar(DataType::Date),
+            &options
+        )
+        .unwrap(),
+        date
+    );
+
+    options.with_temporal_format(JsonTemporalFormat::Elapsed);
+    assert_eq!(date.to_json_with(&options).unwrap(), json!(19797));
+
+    let null: ConstantImpl = Int::default().into();
+    assert_eq!(null.to_json().unwrap(), json!(null));
+    assert_eq!(
+        ConstantImpl::from_json(&json!(null), &JsonType::Scalar(DataType::Int)).unwrap(),
+        null
+    );
+    assert_eq!(
+        ConstantImpl::from_json(&json!("42"), &JsonType::Scalar(DataType::Int)).unwrap(),
+        Int::new(42).into()
+    );
+    assert!(ConstantImpl::from_json(&json!(300), &JsonType::Scalar(DataType::Char)).is_err());
+    assert!(ConstantImpl::from_json(&json!("x"), &JsonType::Scalar(DataType::Int)).is_err());
+}
+
+#[test]
+fn test_json_decimal() {
+    let decimal: ConstantImpl = Decimal32::from_raw(150, 2).unwrap().into();
+    assert_eq!(decimal.to_json().unwrap(), json!(1.5));
+
+    let mut options = JsonOptions::new();
+    options.with_decimal_as_string(true);
+    assert_eq!(decimal.to_json_with(&options).unwrap(), json!("1.50"));
+    assert_eq!(
+        ConstantImpl::from_json(&json!("1.50"), &JsonType::Scalar(DataType::Decimal32)).unwrap(),
+        decimal
+    );
+}
+
+#[test]
+fn test_json_vector_pair_set() {
+    let mut vector = IntVector::new();
+    vector.push(Int::new(1));
+    vector.push(Int::default());
+    vector.push(Int::new(3));
+    let vector: ConstantImpl = vector.into();
+    assert_eq!(vector.to_json().unwrap(), json!([1, null, 3]));
+    assert_eq!(
+        ConstantImpl::from_json(&json!([1, null, 3]), &JsonType::Vector(DataType::Int)).unwrap(),
+        vector
+    );
+
+    let any = ConstantImpl::from_json(&json!([1, "a", [true]]), &JsonType::Vector(DataType::Any))
+        .unwrap();
+    assert_eq!(any.data_type(), DataType::Any);
+    assert_eq!(any.to_json().unwrap(), json!([1, "a", [true]]));
+
+    let pair: ConstantImpl = PairImpl::from(Pair::new((Int::new(1), Int::new(2)))).into();
+    assert_eq!(pair.to_json().unwrap(), json!([1, 2]));
+    assert_eq!(
+        ConstantImpl::from_json(&json!([1, 2]), &JsonType::Pair(DataType::Int)).unwrap(),
+        pair
+    );
+    assert!(ConstantImpl::from_json(&json!([1]), &JsonType::Pair(DataType::Int)).is_err());
+
+    let mut set = Set::new();
+    set.insert(Symbol::new("a".into()));
+    let set: ConstantImpl = SetImpl::from(set).into();
+    assert_eq!(set.to_json().unwrap(), json!(["a"]));
+    assert_eq!(
+        ConstantImpl::from_json(&json!(["a"]), &JsonType::Set(DataType::Symbol)).unwrap(),
+        set
+    );
+}
+
+#[test]
+fn test_json_dictionary() {
+    let mut dict = Dictionary::new();
+    dict.insert(Int::new(1), DolphinString::new("a".into()));
+    dict.insert(Int::new(2), DolphinString::new("b".into()));
+    let dict: ConstantImpl = DictionaryImpl::from(dict).into();
+
+    assert_eq!(dict.to_json().unwrap(), json!({"1": "a", "2": "b"}));
+    assert_eq!(
+        ConstantImpl::from_json(
+            &json!({"1": "a", "2": "b"}),
+            &JsonType::Dictionary(DataType::Int, DataType::String)
+        )
+        .unwrap(),
+        dict
+    );
+}
+
+#[test]
+fn test_json_table() {
+    let table: ConstantImpl = table().into();
+
+    let columns = json!({
+        "int": [1, null],
+        "sym": ["a", "b"],
+        "timestamp": ["2024-03-15T09:30:15.250", null],
+        "decimal": [1.5, null],
+        "array": [[1, null], []],
+    });
+    assert_eq!(table.to_json().unwrap(), columns);
+    assert_eq!(
+        ConstantImpl::from_json(&columns, &table_type()).unwrap(),
+        table
+    );
+
+    let mut options = JsonOptions::new();
+    options.with_table_orient(JsonTableOrient::Records);
+    let records = json!([
+        {"int": 1, "sym": "a", "timestamp": "2024-03-15T09:30:15.250", "decimal": 1.5, "array": [1, null]},
+        {"int": null, "sym": "b", "timestamp": null, "decimal": null, "array": []},
+    ]);
+    assert_eq!(table.to_json_with(&options).unwrap(), records);
+    assert_eq!(
+        ConstantImpl::from_json(&records, &table_type()).unwrap(),
+        table
+    );
+
+    // missing fields of records are null
+    let records = json!([{"int": 1}, {"sym": "b"}]);
+    let read = ConstantImpl::from_json(&records, &table_type()).unwrap();
+    assert_eq!(read.len(), 2);
+    assert!(ConstantImpl::from_json(&json!({"int": [1]}), &table_type()).is_err());
+}
@@ -0,0 +1,308 @@
+mod setup;
+
+use std::time::Duration;
+
+use dolphindb::client::{Client, ClientBuilder, MultithreadedTableWriterBuilder};
+use dolphindb::types::*;
+use setup::settings::Config;
+
+async fn connect(conf: &Config) -> (ClientBuilder<'_, String>, Client) {
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let client = builder.clone().connect().await.unwrap();
+    (builder, client)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multithreaded_table_writer_shared_table() {
+    let conf = Config::new();
+    let (builder, mut client) = connect(&conf).await;
+    client
+        .run_script("share table(10:0, `id`price, [INT, DOUBLE]) as mtw_shared")
+        .await
+        .unwrap();
+
+    let mut writer = MultithreadedTableWriterBuilder::new("", "mtw_shared");
+    writer
+        .with_batch_size(10)
+        .with_flush_interval(Duration::from_millis(10));
+    let mut writer = writer.build(&builder).await.unwrap();
+    assert_eq!(writer.column_names(), ["id", "price"]);
+
+    for i in 0..25 {
+        writer
+            .insert(vec![PrimitiveType::I32(i), PrimitiveType::F64(i as f64)])
+            .unwrap();
+    }
+    writer.wait_for_completion().await.unwrap();
+
+    let status = writer.status();
+    assert_eq!(status.sent_rows, 25);
+    assert_eq!(status.unsent_rows, 0);
+    assert!(status.error.is_none());
+    assert!(writer.insert(vec![1.into(), 1.0.into()]).is_err());
+
+    let res = client
+        .run_script("(exec count(*) from mtw_shared) == 25")
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+    client
+        .run_script("undef(`mtw_shared, SHARED)")
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multithreaded_table_writer_dfs_table() {
+    let conf = Config::new();
+    let (builder, mut client) = connect(&conf).await;
+    client
+        .run_script(
+            r#"
+            if (existsDatabase("dfs://mtw_dfs")) { dropDatabase("dfs://mtw_dfs") }
+            db = database("dfs://mtw_dfs", VALUE, `a`b`c`d)
+            t = table(10:0, `sym`price, [SYMBOL, DOUBLE])
+            db.createPartitionedTable(t, `pt, `sym)
+            "#,
+        )
+        .await
+        .unwrap();
+
+    let mut writer = MultithreadedTableWriterBuilder::new("dfs://mtw_dfs", "pt");
+    writer
+        .with_partition_column("sym")
+        .with_thread_count(3)
+        .with_batch_size(7);
+    let mut writer = writer.build(&builder).await.unwrap();
+
+    for i in 0..100 {
+        let sym = ["a", "b", "c", "d"][i % 4];
+        writer.insert(vec![sym.into(), (i as f64).into()]).unwrap();
+    }
+    writer.wait_for_completion().await.unwrap();
+
+    assert_eq!(writer.thread_status().len(), 3);
+    assert_eq!(writer.status().sent_rows, 100);
+    let res = client
+        .run_script(r#"(exec count(*) from loadTable("dfs://mtw_dfs", `pt) where sym = `b) == 25"#)
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multithreaded_table_writer_errors() {
+    let conf = Config::new();
+    let (builder, mut client) = connect(&conf).await;
+    client
+        .run_script("share table(10:0, `sym`price, [SYMBOL, DOUBLE]) as mtw_errors")
+        .await
+        .unwrap();
+
+    let mut writer = MultithreadedTableWriterBuilder::new("", "mtw_errors");
+    writer.with_thread_count(2);
+    assert!(writer.build(&builder).await.is_err());
+    writer.with_partition_column("unknown");
+    assert!(writer.build(&builder).await.is_err());
+
+    writer.with_partition_column("sym");
+    let mut writer = writer.build(&builder).await.unwrap();
+    assert!(writer.insert(vec!["a".into()]).is_err());
+    assert!(writer.insert(vec!["a".into(), "b".into()]).is_err());
+    writer.insert(vec!["a".into(), 1.0.into()]).unwrap();
+    writer.wait_for_completion().await.unwrap();
+    assert_eq!(writer.status().sent_rows, 1);
+    client
+        .run_script("undef(`mtw_errors, SHARED)")
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multithreaded_table_writer_range_partitions() {
+    let conf = Config::new();
+    let (builder, mut client) = connect(&conf).await;
+    client
+        .run_script(
+            r#"
+            if (existsDatabase("dfs://mtw_range")) { dropDatabase("dfs://mtw_range") }
+            db = database("dfs://mtw_range", RANGE, 0 10 20 30)
+            t = table(10:0, `id`price, [INT, DOUBLE])
+            db.createPartitionedTable(t, `pt, `id)
+            "#,
+        )
+        .await
+        .unwrap();
+
+    let mut writer = MultithreadedTableWriterBuilder::new("dfs://mtw_range", "pt");
+    writer
+        .with_partition_column("id")
+        .with_thread_count(2)
+        .with_batch_size(5);
+    let mut writer = writer.build(&builder).await.unwrap();
+
+    for i in 0..30 {
+        writer
+            .insert(vec![PrimitiveType::I32(i), PrimitiveType::F64(i as f64)])
+            .unwrap();
+    }
+    writer.wait_for_completion().await.unwrap();
+
+    // each partition of ten ids is written by a single writer
+    for status in writer.thread_status() {
+        assert_eq!(status.sent_rows % 10, 0);
+    }
+    assert_eq!(writer.status().sent_rows, 30);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multithreaded_table_writer_take_failed() {
+    let conf = Config::new();
+    let (builder, mut client) = connect(&conf).await;
+    client
+        .run_script("share table(10:0, `id`price, [INT, DOUBLE]) as mtw_take_failed")
+        .await
+        .unwrap();
+
+    let mut writer = MultithreadedTableWriterBuilder::new("", "mtw_take_failed");
+    writer
+        .with_batch_size(100)
+        .with_flush_interval(Duration::from_secs(60));
+    let mut writer = writer.build(&builder).await.unwrap();
+    client
+        .run_script("undef(`mtw_take_failed, SHARED)")
+        .await
+        .unwrap();
+
+    for i in 0..3 {
+        writer
+            .insert(vec![PrimitiveType::I32(i), PrimitiveType::F64(i as f64)])
+            .unwrap();
+    }
+    assert!(writer.wait_for_completion().await.is_err());
+    // the failure is reported again instead of awaiting the writers twice
+    assert!(writer.wait_for_completion().await.is_err());
+    assert_eq!(writer.status().unsent_rows, 3);
+
+    let failed = writer.take_failed().unwrap();
+    assert_eq!(*failed.column_names(), ["id", "price"]);
+    assert_eq!(
+        failed.get_columns_by_index(0),
+        &VectorImpl::from(IntVector::from(vec![Int::new(0), Int::new(1), Int::new(2)]))
+    );
+    assert_eq!(writer.status().unsent_rows, 0);
+    assert_eq!(writer.take_failed().unwrap().len(), 0);
+}
+
+#[cfg(feature = "derive")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multithreaded_table_writer_insert_rows() {
+    #[derive(DolphinRow)]
+    struct Price {
+        id: i32,
+        price: f64,
+    }
+
+    let conf = Config::new();
+    let (builder, mut client) = connect(&conf).await;
+    client
+        .run_script(
+            r#"
+            if (existsDatabase("dfs://mtw_insert_rows")) { dropDatabase("dfs://mtw_insert_rows") }
+            db = database("dfs://mtw_insert_rows", RANGE, 0 10 20 30)
+            t = table(10:0, `id`price, [INT, DOUBLE])
+            db.createPartitionedTable(t, `pt, `id)
+            "#,
+        )
+        .await
+        .unwrap();
+
+    let mut writer = MultithreadedTableWriterBuilder::new("dfs://mtw_insert_rows", "pt");
+    writer
+        .with_partition_column("id")
+        .with_thread_count(2)
+        .with_batch_size(5);
+    let mut writer = writer.build(&builder).await.unwrap();
+
+    let rows = (0..30)
+        .map(|id| Price {
+            id,
+            price: id as f64,
+        })
+        .collect::<Vec<_>>();
+    writer.insert_rows(&rows).unwrap();
+    writer.wait_for_completion().await.unwrap();
+
+    for status in writer.thread_status() {
+        assert_eq!(status.sent_rows % 10, 0);
+    }
+    assert_eq!(writer.status().sent_rows, 30);
+
+    // fields must match the columns
+    #[derive(DolphinRow)]
+    struct Other {
+        price: f64,
+    }
+    assert!(writer.insert_rows(&[Other { price: 1.0 }]).is_err());
+}
+
+#[cfg(feature = "derive")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multithreaded_table_writer_insert_rows_symbol_decimal() {
+    use rust_decimal::Decimal;
+
+    #[derive(DolphinRow)]
+    struct Quote {
+        #[dolphindb(symbol)]
+        sym: String,
+        #[dolphindb(scale = 2)]
+        price: Decimal,
+    }
+
+    let conf = Config::new();
+    let (builder, mut client) = connect(&conf).await;
+    client
+        .run_script(
+            r#"
+            if (existsDatabase("dfs://mtw_insert_rows_sym")) { dropDatabase("dfs://mtw_insert_rows_sym") }
+            db = database("dfs://mtw_insert_rows_sym", VALUE, `a`b)
+            t = table(10:0, `sym`price, [SYMBOL, DECIMAL64(4)])
+            db.createPartitionedTable(t, `pt, `sym)
+            "#,
+        )
+        .await
+        .unwrap();
+
+    let mut writer = MultithreadedTableWriterBuilder::new("dfs://mtw_insert_rows_sym", "pt");
+    writer
+        .with_partition_column("sym")
+        .with_thread_count(2)
+        .with_batch_size(2);
+    let mut writer = writer.build(&builder).await.unwrap();
+
+    let rows = ["a", "b", "a", "b"]
+        .iter()
+        .enumerate()
+        .map(|(i, sym)| Quote {
+            sym: sym.to_string(),
+            price: Decimal::new(i as i64 * 100 + 25, 2),
+        })
+        .collect::<Vec<_>>();
+    writer.insert_rows(&rows).unwrap();
+    writer.wait_for_completion().await.unwrap();
+    assert_eq!(writer.status().sent_rows, 4);
+
+    let prices = client
+        .run_script(
+            r#"exec price from loadTable("dfs://mtw_insert_rows_sym", "pt") order by price"#,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    let mut expected = Decimal64Vector::new();
+    for i in 0..4 {
+        expected.push(Decimal64::from_raw(i * 10000 + 2500, 4).unwrap());
+    }
+    assert_eq!(prices, ConstantImpl::from(VectorImpl::from(expected)));
+}
@@ -0,0 +1,220 @@
+#![cfg(feature = "polars")]
+
+mod setup;
+
+use chrono::NaiveDate;
+use dolphindb::{client::ClientBuilder, types::*};
+use polars::prelude::{DataFrame, DataType as PolarsDataType, NamedFrom, Series, TimeUnit};
+use setup::settings::Config;
+
+fn table() -> Table {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let time = date.and_hms_nano_opt(9, 30, 15, 123_456_789).unwrap();
+
+    let mut int = IntVector::new();
+    int.push(Int::new(1));
+    int.push(Int::default());
+    let mut sym = SymbolVector::new();
+    sym.push(Symbol::new("a".into()));
+    sym.push(Symbol::new("b".into()));
+    let mut string = StringVector::new();
+    string.push(DolphinString::new("x".into()));
+    string.push(DolphinString::default());
+    let mut day = DateVector::new();
+    day.push(Date::from(date));
+    day.push(Date::default());
+    let mut timestamp = TimestampVector::new();
+    timestamp.push(Timestamp::from(
+        date.and_hms_milli_opt(9, 30, 15, 123).unwrap(),
+    ));
+    timestamp.push(Timestamp::default());
+    let mut nano_timestamp = NanoTimestampVector::new();
+    nano_timestamp.push(NanoTimestamp::from(time));
+    nano_timestamp.push(NanoTimestamp::default());
+    let mut nano_time = NanoTimeVector::new();
+    nano_time.push(NanoTime::from(time.time()));
+    nano_time.push(NanoTime::default());
+    let mut decimal = Decimal64Vector::new();
+    decimal.push(Decimal64::from_raw(12345, 3).unwrap());
+    decimal.push(Decimal64::default());
+    let mut array = DoubleArrayVector::new();
+    array.push(vec![1.5, f64::MIN]);
+    array.push(vec![]);
+
+    let mut builder = TableBuilder::new();
+    builder.with_contents(
+        vec![
+            int.into(),
+            sym.into(),
+            string.into(),
+            day.into(),
+            timestamp.into(),
+            nano_timestamp.into(),
+            nano_time.into(),
+            decimal.into(),
+            array.into(),
+        ],
+        vec![
+            "int".into(),
+            "sym".into(),
+            "string".into(),
+            "date".into(),
+            "timestamp".into(),
+            "nano_timestamp".into(),
+            "nano_time".into(),
+            "decimal".into(),
+            "array".into(),
+        ],
+    );
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_polars_to_data_frame() {
+    let df = DataFrame::try_from(&table()).unwrap();
+    assert_eq!(df.shape(), (2, 9));
+    assert_eq!(df.column("int").unwrap().dtype(), &PolarsDataType::Int32);
+    assert!(matches!(
+        df.column("sym").unwrap().dtype(),
+        PolarsDataType::Categorical(_, _)
+    ));
+    assert_eq!(df.column("date").unwrap().dtype(), &PolarsDataType::Date);
+    assert_eq!(
+        df.column("nano_timestamp").unwrap().dtype(),
+        &PolarsDataType::Datetime(TimeUnit::Nanoseconds, None)
+    );
+    assert_eq!(
+        df.column("nano_time").unwrap().dtype(),
+        &PolarsDataType::Time
+    );
+    assert_eq!(
+        df.column("decimal").unwrap().dtype(),
+        &PolarsDataType::Decimal(Some(18), Some(3))
+    );
+    assert_eq!(
+        df.column("array").unwrap().dtype(),
+        &PolarsDataType::List(Box::new(PolarsDataType::Float64))
+    );
+    assert_eq!(df.column("int").unwrap().null_count(), 1);
+    assert_eq!(df.column("string").unwrap().null_count(), 1);
+}
+
+#[test]
+fn test_polars_round_trip() {
+    let table = table();
+    let df = DataFrame::try_from(&table).unwrap();
+    assert_eq!(Table::try_from(df).unwrap(), table);
+}
+
+#[test]
+fn test_polars_round_trip_types() {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let time = date.and_hms_opt(9, 30, 15).unwrap();
+
+    let mut month = MonthVector::new();
+    month.push(Month::from(date));
+    month.push(Month::default());
+    let mut minute = MinuteVector::new();
+    minute.push(Minute::from(time.time()));
+    let mut second = SecondVector::new();
+    second.push(Second::from(time.time()));
+    let mut time_of_day = TimeVector::new();
+    time_of_day.push(Time::from(time.time()));
+    let mut date_time = DateTimeVector::new();
+    date_time.push(DateTime::from(time));
+    let mut date_hour = DateHourVector::new();
+    date_hour.push(DateHour::from(time));
+    let mut decimal = Decimal32Vector::new();
+    decimal.push(Decimal32::from_raw(15, 1).unwrap());
+    minute.push(Minute::default());
+    second.push(Second::default());
+    time_of_day.push(Time::default());
+    date_time.push(DateTime::default());
+    date_hour.push(DateHour::default());
+    decimal.push(Decimal32::default());
+
+    let mut builder = TableBuilder::new();
+    builder.with_contents(
+        vec![
+            month.into(),
+            minute.into(),
+            second.into(),
+            time_of_day.into(),
+            date_time.into(),
+            date_hour.into(),
+            decimal.into(),
+        ],
+        vec![
+            "month".into(),
+            "minute".into(),
+            "second".into(),
+            "time".into(),
+            "date_time".into(),
+            "date_hour".into(),
+            "decimal".into(),
+        ],
+    );
+    let table = builder.build().unwrap();
+
+    let df = DataFrame::try_from(&table).unwrap();
+    let restored = Table::try_from(&df).unwrap();
+    assert_eq!(restored.get_columns_by_index(0).data_type(), DataType::Date);
+    assert_eq!(
+        restored.get_columns_by_index(4).data_type(),
+        DataType::Timestamp
+    );
+
+    let types = table
+        .column_names()
+        .iter()
+        .zip(table.columns())
+        .map(|(name, column)| (name.as_str(), column.data_type()))
+        .collect::<Vec<_>>();
+    assert_eq!(data_frame_to_table(&df, &types).unwrap(), table);
+}
+
+#[test]
+fn test_polars_decimal_precision() {
+    let mut decimal = Decimal32Vector::new();
+    decimal.push(Decimal32::from_raw(15, 1).unwrap());
+    decimal.push(Decimal32::from_raw(1, 9).unwrap());
+    assert!(vector_to_series("decimal", &decimal.into()).is_err());
+}
+
+#[test]
+fn test_polars_from_data_frame() {
+    let df = DataFrame::new(vec![
+        Series::new("u".into(), &[1u32, 2]).into(),
+        Series::new("f".into(), &[Some(1.5f32), None]).into(),
+    ])
+    .unwrap();
+    let table = Table::try_from(&df).unwrap();
+    assert_eq!(table.column_names(), &["u", "f"]);
+    assert_eq!(table.get_columns_by_index(0).data_type(), DataType::Long);
+    assert_eq!(
+        table.get_columns_by_index(1).get(1).unwrap(),
+        Float::default().into()
+    );
+
+    let df = DataFrame::new(vec![Series::new("u".into(), &[u64::MAX]).into()]).unwrap();
+    assert!(Table::try_from(&df).is_err());
+}
+
+#[tokio::test]
+async fn test_polars_query_df() {
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client = builder.connect().await.unwrap();
+    let df = client
+        .query_df("table(1..3 as id, symbol(`a`b`c) as sym, [1.5, NULL, 2.5] as price)")
+        .await
+        .unwrap();
+    assert_eq!(df.shape(), (3, 3));
+    assert!(matches!(
+        df.column("sym").unwrap().dtype(),
+        PolarsDataType::Categorical(_, _)
+    ));
+    assert_eq!(df.column("price").unwrap().null_count(), 1);
+    assert!(client.query_df("1 + 1").await.is_err());
+}
@@ -0,0 +1,154 @@
+use chrono::{FixedOffset, NaiveDate, TimeZone};
+use dolphindb::types::*;
+use rust_decimal::Decimal;
+
+fn pushed(vector: impl Into<VectorImpl>, value: impl Into<PrimitiveType>) -> VectorImpl {
+    let mut vector = vector.into();
+    vector.push_primitive_type(value.into()).unwrap();
+    vector
+}
+
+fn single<S>(scalar: S) -> VectorImpl
+where
+    Vector<S>: Into<VectorImpl>,
+{
+    Vector::from(vec![scalar]).into()
+}
+
+#[test]
+fn test_primitive_type_temporal() {
+    let datetime = NaiveDate::from_ymd_opt(2024, 3, 15)
+        .unwrap()
+        .and_hms_nano_opt(9, 30, 15, 250_000_001)
+        .unwrap();
+
+    assert_eq!(
+        pushed(TimestampVector::new(), datetime),
+        single(Timestamp::from_raw(1_710_495_015_250).unwrap())
+    );
+    assert_eq!(
+        pushed(NanoTimestampVector::new(), datetime),
+        single(NanoTimestamp::from_raw(1_710_495_015_250_000_001).unwrap())
+    );
+    assert_eq!(
+        pushed(DateVector::new(), datetime),
+        single(Date::from_ymd(2024, 3, 15).unwrap())
+    );
+    assert_eq!(
+        pushed(SecondVector::new(), datetime),
+        single(Second::from_hms(9, 30, 15).unwrap())
+    );
+    assert_eq!(
+        pushed(NanoTimeVector::new(), datetime.time()),
+        single(NanoTime::from_hms_nano(9, 30, 15, 250_000_001).unwrap())
+    );
+    assert_eq!(
+        pushed(MonthVector::new(), datetime.date()),
+        single(Month::from_ym(2024, 3).unwrap())
+    );
+    assert_eq!(
+        pushed(DateHourVector::new(), datetime.date()),
+        single(DateHour::from_ymd_h(2024, 3, 15, 0).unwrap())
+    );
+
+    // zoned datetimes are taken in UTC
+    let zoned = FixedOffset::east_opt(8 * 3600)
+        .unwrap()
+        .from_local_datetime(&datetime)
+        .unwrap();
+    assert_eq!(
+        pushed(DateTimeVector::new(), zoned),
+        single(DateTime::from(datetime - chrono::Duration::hours(8)))
+    );
+
+    // integers are the internal representation
+    assert_eq!(
+        pushed(DateVector::new(), 1),
+        single(Date::from_ymd(1970, 1, 2).unwrap())
+    );
+    assert_eq!(pushed(TimestampVector::new(), 0i64).len(), 1);
+
+    let mut dates = VectorImpl::from(DateVector::new());
+    assert!(dates.push_primitive_type(datetime.time().into()).is_err());
+    assert!(dates.push_primitive_type(1i64.into()).is_err());
+    assert!(dates.push_primitive_type("2024.03.15".into()).is_err());
+}
+
+#[test]
+fn test_primitive_type_literal() {
+    assert_eq!(
+        pushed(SymbolVector::new(), "a"),
+        single(Symbol::new("a".into()))
+    );
+    assert_eq!(
+        pushed(StringVector::new(), PrimitiveType::Symbol("a".into())),
+        single(DolphinString::new("a".into()))
+    );
+    assert_eq!(
+        pushed(BlobVector::new(), vec![0u8, 1]),
+        single(Blob::new(vec![0, 1]))
+    );
+    assert_eq!(
+        pushed(BlobVector::new(), "a"),
+        single(Blob::new(b"a".to_vec()))
+    );
+
+    let mut strings = VectorImpl::from(StringVector::new());
+    assert!(strings.push_primitive_type(vec![0u8].into()).is_err());
+}
+
+#[test]
+fn test_primitive_type_decimal() {
+    assert_eq!(
+        pushed(Decimal64Vector::new(), Decimal::new(150, 2)),
+        single(Decimal64::from_raw(150, 2).unwrap())
+    );
+    assert_eq!(
+        pushed(Decimal128Vector::new(), Decimal::new(-3, 5)),
+        single(Decimal128::from_raw(-3, 5).unwrap())
+    );
+
+    let mut decimals = VectorImpl::from(Decimal32Vector::new());
+    assert!(decimals
+        .push_primitive_type(Decimal::new(i64::MAX, 2).into())
+        .is_err());
+    assert!(decimals.push_primitive_type(1.5f64.into()).is_err());
+}
+
+#[test]
+fn test_primitive_type_null_and_scalar() {
+    assert_eq!(
+        pushed(IntVector::new(), PrimitiveType::None),
+        single(Int::default())
+    );
+    assert_eq!(
+        pushed(SymbolVector::new(), PrimitiveType::None),
+        single(Symbol::default())
+    );
+    assert_eq!(
+        pushed(Decimal32Vector::new(), PrimitiveType::None),
+        single(Decimal32::default())
+    );
+    assert_eq!(
+        pushed(NanoTimestampVector::new(), PrimitiveType::None),
+        single(NanoTimestamp::default())
+    );
+
+    let mut arrays = VectorImpl::from(IntArrayVector::new());
+    arrays.push_primitive_type(vec![1, 2].into()).unwrap();
+    arrays.push_primitive_type(PrimitiveType::None).unwrap();
+    assert_eq!(arrays.len(), 2);
+    assert!(arrays.push_primitive_type(vec![1i64].into()).is_err());
+
+    assert_eq!(
+        pushed(
+            MonthVector::new(),
+            ScalarImpl::from(Month::from_ym(2024, 3).unwrap())
+        ),
+        single(Month::from_ym(2024, 3).unwrap())
+    );
+    let mut ints = VectorImpl::from(IntVector::new());
+    assert!(ints
+        .push_primitive_type(ScalarImpl::from(Long::new(1)).into())
+        .is_err());
+}
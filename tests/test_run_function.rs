@@ -157,3 +157,58 @@ async fn test_run_function_error() {
     let res = client.run_function("test_run_function_error", &args).await;
     assert!(!res.is_ok());
 }
+
+#[tokio::test]
+async fn test_run_function_function_def_arg() {
+    // connect
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client = builder.connect().await.unwrap();
+    // run
+    let args: Vec<ConstantImpl> = vec![
+        FunctionDef::system("add").into(),
+        Int::new(1).into(),
+        Int::new(2).into(),
+    ];
+    let res_constantimpl = client.run_function("call", &args).await.unwrap().unwrap();
+    assert_eq!(res_constantimpl, Int::new(3).into());
+}
+
+#[tokio::test]
+async fn test_run_function_function_def_round_trip() {
+    // connect
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client = builder.connect().await.unwrap();
+    // run
+    let func = client.run_script("add").await.unwrap().unwrap();
+    assert_eq!(func.data_form(), DataForm::Scalar);
+    assert_eq!(func.data_type(), DataType::FunctionDef);
+    let args: Vec<ConstantImpl> = vec![func, Long::new(1).into(), Long::new(2).into()];
+    let res_constantimpl = client.run_function("call", &args).await.unwrap().unwrap();
+    assert_eq!(res_constantimpl, Long::new(3).into());
+}
+
+#[tokio::test]
+async fn test_run_function_code_round_trip() {
+    // connect
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client = builder.connect().await.unwrap();
+    // prapare
+    let _ = client
+        .run_script("share table(1..3 as a) as test_run_function_code_round_trip")
+        .await;
+    let code = client
+        .run_script("<select * from test_run_function_code_round_trip>")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(code.data_type(), DataType::Code);
+    assert!(matches!(code, ConstantImpl::Opaque(_)));
+    let res_constantimpl = client.run_function("eval", &[code]).await.unwrap().unwrap();
+    assert_eq!(res_constantimpl.len(), 3);
+}
@@ -0,0 +1,248 @@
+#![cfg(feature = "serde")]
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use dolphindb::types::{
+    from_table, to_table, DataType, Decimal32, Decimal64, DictionaryImpl, Int, IntArrayVector,
+    Long, RowDeserializer, TableBuilder, TableSerializer, Timestamp, Vector, VectorImpl,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Trade {
+    sym: String,
+    qty: Option<i32>,
+    price: f64,
+    active: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Quote {
+    time: NaiveDateTime,
+    date: Option<NaiveDate>,
+    bid: Decimal,
+}
+
+fn trades() -> Vec<Trade> {
+    vec![
+        Trade {
+            sym: "AAPL".into(),
+            qty: Some(100),
+            price: 10.5,
+            active: true,
+        },
+        Trade {
+            sym: "MSFT".into(),
+            qty: None,
+            price: 20.25,
+            active: false,
+        },
+    ]
+}
+
+#[test]
+fn test_serde_round_trip() {
+    let table = to_table(&trades()).unwrap();
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.column_names(), &["sym", "qty", "price", "active"]);
+    assert_eq!(table.get_columns_by_index(0).data_type(), DataType::String);
+    assert_eq!(table.get_columns_by_index(1).data_type(), DataType::Int);
+    assert_eq!(table.get_columns_by_index(2).data_type(), DataType::Double);
+    assert_eq!(table.get_columns_by_index(3).data_type(), DataType::Bool);
+
+    let rows: Vec<Trade> = from_table(&table).unwrap();
+    assert_eq!(rows, trades());
+}
+
+#[test]
+fn test_serde_column_type() {
+    let quotes = vec![
+        Quote {
+            time: NaiveDate::from_ymd_opt(2024, 1, 2)
+                .unwrap()
+                .and_hms_milli_opt(9, 30, 0, 123)
+                .unwrap(),
+            date: NaiveDate::from_ymd_opt(2024, 1, 2),
+            bid: Decimal::new(1050, 2),
+        },
+        Quote {
+            time: NaiveDate::from_ymd_opt(2024, 1, 3)
+                .unwrap()
+                .and_hms_opt(15, 0, 0)
+                .unwrap(),
+            date: None,
+            bid: Decimal::new(-7, 1),
+        },
+    ];
+
+    let table = TableSerializer::new()
+        .with_name("quotes".into())
+        .with_column_type("time", DataType::Timestamp)
+        .with_column_type("date", DataType::Date)
+        .with_column_type("bid", DataType::Decimal64)
+        .to_table(&quotes)
+        .unwrap();
+    assert_eq!(table.name(), "quotes");
+    assert_eq!(
+        table.get_columns_by_index(0).data_type(),
+        DataType::Timestamp
+    );
+    assert_eq!(table.get_columns_by_index(1).data_type(), DataType::Date);
+    assert_eq!(
+        table.get_columns_by_index(2).data_type(),
+        DataType::Decimal64
+    );
+    assert_eq!(
+        table.get_columns_by_index(0).get(0).unwrap(),
+        Timestamp::from(quotes[0].time).into()
+    );
+    assert_eq!(
+        table.get_columns_by_index(2).get(1).unwrap(),
+        Decimal64::from_raw(-7, 1).unwrap().into()
+    );
+
+    let rows: Vec<Quote> = from_table(&table).unwrap();
+    assert_eq!(rows, quotes);
+}
+
+#[test]
+fn test_serde_to_dictionary() {
+    let dict: DictionaryImpl = TableSerializer::new().to_dictionary(&trades()).unwrap();
+    assert_eq!(dict.len(), 4);
+}
+
+#[test]
+fn test_serde_decimal_to_f64() {
+    #[derive(Deserialize)]
+    struct Row {
+        price: f64,
+    }
+
+    let mut price = Vector::<Decimal64>::new();
+    price.push(Decimal64::from_raw(1025, 2).unwrap());
+    let mut builder = TableBuilder::new();
+    builder.with_contents(vec![price.into()], vec!["price".into()]);
+    let table = builder.build().unwrap();
+
+    let rows: Vec<Row> = from_table(&table).unwrap();
+    assert_eq!(rows[0].price, 10.25);
+}
+
+#[test]
+fn test_serde_decimal_scale() {
+    #[derive(Serialize)]
+    struct Row {
+        price: f64,
+    }
+
+    let table = TableSerializer::new()
+        .with_decimal_column_type("price", DataType::Decimal32, 2)
+        .to_table(&[Row { price: 1.5 }, Row { price: 2.125 }])
+        .unwrap();
+    assert_eq!(
+        table.get_columns_by_index(0).get(0).unwrap(),
+        Decimal32::from_raw(150, 2).unwrap().into()
+    );
+    assert_eq!(
+        table.get_columns_by_index(0).get(1).unwrap(),
+        Decimal32::from_raw(213, 2).unwrap().into()
+    );
+}
+
+#[test]
+fn test_serde_temporal_types() {
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct Row {
+        month: NaiveDate,
+        minute: NaiveTime,
+        hour: NaiveDateTime,
+    }
+
+    let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let rows = vec![Row {
+        month: date,
+        minute: NaiveTime::from_hms_opt(9, 30, 0).unwrap(),
+        hour: date.and_hms_opt(9, 0, 0).unwrap(),
+    }];
+    let table = TableSerializer::new()
+        .with_column_type("month", DataType::Month)
+        .with_column_type("minute", DataType::Minute)
+        .with_column_type("hour", DataType::DateHour)
+        .to_table(&rows)
+        .unwrap();
+    assert_eq!(from_table::<Row>(&table).unwrap(), rows);
+}
+
+#[test]
+fn test_serde_vector_cell() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Row {
+        id: i64,
+        values: Vec<Option<i32>>,
+    }
+
+    let mut id = Vector::<Long>::new();
+    id.push(Long::new(1));
+    id.push(Long::new(2));
+    let mut values = IntArrayVector::new();
+    values.push(vec![1, i32::MIN, 3]);
+    values.push(vec![]);
+    let mut builder = TableBuilder::new();
+    builder.with_contents(
+        vec![id.into(), VectorImpl::from(values)],
+        vec!["id".into(), "values".into()],
+    );
+    let table = builder.build().unwrap();
+
+    let rows: Vec<Row> = from_table(&table).unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            Row {
+                id: 1,
+                values: vec![Some(1), None, Some(3)],
+            },
+            Row {
+                id: 2,
+                values: vec![]
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_serde_error() {
+    #[derive(Serialize)]
+    struct Empty {
+        value: Option<i32>,
+    }
+
+    // all-null columns without explicit type
+    assert!(to_table(&[Empty { value: None }]).is_err());
+    let table = TableSerializer::new()
+        .with_column_type("value", DataType::Int)
+        .to_table(&[Empty { value: None }])
+        .unwrap();
+    assert_eq!(
+        table.get_columns_by_index(0).get(0).unwrap(),
+        Int::default().into()
+    );
+
+    // out of range
+    assert!(TableSerializer::new()
+        .with_column_type("value", DataType::Char)
+        .to_table(&[Empty { value: Some(1000) }])
+        .is_err());
+
+    // type mismatch
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Wrong {
+        sym: i32,
+    }
+    assert!(from_table::<Wrong>(&to_table(&trades()).unwrap()).is_err());
+
+    // row out of bounds
+    let table = to_table(&trades()).unwrap();
+    assert!(Trade::deserialize(RowDeserializer::new(&table, 2)).is_err());
+}
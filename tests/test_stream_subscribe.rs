@@ -56,12 +56,60 @@ mod test_stream_subscribe_request {
                 Any::new(Date::from_raw(_index.into()).unwrap().into())
             );
             assert_eq!(*msg.msg(), expect.into());
-            assert_eq!(msg.offset(), _index.into());
+            assert_eq!(msg.offset(), i64::from(_index));
             assert!(re.is_match(msg.topic()));
             _index += 1;
         }
     }
 
+    #[cfg(feature = "derive")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_stream_subscribe_request_decode() {
+        #[derive(DolphinRow, Debug, PartialEq)]
+        struct Row {
+            data: i32,
+            time: chrono::NaiveDate,
+        }
+
+        const STREAM_TABLE: &str = "test_stream_subscribe_request_decode";
+        let conf = Config::new();
+        let mut c_builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+        c_builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+        let mut client = c_builder.connect().await.unwrap();
+        let _ = client
+            .run_script(
+                format!(
+                    "
+                        share streamTable(10:0,`data`time,[INT,DATE]) as `{STREAM_TABLE};
+                        insert into {STREAM_TABLE} values(0i..1i,1970.01.01d..1970.01.02d);
+                    "
+                )
+                .as_str(),
+            )
+            .await
+            .unwrap();
+        let mut builder = SubscriberBuilder::new();
+        let mut req = Request::new(STREAM_TABLE.into(), "rust".into());
+        req.with_auth((conf.user, conf.passwd));
+        req.with_offset(0);
+        let mut subscriber = builder
+            .subscribe(format!("{}:{}", conf.host, conf.port), req)
+            .await
+            .unwrap()
+            .take(2);
+        let mut index = 0;
+        while let Some(msg) = subscriber.next().await {
+            assert_eq!(
+                msg.decode::<Row>().unwrap(),
+                Row {
+                    data: index,
+                    time: chrono::NaiveDate::from_ymd_opt(1970, 1, 1 + index as u32).unwrap(),
+                }
+            );
+            index += 1;
+        }
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_stream_subscribe_request_offset_minus_one() {
         const STREAM_TABLE: &str = "test_stream_subscribe_request_offset_minus_one";
@@ -108,7 +156,7 @@ mod test_stream_subscribe_request {
                 Any::new(Date::from_raw(_index.into()).unwrap().into())
             );
             assert_eq!(*msg.msg(), expect.into());
-            assert_eq!(msg.offset(), _index.into());
+            assert_eq!(msg.offset(), i64::from(_index));
             assert!(re.is_match(msg.topic()));
             _index += 1;
         }
@@ -160,7 +208,7 @@ mod test_stream_subscribe_request {
                 Any::new(Date::from_raw(_index.into()).unwrap().into())
             );
             assert_eq!(*msg.msg(), expect.into());
-            assert_eq!(msg.offset(), _index.into());
+            assert_eq!(msg.offset(), i64::from(_index));
             assert!(re.is_match(msg.topic()));
             _index += 1;
         }
@@ -226,7 +274,7 @@ mod test_stream_subscribe_request {
                 Any::new(Date::from_raw(_index.into()).unwrap().into())
             );
             assert_eq!(*msg.msg(), _expect.into());
-            assert_eq!(msg.offset(), _index.into());
+            assert_eq!(msg.offset(), i64::from(_index));
             assert!(re.is_match(msg.topic()));
             _index += 1;
         }
@@ -653,4 +701,43 @@ mod test_stream_subscribe_subscriber {
             .take(1);
         assert!(subscriber.next().await.is_none());
     }
+
+    #[tokio::test]
+    async fn test_stream_subscribe_close() {
+        const STREAM_TABLE: &str = "test_stream_subscribe_close";
+        let conf = Config::new();
+        let mut c_builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+        c_builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+        let mut client = c_builder.connect().await.unwrap();
+        let _ = client
+            .run_script(format!("share streamTable(10:0,`data,[INT]) as `{STREAM_TABLE};").as_str())
+            .await
+            .unwrap();
+        let subscribed = format!(
+            "(exec count(*) from getStreamingStat().pubTables where tableName == `{STREAM_TABLE}) == 1"
+        );
+
+        let mut builder = SubscriberBuilder::new();
+        let mut req = Request::new(STREAM_TABLE.into(), "rust".into());
+        req.with_auth((conf.user.clone(), conf.passwd.clone()));
+        let subscriber = builder
+            .subscribe(format!("{}:{}", conf.host, conf.port), req.clone())
+            .await
+            .unwrap();
+        let res = client.run_script(subscribed.as_str()).await.unwrap();
+        assert_eq!(res.unwrap(), Bool::new(true).into());
+        subscriber.close().await.unwrap();
+        let res = client.run_script(subscribed.as_str()).await.unwrap();
+        assert_eq!(res.unwrap(), Bool::new(false).into());
+
+        // dropping on a current thread runtime unsubscribes in the background
+        let subscriber = builder
+            .subscribe(format!("{}:{}", conf.host, conf.port), req)
+            .await
+            .unwrap();
+        drop(subscriber);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let res = client.run_script(subscribed.as_str()).await.unwrap();
+        assert_eq!(res.unwrap(), Bool::new(false).into());
+    }
 }
@@ -0,0 +1,155 @@
+mod setup;
+
+use dolphindb::client::{ClientBuilder, TableUpserter, UpsertOptions};
+use dolphindb::types::*;
+use setup::settings::Config;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_upserter_keyed_table() {
+    const TABLE: &str = "test_table_upserter_keyed_table";
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let client_writer = builder.connect().await.unwrap();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder.connect().await.unwrap();
+    client_query
+        .run_script(
+            format!(
+                "share keyedTable(`id, 1:0, `id`qty, [SYMBOL, INT]) as {TABLE}
+                 tableInsert({TABLE}, `a`b, 1 2)"
+            )
+            .as_str(),
+        )
+        .await
+        .unwrap();
+
+    let mut options = UpsertOptions::new();
+    options.with_ignore_null(true);
+    let mut upserter = TableUpserter::new(client_writer, TABLE, 2, &options)
+        .await
+        .unwrap();
+    let res = upserter
+        .append_row(&mut vec!["a".into(), 10.into()])
+        .await
+        .unwrap();
+    assert!(res.is_none());
+    let res = upserter
+        .append_row(&mut vec!["c".into(), 30.into()])
+        .await
+        .unwrap();
+    assert_eq!(res, Some(2));
+    upserter
+        .append_row(&mut vec!["b".into(), PrimitiveType::None])
+        .await
+        .unwrap();
+    assert_eq!(upserter.size(), 1);
+    assert_eq!(upserter.flush().await.unwrap(), 1);
+    assert_eq!(upserter.flush().await.unwrap(), 0);
+
+    let res = client_query
+        .run_script(format!("eqObj((exec qty from {TABLE} order by id), 10 2 30)").as_str())
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_upserter_dfs_table() {
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let client_writer = builder.connect().await.unwrap();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder.connect().await.unwrap();
+    client_query
+        .run_script(
+            r#"
+            if (existsDatabase("dfs://test_table_upserter")) { dropDatabase("dfs://test_table_upserter") }
+            db = database("dfs://test_table_upserter", HASH, [SYMBOL, 2], engine="TSDB")
+            t = table(`a`b as id, 1 2 as qty, 2024.01.01 2024.01.01 as date)
+            db.createPartitionedTable(t, `pt, `id, sortColumns=`id`date).append!(t)
+            "#,
+        )
+        .await
+        .unwrap();
+
+    let mut options = UpsertOptions::new();
+    options.with_key_columns(["id"]).with_sort_columns(["date"]);
+    let mut upserter = TableUpserter::load(
+        client_writer,
+        "dfs://test_table_upserter",
+        "pt",
+        10,
+        &options,
+    )
+    .await
+    .unwrap();
+    for (id, qty) in [("b", 20), ("c", 30)] {
+        upserter
+            .append_row(&mut vec![
+                id.into(),
+                qty.into(),
+                chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap().into(),
+            ])
+            .await
+            .unwrap();
+    }
+    assert_eq!(upserter.flush().await.unwrap(), 2);
+
+    let res = client_query
+        .run_script(
+            r#"eqObj((exec qty from loadTable("dfs://test_table_upserter", `pt) order by id), 1 20 30)"#,
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_upserter_status() {
+    const TABLE: &str = "test_table_upserter_status";
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let client_writer = builder.connect().await.unwrap();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder.connect().await.unwrap();
+    client_query
+        .run_script(
+            format!("share keyedTable(`id, 1:0, `id`qty, [SYMBOL, INT]) as {TABLE}").as_str(),
+        )
+        .await
+        .unwrap();
+
+    let mut upserter = TableUpserter::new(client_writer, TABLE, 1, &UpsertOptions::new())
+        .await
+        .unwrap();
+    let res = upserter
+        .append_row(&mut vec!["a".into(), 1.into()])
+        .await
+        .unwrap();
+    assert_eq!(res, Some(1));
+
+    // rows the server refuses are not counted
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+    let res = upserter
+        .append_row(&mut vec!["b".into(), 2.into()])
+        .await
+        .unwrap();
+    assert_eq!(res, Some(0));
+    let status = upserter.status();
+    assert_eq!(status.sent_rows, 1);
+    assert_eq!(status.unsent_rows, 1);
+    assert!(status.error.is_some());
+}
@@ -4,7 +4,7 @@ mod utils;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
 use dolphindb::client::ClientBuilder;
-use dolphindb::client::TableWriter;
+use dolphindb::client::{LogSync, PartitionType, RetryPolicy, TableWriter};
 use dolphindb::types::PrimitiveType;
 use dolphindb::types::*;
 use setup::settings::Config;
@@ -29,7 +29,7 @@ mod test_table_writer_type {
                 let _ = client_writer.run_script(
                     format!("share table(10:0,[`data],[{0}]) as `{TABLE}", $type_str).as_str()
                 ).await;
-                let mut table_writer = TableWriter::new(client_writer, TABLE, 1024).await;
+                let mut table_writer = TableWriter::new(client_writer, TABLE, 1024).await.unwrap();
                 // append
                 let mut _index: usize = 0;
                 $(
@@ -468,7 +468,6 @@ mod test_table_writer_type {
 }
 
 #[tokio::test(flavor = "multi_thread")]
-#[should_panic(expected = "batch_size must be positive.")]
 async fn test_table_writer_batch_size_0() {
     const TABLE: &str = "test_table_writer_batch_size_0";
     // connect
@@ -480,11 +479,14 @@ async fn test_table_writer_batch_size_0() {
     let _ = client_writer
         .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
         .await;
-    let _ = TableWriter::new(client_writer, TABLE, 0).await;
+    let err = TableWriter::new(client_writer, TABLE, 0)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "violation: batch size must be positive");
 }
 
 #[tokio::test(flavor = "multi_thread")]
-#[should_panic(expected = "test_table_writer_columns_error has 1 columns, but 2 provided.")]
 async fn test_table_writer_columns_error() {
     const TABLE: &str = "test_table_writer_columns_error";
     // connect
@@ -496,17 +498,22 @@ async fn test_table_writer_columns_error() {
     let _ = client_writer
         .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
         .await;
-    let mut table_writer = TableWriter::new(client_writer, TABLE, 1).await;
-    let _ = table_writer
+    let mut table_writer = TableWriter::new(client_writer, TABLE, 1).await.unwrap();
+    let err = table_writer
         .append_row(&mut vec![
             PrimitiveType::I32(0i32),
             PrimitiveType::I32(1i32),
         ])
-        .await;
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid data, expect 1 columns in test_table_writer_columns_error but gets 2 columns"
+    );
+    assert_eq!(table_writer.size(), 0);
 }
 
 #[tokio::test(flavor = "multi_thread")]
-#[should_panic(expected = "Failed to get schema for table test_table_writer_table_not_exist")]
 async fn test_table_writer_table_not_exist() {
     const TABLE: &str = "test_table_writer_table_not_exist";
     // connect
@@ -515,11 +522,10 @@ async fn test_table_writer_table_not_exist() {
     builder_writer.with_auth((conf.user.as_str(), conf.passwd.as_str()));
     let client_writer = builder_writer.connect().await.unwrap();
     // create table writer
-    let _ = TableWriter::new(client_writer, TABLE, 3).await;
+    assert!(TableWriter::new(client_writer, TABLE, 3).await.is_err());
 }
 
 #[tokio::test(flavor = "multi_thread")]
-#[should_panic(expected = "not implemented")]
 async fn test_table_writer_type_not_support() {
     const TABLE: &str = "test_table_writer_type_not_support";
     // connect
@@ -531,13 +537,14 @@ async fn test_table_writer_type_not_support() {
     let _ = client_writer.run_script(
         format!("share table(10:0,`data1`data2`data3`data4,[DECIMAL32(2),DECIMAL64(3),DECIMAL128(4),ANY]) as `{TABLE}").as_str()
     ).await;
-    let _ = TableWriter::new(client_writer, TABLE, 3).await;
+    let err = TableWriter::new(client_writer, TABLE, 3)
+        .await
+        .err()
+        .unwrap();
+    assert!(err.to_string().starts_with("unsupported type"));
 }
 
 #[tokio::test(flavor = "multi_thread")]
-#[should_panic(
-    expected = "Failed to insert into column `data1`: type NaiveDate cannot be converted to Second."
-)]
 async fn test_table_writer_type_error_naive_date() {
     const TABLE: &str = "test_table_writer_type_error_naive_date";
     // connect
@@ -549,18 +556,21 @@ async fn test_table_writer_type_error_naive_date() {
     let _ = client_writer
         .run_script(format!("share table(10:0,[`data1],[SECOND]) as `{TABLE}").as_str())
         .await;
-    let mut table_writer = TableWriter::new(client_writer, TABLE, 1).await;
-    let _ = table_writer
+    let mut table_writer = TableWriter::new(client_writer, TABLE, 1).await.unwrap();
+    let err = table_writer
         .append_row(&mut vec![PrimitiveType::NaiveDate(
             NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
         )])
-        .await;
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "type NaiveDate cannot be converted to Second"
+    );
+    assert_eq!(table_writer.size(), 0);
 }
 
 #[tokio::test(flavor = "multi_thread")]
-#[should_panic(
-    expected = "Failed to insert into column `data1`: type NaiveTime cannot be converted to Date."
-)]
 async fn test_table_writer_type_error_naive_time() {
     const TABLE: &str = "test_table_writer_type_error_naive_time";
     // connect
@@ -572,18 +582,21 @@ async fn test_table_writer_type_error_naive_time() {
     let _ = client_writer
         .run_script(format!("share table(10:0,[`data1],[DATE]) as `{TABLE}").as_str())
         .await;
-    let mut table_writer = TableWriter::new(client_writer, TABLE, 1).await;
-    let _ = table_writer
+    let mut table_writer = TableWriter::new(client_writer, TABLE, 1).await.unwrap();
+    let err = table_writer
         .append_row(&mut vec![PrimitiveType::NaiveTime(
             NaiveTime::from_hms_milli_opt(0, 0, 0, 0).unwrap(),
         )])
-        .await;
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "type NaiveTime cannot be converted to Date"
+    );
+    assert_eq!(table_writer.size(), 0);
 }
 
 #[tokio::test(flavor = "multi_thread")]
-#[should_panic(
-    expected = "Failed to insert into column `data1`: type NaiveDateTime cannot be converted to Date."
-)]
 async fn test_table_writer_type_error_naive_date_time() {
     const TABLE: &str = "test_table_writer_type_error_naive_date_time";
     // connect
@@ -593,15 +606,21 @@ async fn test_table_writer_type_error_naive_date_time() {
     let mut client_writer = builder_writer.connect().await.unwrap();
     // create table writer
     let _ = client_writer
-        .run_script(format!("share table(10:0,[`data1],[DATE]) as `{TABLE}").as_str())
+        .run_script(format!("share table(10:0,[`data1],[BOOL]) as `{TABLE}").as_str())
         .await;
-    let mut table_writer = TableWriter::new(client_writer, TABLE, 1).await;
-    let _ = table_writer
+    let mut table_writer = TableWriter::new(client_writer, TABLE, 1).await.unwrap();
+    let err = table_writer
         .append_row(&mut vec![PrimitiveType::NaiveDateTime(NaiveDateTime::new(
             NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
             NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
         ))])
-        .await;
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "type NaiveDateTime cannot be converted to Bool"
+    );
+    assert_eq!(table_writer.size(), 0);
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -616,7 +635,7 @@ async fn test_table_writer_auto_flush() {
     let _ = client_writer
         .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
         .await;
-    let mut table_writer = TableWriter::new(client_writer, TABLE, 3).await;
+    let mut table_writer = TableWriter::new(client_writer, TABLE, 3).await.unwrap();
     for i in 0..3 {
         let _ = table_writer
             .append_row(&mut vec![PrimitiveType::I32(i)])
@@ -624,3 +643,808 @@ async fn test_table_writer_auto_flush() {
     }
     assert_eq!(table_writer.size(), 0);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_dfs_table() {
+    // connect
+    let conf = Config::new();
+    let mut builder_writer = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder_writer.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let client_writer = builder_writer.connect().await.unwrap();
+    let mut builder_query = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder_query.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder_query.connect().await.unwrap();
+    // create table writer
+    client_query
+        .run_script(
+            r#"
+            if (existsDatabase("dfs://test_table_writer_dfs")) { dropDatabase("dfs://test_table_writer_dfs") }
+            db1 = database(, VALUE, 2024.01.01..2024.01.03)
+            db2 = database(, HASH, [SYMBOL, 4])
+            db = database("dfs://test_table_writer_dfs", COMPO, [db1, db2])
+            t = table(10:0, `date`sym`price, [DATE, SYMBOL, DOUBLE])
+            db.createPartitionedTable(t, `pt, `date`sym)
+            "#,
+        )
+        .await
+        .unwrap();
+    let mut table_writer = TableWriter::load(client_writer, "dfs://test_table_writer_dfs", "pt", 2)
+        .await
+        .unwrap();
+    let partitions = table_writer.partition_columns();
+    assert_eq!(partitions.len(), 2);
+    assert_eq!(partitions[0].name, "date");
+    assert_eq!(partitions[0].index, 0);
+    assert_eq!(partitions[0].partition_type, PartitionType::Value);
+    assert_eq!(partitions[1].name, "sym");
+    assert_eq!(partitions[1].index, 1);
+    assert_eq!(partitions[1].partition_type, PartitionType::Hash);
+    assert_eq!(partitions[1].scheme, Int::new(4).into());
+
+    for sym in ["a", "b"] {
+        let _ = table_writer
+            .append_row(&mut vec![
+                NaiveDate::from_ymd_opt(2024, 1, 2).unwrap().into(),
+                sym.into(),
+                1.5.into(),
+            ])
+            .await
+            .unwrap();
+    }
+    assert_eq!(table_writer.size(), 0);
+    drop(table_writer);
+    let res = client_query
+        .run_script(r#"(exec count(*) from loadTable("dfs://test_table_writer_dfs", `pt)) == 2"#)
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_flush_interval() {
+    const TABLE: &str = "test_table_writer_flush_interval";
+    // connect
+    let conf = Config::new();
+    let mut builder_writer = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder_writer.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_writer = builder_writer.connect().await.unwrap();
+    // create table writer
+    let _ = client_writer
+        .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
+        .await;
+    let mut table_writer = TableWriter::new(client_writer, TABLE, 1024).await.unwrap();
+    table_writer.with_flush_interval(std::time::Duration::from_millis(50));
+    // producers append through clones
+    let mut producers = vec![];
+    for i in 0..4 {
+        let mut writer = table_writer.clone();
+        producers.push(tokio::spawn(async move {
+            writer
+                .append_row(&mut vec![PrimitiveType::I32(i)])
+                .await
+                .unwrap();
+        }));
+    }
+    for producer in producers {
+        producer.await.unwrap();
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    assert_eq!(table_writer.size(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_max_bytes() {
+    const TABLE: &str = "test_table_writer_max_bytes";
+    // connect
+    let conf = Config::new();
+    let mut builder_writer = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder_writer.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_writer = builder_writer.connect().await.unwrap();
+    // create table writer
+    let _ = client_writer
+        .run_script(format!("share table(10:0,[`data],[STRING]) as `{TABLE}").as_str())
+        .await;
+    let mut table_writer = TableWriter::new(client_writer, TABLE, 1024).await.unwrap();
+    table_writer.with_max_bytes(16);
+    let result = table_writer
+        .append_row(&mut vec![PrimitiveType::String("12345678".into())])
+        .await
+        .unwrap();
+    assert!(result.is_none());
+    assert_eq!(table_writer.size(), 1);
+    let result = table_writer
+        .append_row(&mut vec![PrimitiveType::String("12345678".into())])
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), Int::new(2).into());
+    assert_eq!(table_writer.size(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_row_rollback() {
+    const TABLE: &str = "test_table_writer_row_rollback";
+    // connect
+    let conf = Config::new();
+    let mut builder_writer = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder_writer.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_writer = builder_writer.connect().await.unwrap();
+    // create table writer
+    let _ = client_writer
+        .run_script(format!("share table(10:0,`date`data,[DATE,INT]) as `{TABLE}").as_str())
+        .await;
+    let mut table_writer = TableWriter::new(client_writer, TABLE, 1024).await.unwrap();
+    assert!(table_writer
+        .append_row(&mut vec![
+            NaiveTime::from_hms_opt(0, 0, 0).unwrap().into(),
+            PrimitiveType::I32(1),
+        ])
+        .await
+        .is_err());
+    assert_eq!(table_writer.size(), 0);
+    table_writer
+        .append_row(&mut vec![
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().into(),
+            PrimitiveType::I32(1),
+        ])
+        .await
+        .unwrap();
+    let result = table_writer.flush().await.unwrap();
+    assert_eq!(result.unwrap(), Int::new(1).into());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_take_failed() {
+    const TABLE: &str = "test_table_writer_take_failed";
+    // connect
+    let conf = Config::new();
+    let mut builder_writer = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder_writer.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_writer = builder_writer.connect().await.unwrap();
+    let mut builder_query = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder_query.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder_query.connect().await.unwrap();
+    // create table writer
+    let _ = client_writer
+        .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
+        .await;
+    let mut table_writer = TableWriter::new(client_writer, TABLE, 1024).await.unwrap();
+    let mut retry_policy = RetryPolicy::new();
+    retry_policy
+        .with_max_retries(1)
+        .with_interval(std::time::Duration::from_millis(10));
+    table_writer.with_retry_policy(retry_policy);
+    // batches sent while the table is missing are kept
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+    for i in 0..2 {
+        table_writer
+            .append_row(&mut vec![PrimitiveType::I32(i)])
+            .await
+            .unwrap();
+    }
+    assert!(table_writer.flush().await.is_err());
+    assert_eq!(table_writer.size(), 0);
+    table_writer
+        .append_row(&mut vec![PrimitiveType::I32(2)])
+        .await
+        .unwrap();
+    assert!(table_writer.flush().await.is_err());
+    // then set aside without holding back newer rows
+    client_query
+        .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
+        .await
+        .unwrap();
+    table_writer
+        .append_row(&mut vec![PrimitiveType::I32(3)])
+        .await
+        .unwrap();
+    let result = table_writer.flush().await.unwrap();
+    assert_eq!(result.unwrap(), Int::new(1).into());
+    let failed = table_writer.take_failed().await.unwrap();
+    assert_eq!(
+        failed.get_columns_by_index(0),
+        &VectorImpl::from(IntVector::from(vec![Int::new(0), Int::new(1), Int::new(2)]))
+    );
+    table_writer.append_table(&failed).await.unwrap();
+    let result = table_writer.flush().await.unwrap();
+    assert_eq!(result.unwrap(), Int::new(3).into());
+    let res = client_query
+        .run_script(format!("eqObj((exec data from {TABLE}), 3 0 1 2)").as_str())
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+    assert_eq!(
+        table_writer.take_failed().await.unwrap().columns()[0].len(),
+        0
+    );
+
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+    table_writer
+        .append_row(&mut vec![PrimitiveType::I32(4)])
+        .await
+        .unwrap();
+    assert!(table_writer.flush().await.is_err());
+    let failed = table_writer.take_failed().await.unwrap();
+    assert_eq!(
+        failed.get_columns_by_index(0),
+        &VectorImpl::from(IntVector::from(vec![Int::new(4)]))
+    );
+    assert_eq!(
+        table_writer.take_failed().await.unwrap().columns()[0].len(),
+        0
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_status() {
+    const TABLE: &str = "test_table_writer_status";
+    // connect
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder.clone().connect().await.unwrap();
+    // create table writer
+    client_query
+        .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
+        .await
+        .unwrap();
+    let mut table_writer = TableWriter::new(builder.clone().connect().await.unwrap(), TABLE, 2)
+        .await
+        .unwrap();
+    let mut retry_policy = RetryPolicy::new();
+    retry_policy.with_max_retries(0);
+    table_writer.with_retry_policy(retry_policy);
+    let append = |i| {
+        let mut table_writer = table_writer.clone();
+        async move {
+            table_writer
+                .append_row(&mut vec![PrimitiveType::I32(i)])
+                .await
+        }
+    };
+    assert!(append(0).await.unwrap().is_none());
+    assert_eq!(append(1).await.unwrap().unwrap(), Int::new(2).into());
+    let status = table_writer.status();
+    assert_eq!((status.sent_rows, status.unsent_rows), (2, 0));
+    assert!(status.error.is_none());
+
+    // rows are accepted even if their flush fails
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+    assert!(append(2).await.unwrap().is_none());
+    assert!(append(3).await.unwrap().is_none());
+    let status = table_writer.status();
+    assert_eq!((status.sent_rows, status.unsent_rows), (2, 2));
+    assert!(status.error.is_some());
+
+    client_query
+        .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
+        .await
+        .unwrap();
+    append(4).await.unwrap();
+    assert_eq!(append(5).await.unwrap().unwrap(), Int::new(2).into());
+    let status = table_writer.status();
+    assert_eq!((status.sent_rows, status.unsent_rows), (4, 2));
+    assert!(status.error.is_none());
+    assert_eq!(table_writer.take_failed().await.unwrap().len(), 2);
+    assert_eq!(table_writer.status().unsent_rows, 0);
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_append_columns() {
+    const TABLE: &str = "test_table_writer_append_columns";
+    // connect
+    let conf = Config::new();
+    let mut builder_writer = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder_writer.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_writer = builder_writer.connect().await.unwrap();
+    let mut builder_query = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder_query.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder_query.connect().await.unwrap();
+    // create table writer
+    let _ = client_writer
+        .run_script(
+            format!("share table(10:0,`id`sym`price,[LONG,SYMBOL,DECIMAL64(2)]) as `{TABLE}")
+                .as_str(),
+        )
+        .await;
+    let mut table_writer = TableWriter::new(client_writer, TABLE, 3).await.unwrap();
+    assert_eq!(table_writer.column_names(), ["id", "sym", "price"]);
+    assert_eq!(
+        table_writer.column_types(),
+        [DataType::Long, DataType::Symbol, DataType::Decimal64]
+    );
+
+    let id: IntVector = (1..=5).map(Int::new).collect::<Vec<_>>().into();
+    let sym: StringVector = ["a", "b", "c", "d", "e"]
+        .map(|s| DolphinString::new(s.into()))
+        .to_vec()
+        .into();
+    let price: DoubleVector = vec![Double::new(1.25); 5].into();
+    // invalid columns leave the buffer unchanged
+    assert!(table_writer
+        .append_columns(vec![id.clone().into(), sym.clone().into()])
+        .await
+        .is_err());
+    let short: DoubleVector = vec![Double::new(1.25)].into();
+    assert!(table_writer
+        .append_columns(vec![id.clone().into(), sym.clone().into(), short.into()])
+        .await
+        .is_err());
+    let blob: BlobVector = vec![Blob::new(vec![1]); 5].into();
+    assert!(table_writer
+        .append_columns(vec![blob.into(), sym.clone().into(), price.clone().into()])
+        .await
+        .is_err());
+    assert_eq!(table_writer.size(), 0);
+
+    // five rows are sent as batches of three and two
+    let result = table_writer
+        .append_columns(vec![id.into(), sym.into(), price.into()])
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), Int::new(2).into());
+    assert_eq!(table_writer.size(), 0);
+    let res = client_query
+        .run_script(
+            format!("(exec sum(id) from {TABLE}) == 15 && (exec sum(price) from {TABLE}) == 6.25")
+                .as_str(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_append_table() {
+    const TABLE: &str = "test_table_writer_append_table";
+    // connect
+    let conf = Config::new();
+    let mut builder_writer = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder_writer.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_writer = builder_writer.connect().await.unwrap();
+    // create table writer
+    let _ = client_writer
+        .run_script(format!("share table(10:0,`ts`data,[TIMESTAMP,INT]) as `{TABLE}").as_str())
+        .await;
+    let mut table_writer = TableWriter::new(client_writer, TABLE, 1024).await.unwrap();
+
+    let ts: NanoTimestampVector =
+        vec![NanoTimestamp::from_raw(1_710_495_015_250_999_999).unwrap()].into();
+    let data: IntVector = vec![Int::new(1)].into();
+    let table = |names: [&str; 2]| {
+        let mut builder = TableBuilder::new();
+        builder.with_contents(
+            vec![ts.clone().into(), data.clone().into()],
+            names.map(String::from).to_vec(),
+        );
+        builder.build().unwrap()
+    };
+    assert!(table_writer
+        .append_table(&table(["ts", "value"]))
+        .await
+        .is_err());
+    let result = table_writer
+        .append_table(&table(["ts", "data"]))
+        .await
+        .unwrap();
+    assert!(result.is_none());
+    assert_eq!(table_writer.size(), 1);
+    let result = table_writer.flush().await.unwrap();
+    assert_eq!(result.unwrap(), Int::new(1).into());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_log_dir() {
+    const TABLE: &str = "test_table_writer_log_dir";
+    let dir = std::env::temp_dir().join(TABLE);
+    let _ = std::fs::remove_dir_all(&dir);
+    // connect
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder.clone().connect().await.unwrap();
+    // create table writer
+    client_query
+        .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
+        .await
+        .unwrap();
+    let mut table_writer = TableWriter::new(builder.clone().connect().await.unwrap(), TABLE, 1024)
+        .await
+        .unwrap();
+    let mut retry_policy = RetryPolicy::new();
+    retry_policy.with_max_retries(0);
+    table_writer
+        .with_retry_policy(retry_policy)
+        .with_log_dir(&dir)
+        .await
+        .unwrap();
+    // rows the server never confirmed survive the writer
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+    for i in 0..2 {
+        table_writer
+            .append_row(&mut vec![PrimitiveType::I32(i)])
+            .await
+            .unwrap();
+    }
+    assert!(table_writer.flush().await.is_err());
+    table_writer
+        .append_row(&mut vec![PrimitiveType::I32(2)])
+        .await
+        .unwrap();
+    assert!(table_writer.close().await.is_err());
+
+    // and are replayed by the next writer
+    client_query
+        .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
+        .await
+        .unwrap();
+    let mut table_writer = TableWriter::new(builder.clone().connect().await.unwrap(), TABLE, 1024)
+        .await
+        .unwrap();
+    table_writer
+        .with_log_sync(LogSync::OnFlush)
+        .with_log_dir(&dir)
+        .await
+        .unwrap();
+    table_writer.flush().await.unwrap();
+    let res = client_query
+        .run_script(format!("eqObj((exec data from {TABLE}), 0 1 2)").as_str())
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    // rejected rows are taken back and sent through a new connection
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+    table_writer
+        .append_row(&mut vec![PrimitiveType::I32(3)])
+        .await
+        .unwrap();
+    assert!(table_writer.flush().await.is_err());
+    client_query
+        .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
+        .await
+        .unwrap();
+    let failed = table_writer.take_failed().await.unwrap();
+    table_writer.append_table(&failed).await.unwrap();
+    let result = table_writer
+        .reconnect(builder.clone().connect().await.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), Int::new(1).into());
+    table_writer.close().await.unwrap();
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_log_dir_rejected() {
+    const TABLE: &str = "test_table_writer_log_dir_rejected";
+    let dir = std::env::temp_dir().join(TABLE);
+    let _ = std::fs::remove_dir_all(&dir);
+    // connect
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder.clone().connect().await.unwrap();
+    // create table writer
+    client_query
+        .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
+        .await
+        .unwrap();
+    let mut table_writer = TableWriter::new(builder.clone().connect().await.unwrap(), TABLE, 1024)
+        .await
+        .unwrap();
+    let mut retry_policy = RetryPolicy::new();
+    retry_policy.with_max_retries(0);
+    table_writer
+        .with_retry_policy(retry_policy)
+        .with_log_dir(&dir)
+        .await
+        .unwrap();
+    // rows that fail to convert are neither buffered nor logged
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+    let blob: BlobVector = vec![Blob::new(vec![1]); 2].into();
+    assert!(table_writer
+        .append_columns(vec![blob.into()])
+        .await
+        .is_err());
+    assert!(table_writer
+        .append_row(&mut vec![PrimitiveType::String("a".into())])
+        .await
+        .is_err());
+    table_writer
+        .append_row(&mut vec![PrimitiveType::I32(1)])
+        .await
+        .unwrap();
+    assert_eq!(table_writer.size(), 1);
+    assert!(table_writer.close().await.is_err());
+
+    // so the next writer replays only the accepted row
+    client_query
+        .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
+        .await
+        .unwrap();
+    let mut table_writer = TableWriter::new(builder.clone().connect().await.unwrap(), TABLE, 1024)
+        .await
+        .unwrap();
+    table_writer.with_log_dir(&dir).await.unwrap();
+    table_writer.close().await.unwrap();
+    let res = client_query
+        .run_script(format!("eqObj((exec data from {TABLE}), [1])").as_str())
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "derive")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_append_rows() {
+    #[derive(DolphinRow)]
+    struct Row {
+        #[dolphindb(symbol)]
+        sym: String,
+        #[dolphindb(precision = 9, scale = 2)]
+        price: rust_decimal::Decimal,
+    }
+
+    const TABLE: &str = "test_table_writer_append_rows";
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder.clone().connect().await.unwrap();
+    client_query
+        .run_script(
+            format!("share table(10:0,[`sym,`price],[SYMBOL,DECIMAL32(2)]) as `{TABLE}").as_str(),
+        )
+        .await
+        .unwrap();
+    let mut table_writer = TableWriter::new(builder.clone().connect().await.unwrap(), TABLE, 2)
+        .await
+        .unwrap();
+    let rows = ["a", "b"].map(|sym| Row {
+        sym: sym.into(),
+        price: rust_decimal::Decimal::new(105, 1),
+    });
+    let result = table_writer.append_rows(&rows).await.unwrap();
+    assert_eq!(result.unwrap(), Int::new(2).into());
+    let res = client_query
+        .run_script(format!("eqObj((exec price from {TABLE}), 10.50 10.50$DECIMAL32(2))").as_str())
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_log_dir_time_out_of_bound() {
+    const TABLE: &str = "test_table_writer_log_dir_time_out_of_bound";
+    let dir = std::env::temp_dir().join(TABLE);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    // a log record holding a table whose TIME column is out of bound
+    let mut table = vec![27u8, 6];
+    table.extend(1i32.to_le_bytes());
+    table.extend(1i32.to_le_bytes());
+    table.extend(b"t\0ts\0");
+    table.extend([8u8, 1]);
+    table.extend(1i32.to_le_bytes());
+    table.extend(1i32.to_le_bytes());
+    table.extend((-1i32).to_le_bytes());
+    let mut record = (table.len() as u32).to_le_bytes().to_vec();
+    record.extend(table);
+    std::fs::write(dir.join(format!("{:020}.wal", 1)), record).unwrap();
+    // connect
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder.clone().connect().await.unwrap();
+    client_query
+        .run_script(format!("share table(10:0,[`ts],[TIME]) as `{TABLE}").as_str())
+        .await
+        .unwrap();
+    let mut table_writer = TableWriter::new(builder.clone().connect().await.unwrap(), TABLE, 1024)
+        .await
+        .unwrap();
+    let result = table_writer.with_log_dir(&dir).await;
+    assert!(matches!(
+        result,
+        Err(dolphindb::error::Error::ConstraintsViolated(_))
+    ));
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_table_writer_close() {
+    const TABLE: &str = "test_table_writer_close";
+    // connect
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder.clone().connect().await.unwrap();
+    // create table writer
+    client_query
+        .run_script(format!("share table(10:0,[`data],[INT]) as `{TABLE}").as_str())
+        .await
+        .unwrap();
+    let table_writer = TableWriter::new(builder.clone().connect().await.unwrap(), TABLE, 1024)
+        .await
+        .unwrap();
+    let mut writer = table_writer.clone();
+    for i in 0..2 {
+        writer
+            .append_row(&mut vec![PrimitiveType::I32(i)])
+            .await
+            .unwrap();
+    }
+    let result = table_writer.close().await.unwrap();
+    assert_eq!(result.unwrap(), Int::new(2).into());
+    // the connection is closed for clones too
+    assert!(writer.flush().await.is_err());
+    drop(writer);
+
+    // dropping on a current thread runtime flushes in the background
+    let mut table_writer = TableWriter::new(builder.connect().await.unwrap(), TABLE, 1024)
+        .await
+        .unwrap();
+    table_writer
+        .append_row(&mut vec![PrimitiveType::I32(2)])
+        .await
+        .unwrap();
+    drop(table_writer);
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let res = client_query
+        .run_script(format!("eqObj((exec data from {TABLE}), 0 1 2)").as_str())
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_append_named_row() {
+    const TABLE: &str = "test_table_writer_append_named_row";
+    // connect
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder.clone().connect().await.unwrap();
+    // create table writer
+    client_query
+        .run_script(
+            format!("share table(10:0,`id`sym`price,[INT,SYMBOL,DOUBLE]) as `{TABLE}").as_str(),
+        )
+        .await
+        .unwrap();
+    let mut table_writer = TableWriter::new(builder.connect().await.unwrap(), TABLE, 1024)
+        .await
+        .unwrap();
+    let row = std::collections::HashMap::from([("price", 1.5.into()), ("ID", 1.into())]);
+    let result = table_writer.append_named_row(row).await.unwrap();
+    assert!(result.is_none());
+    assert!(table_writer
+        .append_named_row([("sym", "a".into()), ("qty", 1.into())])
+        .await
+        .is_err());
+    assert!(table_writer
+        .append_named_row([("sym", "a".into()), ("sym", "b".into())])
+        .await
+        .is_err());
+    assert_eq!(table_writer.size(), 1);
+    let result = table_writer.flush().await.unwrap();
+    assert_eq!(result.unwrap(), Int::new(1).into());
+    let res = client_query
+        .run_script(
+            format!(
+                "t = select * from {TABLE}; t.id[0] == 1 && isNull(t.sym[0]) && t.price[0] == 1.5"
+            )
+            .as_str(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_writer_refresh_schema() {
+    const TABLE: &str = "test_table_writer_refresh_schema";
+    // connect
+    let conf = Config::new();
+    let mut builder = ClientBuilder::new(format!("{}:{}", conf.host, conf.port));
+    builder.with_auth((conf.user.as_str(), conf.passwd.as_str()));
+    let mut client_query = builder.clone().connect().await.unwrap();
+    // create table writer
+    client_query
+        .run_script(format!("share table(10:0,`id`price,[INT,DOUBLE]) as `{TABLE}").as_str())
+        .await
+        .unwrap();
+    let mut table_writer = TableWriter::new(builder.connect().await.unwrap(), TABLE, 1024)
+        .await
+        .unwrap();
+    table_writer
+        .append_named_row([("id", 1.into()), ("price", 1.5.into())])
+        .await
+        .unwrap();
+
+    client_query
+        .run_script(format!("addColumn({TABLE}, `qty, INT)").as_str())
+        .await
+        .unwrap();
+    assert!(table_writer
+        .append_named_row([("id", 2.into()), ("qty", 5.into())])
+        .await
+        .is_err());
+    table_writer.refresh_schema().await.unwrap();
+    assert_eq!(table_writer.column_names(), ["id", "price", "qty"]);
+    assert_eq!(
+        table_writer.column_types(),
+        [DataType::Int, DataType::Double, DataType::Int]
+    );
+    // the buffered row gets a null qty
+    assert_eq!(table_writer.size(), 1);
+    table_writer
+        .append_named_row([("id", 2.into()), ("qty", 5.into())])
+        .await
+        .unwrap();
+    let result = table_writer.flush().await.unwrap();
+    assert_eq!(result.unwrap(), Int::new(2).into());
+    let res = client_query
+        .run_script(
+            format!("t = select * from {TABLE} order by id; isNull(t.qty[0]) && t.qty[1] == 5 && isNull(t.price[1])")
+                .as_str(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap(), Bool::new(true).into());
+    client_query
+        .run_script(format!("undef(`{TABLE}, SHARED)").as_str())
+        .await
+        .unwrap();
+}
@@ -0,0 +1,75 @@
+use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+use chrono_tz::{America::New_York, Asia::Shanghai};
+use dolphindb::types::*;
+
+#[test]
+fn test_timezone_from_zoned() {
+    let utc = Utc.with_ymd_and_hms(2024, 3, 15, 1, 30, 0).unwrap();
+    let wall = NaiveDate::from_ymd_opt(2024, 3, 15)
+        .unwrap()
+        .and_hms_opt(9, 30, 0)
+        .unwrap();
+
+    assert_eq!(
+        Timestamp::from_zoned(&utc, &Shanghai),
+        Timestamp::from(wall)
+    );
+    assert_eq!(DateTime::from_zoned(&utc, &Shanghai), DateTime::from(wall));
+    assert_eq!(
+        NanoTimestamp::from_zoned(&utc.with_timezone(&New_York), &Shanghai),
+        NanoTimestamp::from(wall)
+    );
+    assert_eq!(
+        Timestamp::from_zoned(&utc, &Utc),
+        Timestamp::from(utc.naive_utc())
+    );
+}
+
+#[test]
+fn test_timezone_to_zoned() {
+    let wall = NaiveDate::from_ymd_opt(2024, 3, 15)
+        .unwrap()
+        .and_hms_milli_opt(9, 30, 0, 250)
+        .unwrap();
+
+    let zoned = Timestamp::from(wall).to_zoned(&Shanghai).unwrap();
+    assert_eq!(zoned.naive_local(), wall);
+    assert_eq!(
+        zoned.with_timezone(&Utc),
+        Utc.with_ymd_and_hms(2024, 3, 15, 1, 30, 0).unwrap() + chrono::Duration::milliseconds(250)
+    );
+    assert!(Timestamp::default().to_zoned(&Shanghai).is_none());
+
+    // skipped by daylight saving time
+    let skipped = NaiveDate::from_ymd_opt(2024, 3, 10)
+        .unwrap()
+        .and_hms_opt(2, 30, 0)
+        .unwrap();
+    assert!(DateTime::from(skipped).to_zoned(&New_York).is_none());
+
+    // ambiguous times take the earlier offset
+    let ambiguous = NaiveDate::from_ymd_opt(2024, 11, 3)
+        .unwrap()
+        .and_hms_opt(1, 30, 0)
+        .unwrap();
+    let zoned = DateTime::from(ambiguous).to_zoned(&New_York).unwrap();
+    assert_eq!(
+        zoned.offset().to_string(),
+        New_York
+            .offset_from_utc_datetime(&(ambiguous + chrono::Duration::hours(4)))
+            .to_string()
+    );
+}
+
+#[test]
+fn test_timezone_primitive_type() {
+    let zoned = FixedOffset::east_opt(8 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(2024, 3, 15, 9, 30, 0)
+        .unwrap();
+
+    match PrimitiveType::from(zoned.with_timezone(&Shanghai)) {
+        PrimitiveType::ZonedDateTime(dt) => assert_eq!(dt, zoned),
+        other => panic!("unexpected {other}"),
+    }
+}
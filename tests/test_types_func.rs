@@ -612,8 +612,25 @@ mod test_types_func_type {
     // as mut
     #[test]
     fn test_types_func_type_float_as_mut() {
-        let result = Float::new(3.14f32).as_mut().unwrap();
-        assert_eq!(result, 3.14f32);
+        let result = Float::new(2.5f32).as_mut().unwrap();
+        assert_eq!(result, 2.5f32);
+    }
+
+    #[test]
+    fn test_types_func_type_int_as_mut() {
+        let mut int = Int::new(1);
+        *int.as_mut() = None;
+        assert!(int.is_null());
+        int.as_mut().replace(2);
+        assert_eq!(*int.as_ref(), Some(2));
+        assert_eq!(Int::from_raw(i32::MIN), Int::default());
+    }
+
+    // raw value
+    #[test]
+    fn test_types_func_type_float_raw_value() {
+        assert_eq!(Float::new(2.5f32).raw_value(), 2.5f32);
+        assert_eq!(Float::default().raw_value(), f32::MIN);
     }
 
     // hash
@@ -1168,9 +1185,28 @@ mod test_types_func_form {
         );
     }
 
+    #[test]
+    fn test_types_func_form_vector_as_slice() {
+        let mut vector = vector_build!(Int, Int::new(1), Int::default(), Int::new(3));
+        assert_eq!(vector.as_slice(), &[1, i32::MIN, 3]);
+        vector.as_mut_slice()[1] = 2;
+        assert_eq!(vector[1], Int::new(2));
+
+        let vector = vector_build!(Date, Date::from_ymd(1970, 1, 2).unwrap(), Date::default());
+        assert_eq!(vector.as_slice(), &[1, i32::MIN]);
+    }
+
+    #[test]
+    fn test_types_func_form_vector_raw_vec() {
+        let vector = Vector::<Double>::from_raw_vec(vec![1.5, f64::MIN]);
+        assert_eq!(vector[0], Double::new(1.5));
+        assert!(vector[1].is_null());
+        assert_eq!(vector.into_raw_vec(), vec![1.5, f64::MIN]);
+    }
+
     #[test]
     fn test_types_func_form_vector_primitive() {
-        let mut vector = Vector::<DolphinString>::from_raw(&vec!["1", "2", "3"]);
+        let mut vector = Vector::<DolphinString>::from_raw(&["1", "2", "3"]);
         assert_eq!(
             vector,
             vector_build!(
@@ -1969,10 +2005,232 @@ mod test_types_func_form {
         );
     }
 
+    #[test]
+    fn test_types_func_form_table_row() {
+        let result = table_build!(
+            String::from("a") => vector_build!(Int, Int::new(1i32), Int::default()),
+            String::from("b") => vector_build!(
+                DolphinString,
+                DolphinString::new("x".into()),
+                DolphinString::new("y".into())
+            )
+        );
+        let row = result.row(1).unwrap();
+        assert_eq!(row.index(), 1);
+        assert_eq!(row.len(), 2);
+        assert_eq!(row.get::<String>("b").unwrap(), "y");
+        assert_eq!(row.get::<String>(1).unwrap(), "y");
+        assert_eq!(row.get::<Option<i32>>("a").unwrap(), None);
+        assert!(row.get::<i32>("a").is_err());
+        assert!(row.get::<i32>("c").is_err());
+        assert!(result.row(2).is_none());
+    }
+
+    #[test]
+    fn test_types_func_form_table_rows() {
+        let result = table_build!(
+            String::from("a") => vector_build!(Int, Int::new(1i32), Int::new(2i32), Int::new(3i32))
+        );
+        let rows = result.rows();
+        assert_eq!(rows.len(), 3);
+        let values = rows
+            .map(|row| row.get::<i32>("a").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![1, 2, 3]);
+        assert_eq!(result.rows().next_back().unwrap().index(), 2);
+    }
+
+    #[test]
+    fn test_types_func_form_table_cell() {
+        let result = table_build!(
+            String::from("a") => vector_build!(Int, Int::new(1i32), Int::new(2i32))
+        );
+        assert_eq!(result.cell(1, "a"), Some(Int::new(2i32).into()));
+        assert_eq!(result.cell(0, 0), Some(Int::new(1i32).into()));
+        assert!(result.cell(2, "a").is_none());
+        assert!(result.cell(0, "b").is_none());
+        assert!(result.cell(0, 1).is_none());
+    }
+
+    #[test]
+    fn test_types_func_form_vector_impl_manipulation() {
+        let vector: VectorImpl =
+            vector_build!(Int, Int::new(3i32), Int::default(), Int::new(1i32)).into();
+        assert_eq!(
+            vector.take(&[2, 0]).unwrap(),
+            vector_build!(Int, Int::new(1i32), Int::new(3i32)).into()
+        );
+        assert!(vector.take(&[3]).is_err());
+        assert_eq!(
+            vector.slice(1..).unwrap(),
+            vector_build!(Int, Int::default(), Int::new(1i32)).into()
+        );
+        assert!(vector.slice(..4).is_err());
+        assert!(vector.slice(..=usize::MAX).is_err());
+        assert!(vector
+            .slice((
+                std::ops::Bound::Excluded(usize::MAX),
+                std::ops::Bound::Unbounded
+            ))
+            .is_err());
+        assert_eq!(
+            vector.filter(&[true, true, false]).unwrap(),
+            vector_build!(Int, Int::new(3i32), Int::default()).into()
+        );
+        assert_eq!(
+            vector.sort(true).unwrap(),
+            vector_build!(Int, Int::default(), Int::new(1i32), Int::new(3i32)).into()
+        );
+        assert_eq!(vector.argsort(false).unwrap(), vec![0, 2, 1]);
+        assert_eq!(vector.concat(&vector).unwrap().len(), 6);
+
+        let symbols: VectorImpl = vector_build!(Symbol, Symbol::new("a".into())).into();
+        assert!(vector.concat(&symbols).is_err());
+        assert_eq!(
+            symbols.concat(&symbols).unwrap().data_type(),
+            DataType::Symbol
+        );
+
+        let array: VectorImpl = array_vector_build!(i32, vec![1, 2], vec![], vec![3]).into();
+        assert_eq!(
+            array.take(&[2, 0]).unwrap(),
+            array_vector_build!(i32, vec![3], vec![1, 2]).into()
+        );
+        assert_eq!(
+            array.concat(&array.slice(..1).unwrap()).unwrap(),
+            array_vector_build!(i32, vec![1, 2], vec![], vec![3], vec![1, 2]).into()
+        );
+        assert!(array.argsort(true).is_err());
+    }
+
+    #[test]
+    fn test_types_func_form_table_manipulation() {
+        let table = table_build!(
+            String::from("a") => vector_build!(Int, Int::new(2i32), Int::new(1i32), Int::new(3i32)),
+            String::from("b") => vector_build!(
+                Symbol,
+                Symbol::new("x".into()),
+                Symbol::new("y".into()),
+                Symbol::default()
+            )
+        );
+
+        let selected = table.select(["b"]).unwrap();
+        assert_eq!(selected.column_names(), &vec![String::from("b")]);
+        assert_eq!(selected.len(), 3);
+        assert!(table.select(["c"]).is_err());
+
+        let sliced = table.slice(1..2).unwrap();
+        assert_eq!(sliced.cell(0, "b"), Some(Symbol::new("y".into()).into()));
+
+        let filtered = table
+            .filter(|row| row.get::<i32>("a").unwrap() > 1)
+            .unwrap();
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(filtered.cell(1, "b"), Some(Symbol::default().into()));
+        assert_eq!(
+            table.filter_mask(&[false, true, false]).unwrap(),
+            table.slice(1..2).unwrap()
+        );
+
+        let sorted = table.sort_by("a", false).unwrap();
+        assert_eq!(
+            *sorted.get_columns_by_name("a").unwrap(),
+            vector_build!(Int, Int::new(3i32), Int::new(2i32), Int::new(1i32)).into()
+        );
+
+        let concatenated = table.concat(&table).unwrap();
+        assert_eq!(concatenated.len(), 6);
+        assert!(table.concat(&selected).is_err());
+    }
+
+    #[test]
+    fn test_types_func_form_table_display() {
+        let mut array = IntArrayVector::new();
+        array.push(vec![1, i32::MIN]);
+        array.push(vec![]);
+        array.push(vec![3]);
+        let table = table_build!(
+            String::from("a") => vector_build!(Int, Int::new(1i32), Int::default(), Int::new(3i32)),
+            String::from("b") => vector_build!(
+                Symbol,
+                Symbol::new("x, y".into()),
+                Symbol::new("z".into()),
+                Symbol::new("a long symbol value".into())
+            ),
+            String::from("c") => VectorImpl::from(array),
+            String::from("d") => vector_build!(
+                Any,
+                Any::new(Int::default().into()),
+                Any::new(vector_build!(Int, Int::new(1i32), Int::new(2i32)).into()),
+                Any::new(DolphinString::new("s".into()).into())
+            )
+        );
+
+        let lines = table.to_string();
+        let lines = lines.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[3],
+            "| 1    | x, y                | [1, null] | null   |"
+        );
+        assert_eq!(
+            lines[5],
+            "| null | z                   | []        | [1, 2] |"
+        );
+        assert_eq!(
+            lines[7],
+            "| 3    | a long symbol value | [3]       | s      |"
+        );
+
+        let mut options = DisplayOptions::new();
+        options
+            .with_max_rows(Some(2))
+            .with_max_columns(Some(3))
+            .with_max_width(Some(6))
+            .with_null("");
+        let lines = table.display_with(&options).to_string();
+        let lines = lines.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1], "| a   | b      | ... | d   |");
+        assert_eq!(lines[3], "| 1   | x, y   | ... |     |");
+        assert_eq!(lines[5], "| ... | ...    | ... | ... |");
+        assert_eq!(lines[7], "| 3   | a l... | ... | s   |");
+        assert_eq!(lines[9], "[3 rows x 4 columns]");
+
+        let column = table.get_columns_by_name("a").unwrap();
+        assert_eq!(column.display_with(&options).to_string(), "[1, ..., 3]");
+
+        let mut options = DisplayOptions::new();
+        options.with_max_width(Some(2));
+        let lines = table.display_with(&options).to_string();
+        let lines = lines.lines().collect::<Vec<_>>();
+        assert_eq!(lines[7], "| 3  | a  | [3 | s  |");
+    }
+
     // data form
     #[test]
     fn test_types_func_form_data_form_try_from_error() {
         let x: Result<DataForm, _> = 7u8.try_into();
         assert!(x.is_err());
     }
+
+    // opaque
+    #[test]
+    fn test_types_func_form_function_def() {
+        let func = FunctionDef::system("add");
+        assert_eq!(func.kind(), FunctionDef::SYSTEM);
+        assert_eq!(func.body(), "add");
+        let c: ConstantImpl = func.clone().into();
+        assert_eq!(c.data_form(), DataForm::Scalar);
+        assert_eq!(c.data_type(), DataType::FunctionDef);
+        assert_eq!(c.as_functiondef().unwrap(), &func);
+        assert_eq!(FunctionDef::try_from(c).unwrap(), func);
+    }
+
+    #[test]
+    fn test_types_func_form_opaque() {
+        let code = Opaque::new(DataType::Code, "< select * from t >").unwrap();
+        assert_eq!(code.data_type(), DataType::Code);
+        assert_eq!(code.content(), "< select * from t >");
+        assert!(Opaque::new(DataType::Int, "1").is_err());
+    }
 }